@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use crate::api::problem::Problem;
+use crate::api::{internal_error, Status};
+use crate::domain::{authorize, entities::Role};
+use crate::repositories::pokemon::Repository;
+
+/// The least role allowed on a route. Reading stays public, importing is
+/// reserved to administrators and every other change needs an editor, even on
+/// a route that does not exist.
+pub(super) fn required_role(method: &str, route: &str) -> Option<Role> {
+    match (method, route) {
+        ("GET", _) => None,
+        (_, "/import") => Some(Role::Admin),
+        _ => Some(Role::Editor),
+    }
+}
+
+/// Checks the key of a request against the role its route requires, returning
+/// the name of the key when one was presented, or the response refusing it.
+pub(super) fn check(
+    repo: &Arc<dyn Repository>,
+    req: &rouille::Request,
+    route: &str,
+) -> Result<Option<String>, rouille::Response> {
+    let req = authorize::Request {
+        secret: secret(req),
+        required: required_role(req.method(), route),
+    };
+
+    match authorize::execute(repo.clone(), req) {
+        Ok(key) => Ok(key.map(|key| key.name)),
+        Err(authorize::Error::Unauthenticated(reason)) => Err(rouille::Response::from(
+            Problem::new(Status::Unauthorized).with_detail(String::from(reason)),
+        )
+        .with_additional_header("WWW-Authenticate", "Bearer")),
+        Err(authorize::Error::Forbidden {
+            name,
+            role,
+            required,
+        }) => Err(rouille::Response::from(
+            Problem::new(Status::Forbidden).with_detail(format!(
+                "The API key '{}' has the role '{}' but this operation requires the role '{}'",
+                name,
+                String::from(role),
+                String::from(required)
+            )),
+        )),
+        Err(authorize::Error::Unknown(e)) => Err(internal_error(e)),
+    }
+}
+
+/// The key sent as `Authorization: Bearer <key>` or as `X-Api-Key: <key>`.
+fn secret(req: &rouille::Request) -> Option<String> {
+    req.header("Authorization")
+        .and_then(|value| value.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+        .map(|(_, key)| key)
+        .or_else(|| req.header("X-Api-Key"))
+        .map(|key| String::from(key.trim()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::metrics::UNMATCHED;
+    use crate::domain::entities::ApiKey;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn request(method: &str, headers: &[(&str, &str)]) -> rouille::Request {
+        rouille::Request::fake_http(
+            method,
+            "/25",
+            headers
+                .iter()
+                .map(|(name, value)| (String::from(*name), String::from(*value)))
+                .collect(),
+            vec![],
+        )
+    }
+
+    fn repo() -> Arc<dyn Repository> {
+        let repo = InMemoryRepository::new();
+        let _ = repo.insert_key(ApiKey::editor());
+        Arc::new(repo)
+    }
+
+    #[test]
+    fn it_should_require_a_role_on_every_change_only() {
+        assert_eq!(required_role("GET", "/{number}"), None);
+        assert_eq!(required_role("DELETE", "/{number}"), Some(Role::Editor));
+        assert_eq!(required_role("POST", "/import"), Some(Role::Admin));
+        assert_eq!(required_role("POST", UNMATCHED), Some(Role::Editor));
+        assert_eq!(required_role("GET", UNMATCHED), None);
+    }
+
+    #[test]
+    fn it_should_answer_401_with_a_challenge_when_the_key_is_missing() {
+        match check(&repo(), &request("DELETE", &[]), "/{number}") {
+            Err(res) => {
+                assert_eq!(res.status_code, 401);
+                assert!(res
+                    .headers
+                    .iter()
+                    .any(|(name, value)| name == "WWW-Authenticate" && value == "Bearer"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_accept_the_key_from_either_header() {
+        for header in [
+            ("Authorization", "Bearer pdx_editor"),
+            ("X-Api-Key", "pdx_editor"),
+        ] {
+            match check(&repo(), &request("DELETE", &[header]), "/{number}") {
+                Ok(Some(name)) => assert_eq!(name, "ci"),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_answer_403_when_the_role_is_too_low() {
+        let req = request("POST", &[("X-Api-Key", "pdx_editor")]);
+
+        match check(&repo(), &req, "/import") {
+            Err(res) => assert_eq!(res.status_code, 403),
+            _ => unreachable!(),
+        }
+    }
+}
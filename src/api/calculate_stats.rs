@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use crate::api::{internal_error, not_found, problem::Problem, Stats, Status};
+use crate::domain::calculate_stats;
+use crate::domain::entities::StatValues;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+
+pub const DEFAULT_LEVEL: u8 = 50;
+pub const DEFAULT_NATURE: &str = "Hardy";
+pub const DEFAULT_IV: u16 = 31;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    level: u8,
+    nature: String,
+    stats: Stats,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16, req: &rouille::Request) -> rouille::Response {
+    let domain_req = match parse_request(number, req) {
+        Ok(domain_req) => domain_req,
+        Err(detail) => return rouille::Response::from(Problem::malformed(detail)),
+    };
+
+    let form = domain_req.form.clone();
+
+    match calculate_stats::execute(repo, domain_req) {
+        Ok(res) => rouille::Response::json(&Response {
+            number: res.number,
+            name: res.name,
+            level: res.level,
+            nature: res.nature,
+            stats: Stats::from(res.stats),
+        }),
+        Err(calculate_stats::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(calculate_stats::Error::NotFound) => not_found(number, form.as_deref()),
+        Err(calculate_stats::Error::NoBaseStats) => rouille::Response::from(
+            Problem::new(Status::NotFound)
+                .with_detail(format!("The base stats of Pokemon {} are unknown", number)),
+        ),
+        Err(calculate_stats::Error::Unknown(e)) => internal_error(e),
+    }
+}
+
+/// Parses the query string, IVs and EVs being given as six comma separated
+/// values in HP/Atk/Def/SpA/SpD/Spe order.
+fn parse_request(number: u16, req: &rouille::Request) -> Result<calculate_stats::Request, String> {
+    fn stats(req: &rouille::Request, name: &str, default: u16) -> Result<StatValues, String> {
+        let value = match req.get_param(name) {
+            Some(value) => value,
+            None => return Ok(StatValues::uniform(default)),
+        };
+
+        match value
+            .split(',')
+            .map(|v| v.trim().parse::<u16>())
+            .collect::<Result<Vec<_>, _>>()
+            .map(<[u16; 6]>::try_from)
+        {
+            Ok(Ok(values)) => Ok(StatValues::from(values)),
+            _ => Err(format!(
+                "the query parameter '{}' must be six comma separated positive integers",
+                name
+            )),
+        }
+    }
+
+    let level = match req.get_param("level") {
+        Some(level) => level
+            .parse::<u8>()
+            .map_err(|_| String::from("the query parameter 'level' must be a positive integer"))?,
+        None => DEFAULT_LEVEL,
+    };
+
+    Ok(calculate_stats::Request {
+        number,
+        form: req.get_param("form"),
+        level,
+        nature: req
+            .get_param("nature")
+            .unwrap_or_else(|| String::from(DEFAULT_NATURE)),
+        ivs: stats(req, "ivs", DEFAULT_IV)?,
+        evs: stats(req, "evs", 0)?,
+    })
+}
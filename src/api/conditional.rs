@@ -0,0 +1,101 @@
+//! Conditional requests. A Pokemon is tagged with its revision, so that a
+//! client can revalidate its copy with `If-None-Match` and make sure it only
+//! changes the revision it has seen with `If-Match`. Revisions carry on from
+//! the audit log when a purged Pokemon is created again, so that a tag never
+//! matches a former incarnation of it.
+
+use crate::api::problem::Problem;
+use crate::api::Status;
+
+/// The strong entity tag of a revision.
+pub(super) fn etag(revision: u32) -> String {
+    format!("\"{}\"", revision)
+}
+
+/// Whether `If-None-Match` lists the tag of the revision, or `*`, the copy of
+/// the client being then up to date. Tags are compared weakly, as RFC 9110
+/// requires for this header.
+pub(super) fn is_fresh(req: &rouille::Request, revision: u32) -> bool {
+    let Some(header) = req.header("If-None-Match") else {
+        return false;
+    };
+
+    let tag = etag(revision);
+    header.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == tag
+    })
+}
+
+/// The revisions `If-Match` accepts, any of them matching, `None` when any
+/// revision will do. Only strong tags are compared; a header without any can
+/// match no revision, which is then reported as stale.
+pub(super) fn expected_revisions(req: &rouille::Request) -> Option<Vec<u32>> {
+    let header = req.header("If-Match")?.trim();
+    if header == "*" {
+        return None;
+    }
+
+    Some(
+        header
+            .split(',')
+            .map(str::trim)
+            .filter_map(|tag| tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok())
+            .collect(),
+    )
+}
+
+/// Answers that the copy of the client is up to date.
+pub(super) fn not_modified(revision: u32) -> rouille::Response {
+    rouille::Response::from(Status::NotModified).with_additional_header("ETag", etag(revision))
+}
+
+/// Answers that the Pokemon has changed since the client last saw it.
+pub(super) fn precondition_failed(revision: u32) -> rouille::Response {
+    rouille::Response::from(
+        Problem::new(Status::PreconditionFailed).with_detail(format!(
+            "The Pokemon has changed, it is now at revision {}",
+            revision
+        )),
+    )
+    .with_additional_header("ETag", etag(revision))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn request(header: &str, value: &str) -> rouille::Request {
+        rouille::Request::fake_http(
+            "GET",
+            "/25",
+            vec![(String::from(header), String::from(value))],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn it_should_be_fresh_when_if_none_match_lists_the_revision() {
+        assert!(is_fresh(&request("If-None-Match", "\"1\", \"2\""), 2));
+        assert!(is_fresh(&request("If-None-Match", "W/\"2\""), 2));
+        assert!(is_fresh(&request("If-None-Match", "*"), 2));
+        assert!(!is_fresh(&request("If-None-Match", "\"1\""), 2));
+    }
+
+    #[test]
+    fn it_should_expect_the_revisions_of_the_strong_tags_in_if_match() {
+        assert_eq!(
+            expected_revisions(&request("If-Match", "\"3\"")),
+            Some(vec![3])
+        );
+        assert_eq!(
+            expected_revisions(&request("If-Match", "\"1\", W/\"2\", \"3\"")),
+            Some(vec![1, 3])
+        );
+        assert_eq!(expected_revisions(&request("If-Match", "*")), None);
+        assert_eq!(
+            expected_revisions(&request("If-Match", "W/\"3\"")),
+            Some(vec![])
+        );
+        assert_eq!(expected_revisions(&request("Accept", "*/*")), None);
+    }
+}
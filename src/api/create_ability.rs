@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crate::api::{internal_error, problem::Problem, Status};
+use crate::domain::create_ability;
+use crate::repositories::pokemon::Repository;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub(super) struct Response {
+    pub(super) name: String,
+    pub(super) description: String,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_ability::Request {
+            name: req.name,
+            description: req.description,
+        },
+        Err(e) => return rouille::Response::from(Problem::malformed(e.to_string())),
+    };
+    let name = req.name.clone();
+
+    match create_ability::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            name: res.name,
+            description: res.description,
+        }),
+        Err(create_ability::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(create_ability::Error::Conflict) => rouille::Response::from(
+            Problem::new(Status::Conflict)
+                .with_detail(format!("An ability named '{}' already exists", name)),
+        ),
+        Err(create_ability::Error::Unknown(e)) => internal_error(e),
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use crate::api::{internal_error, problem::Problem, Status, Trigger};
+use crate::domain::create_evolution;
+use crate::repositories::pokemon::Repository;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+struct Response {
+    from: u16,
+    to: u16,
+    trigger: Trigger,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    to: u16,
+    trigger: Trigger,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_evolution::Request {
+            from: number,
+            to: req.to,
+            trigger: req.trigger.into(),
+        },
+        Err(e) => return rouille::Response::from(Problem::malformed(e.to_string())),
+    };
+    let to = req.to;
+
+    match create_evolution::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            from: res.from,
+            to: res.to,
+            trigger: Trigger::from(res.trigger),
+        }),
+        Err(create_evolution::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(create_evolution::Error::NotFound) => rouille::Response::from(
+            Problem::new(Status::NotFound)
+                .with_detail(format!("There is no Pokemon with number {}", number)),
+        ),
+        Err(create_evolution::Error::Conflict) => {
+            rouille::Response::from(Problem::new(Status::Conflict).with_detail(format!(
+                "Pokemon {} already evolves into Pokemon {}",
+                number, to
+            )))
+        }
+        Err(create_evolution::Error::Unknown(e)) => internal_error(e),
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use crate::api::{create_move, internal_error, problem::Problem, Status};
+use crate::domain::create_learnset_entry::{self, LearnsetResponse};
+use crate::repositories::pokemon::Repository;
+use serde::{Deserialize, Serialize};
+
+/// A move of a learnset, flattened with its details.
+#[derive(Serialize)]
+pub(super) struct Response {
+    #[serde(flatten)]
+    details: create_move::Response,
+    method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    level: Option<u8>,
+    generation: u8,
+}
+
+impl From<LearnsetResponse> for Response {
+    fn from(val: LearnsetResponse) -> Self {
+        Self {
+            details: create_move::Response::from(val.details),
+            method: val.method,
+            level: val.level,
+            generation: val.generation,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Request {
+    #[serde(rename = "move")]
+    move_name: String,
+    method: String,
+    level: Option<u8>,
+    generation: u8,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_learnset_entry::Request {
+            number,
+            move_name: req.move_name,
+            method: req.method,
+            level: req.level,
+            generation: req.generation,
+        },
+        Err(e) => return rouille::Response::from(Problem::malformed(e.to_string())),
+    };
+    let (move_name, generation) = (req.move_name.clone(), req.generation);
+
+    match create_learnset_entry::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response::from(res)),
+        Err(create_learnset_entry::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(create_learnset_entry::Error::NotFound) => rouille::Response::from(
+            Problem::new(Status::NotFound)
+                .with_detail(format!("There is no Pokemon with number {}", number)),
+        ),
+        Err(create_learnset_entry::Error::Conflict) => {
+            rouille::Response::from(Problem::new(Status::Conflict).with_detail(format!(
+                "Pokemon {} already learns {} this way in generation {}",
+                number, move_name, generation
+            )))
+        }
+        Err(create_learnset_entry::Error::Unknown(e)) => internal_error(e),
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::api::{internal_error, problem::Problem, Status};
+use crate::domain::create_move::{self, MoveResponse};
+use crate::repositories::pokemon::Repository;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub(super) struct Response {
+    name: String,
+    #[serde(rename = "type")]
+    move_type: String,
+    category: String,
+    power: Option<u8>,
+    accuracy: Option<u8>,
+    pp: u8,
+}
+
+impl From<MoveResponse> for Response {
+    fn from(val: MoveResponse) -> Self {
+        Self {
+            name: val.name,
+            move_type: val.move_type,
+            category: val.category,
+            power: val.power,
+            accuracy: val.accuracy,
+            pp: val.pp,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+    #[serde(rename = "type")]
+    move_type: String,
+    category: String,
+    power: Option<u8>,
+    accuracy: Option<u8>,
+    pp: u8,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_move::Request {
+            name: req.name,
+            move_type: req.move_type,
+            category: req.category,
+            power: req.power,
+            accuracy: req.accuracy,
+            pp: req.pp,
+        },
+        Err(e) => return rouille::Response::from(Problem::malformed(e.to_string())),
+    };
+    let name = req.name.clone();
+
+    match create_move::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response::from(res)),
+        Err(create_move::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(create_move::Error::Conflict) => rouille::Response::from(
+            Problem::new(Status::Conflict)
+                .with_detail(format!("A move named '{}' already exists", name)),
+        ),
+        Err(create_move::Error::Unknown(e)) => internal_error(e),
+    }
+}
@@ -1,14 +1,21 @@
 use std::sync::Arc;
 
+use crate::api::conditional;
+use crate::api::{internal_error, not_found, problem::Problem, Slot, Stats, Status};
 use crate::domain::create_pokemon;
-use crate::{api::Status, repositories::pokemon::Repository};
+use crate::domain::entities::{Actor, SlotValue, StatValues};
+use crate::repositories::pokemon::Repository;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize)]
 struct Response {
     number: u16,
+    form: String,
     name: String,
     types: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<Stats>,
+    abilities: Vec<Slot>,
 }
 
 #[derive(Deserialize)]
@@ -16,30 +23,114 @@ struct Request {
     number: u16,
     name: String,
     types: Vec<String>,
+    stats: Option<Stats>,
+    #[serde(default)]
+    abilities: Vec<Slot>,
 }
 
-pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+/// An alternate form, the number being taken from the path.
+#[derive(Deserialize)]
+struct FormRequest {
+    form: String,
+    name: String,
+    types: Vec<String>,
+    stats: Option<Stats>,
+    #[serde(default)]
+    abilities: Vec<Slot>,
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    req: &rouille::Request,
+    actor: &Actor,
+) -> rouille::Response {
     let req = match rouille::input::json_input::<Request>(req) {
         Ok(req) => create_pokemon::Request {
             number: req.number,
+            form: None,
             name: req.name,
             types: req.types,
+            stats: req.stats.map(StatValues::from),
+            abilities: req.abilities.into_iter().map(SlotValue::from).collect(),
         },
-        _ => return rouille::Response::from(Status::BadRequest),
+        Err(e) => return rouille::Response::from(Problem::malformed(e.to_string())),
+    };
+
+    execute(repo, req, actor)
+}
+
+pub fn serve_form(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    req: &rouille::Request,
+    actor: &Actor,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<FormRequest>(req) {
+        Ok(req) => create_pokemon::Request {
+            number,
+            form: Some(req.form),
+            name: req.name,
+            types: req.types,
+            stats: req.stats.map(StatValues::from),
+            abilities: req.abilities.into_iter().map(SlotValue::from).collect(),
+        },
+        Err(e) => return rouille::Response::from(Problem::malformed(e.to_string())),
+    };
+
+    execute(repo, req, actor)
+}
+
+fn execute(
+    repo: Arc<dyn Repository>,
+    req: create_pokemon::Request,
+    actor: &Actor,
+) -> rouille::Response {
+    let number = req.number;
+    let (conflict, deleted) = match &req.form {
+        Some(form) => (
+            format!("The form '{}' of Pokemon {} already exists", form, number),
+            format!(
+                "The form '{}' of Pokemon {} is in the trash, restore or purge it first",
+                form, number
+            ),
+        ),
+        None => (
+            format!("A Pokemon with number {} already exists", number),
+            format!(
+                "Pokemon {} is in the trash, restore or purge it first",
+                number
+            ),
+        ),
     };
 
-    match create_pokemon::execute(repo, req) {
+    match create_pokemon::execute(repo, req, actor) {
         Ok(create_pokemon::InsertResponse {
             number,
+            form,
             name,
             types,
+            stats,
+            abilities,
+            revision,
         }) => rouille::Response::json(&Response {
             number,
+            form,
             name,
             types,
-        }),
-        Err(create_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
-        Err(create_pokemon::Error::Conflict) => rouille::Response::from(Status::Conflict),
-        Err(create_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+            stats: stats.map(Stats::from),
+            abilities: abilities.into_iter().map(Slot::from).collect(),
+        })
+        .with_additional_header("ETag", conditional::etag(revision)),
+        Err(create_pokemon::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(create_pokemon::Error::NotFound) => not_found(number, None),
+        Err(create_pokemon::Error::Conflict) => {
+            rouille::Response::from(Problem::new(Status::Conflict).with_detail(conflict))
+        }
+        Err(create_pokemon::Error::Deleted) => {
+            rouille::Response::from(Problem::new(Status::Conflict).with_detail(deleted))
+        }
+        Err(create_pokemon::Error::Unknown(e)) => internal_error(e),
     }
 }
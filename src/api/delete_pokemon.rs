@@ -1,22 +1,32 @@
 use std::sync::Arc;
 
+use crate::api::conditional;
+use crate::api::{internal_error, not_found, problem::Problem, Status};
 use crate::domain::delete_pokemon;
-use crate::{api::Status, repositories::pokemon::Repository};
-use serde::Serialize;
+use crate::domain::entities::Actor;
+use crate::repositories::pokemon::Repository;
 
-#[derive(Serialize)]
-struct Response {
+pub fn serve(
+    repo: Arc<dyn Repository>,
     number: u16,
-    name: String,
-    types: Vec<String>,
-}
-
-pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
-    let req = delete_pokemon::Request { number };
-    match delete_pokemon::execute(repo, req) {
+    form: Option<String>,
+    req: &rouille::Request,
+    actor: &Actor,
+) -> rouille::Response {
+    let req = delete_pokemon::Request {
+        number,
+        form: form.clone(),
+        revisions: conditional::expected_revisions(req),
+    };
+    match delete_pokemon::execute(repo, req, actor) {
         Ok(()) => rouille::Response::from(Status::Ok),
-        Err(delete_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
-        Err(delete_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
-        Err(delete_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(delete_pokemon::Error::Unknown(e)) => internal_error(e),
+        Err(delete_pokemon::Error::NotFound) => not_found(number, form.as_deref()),
+        Err(delete_pokemon::Error::PreconditionFailed(revision)) => {
+            conditional::precondition_failed(revision)
+        }
+        Err(delete_pokemon::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
     }
 }
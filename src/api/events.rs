@@ -0,0 +1,229 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::api::fetch_history::Snapshot;
+use crate::api::problem::Problem;
+use crate::api::Status;
+use crate::domain::fetch_pokemon::RetrieveResponse;
+use crate::events::Events;
+
+pub const CONTENT_TYPE: &str = "text/event-stream";
+
+/// How long a stream stays silent before a comment is sent, which keeps the
+/// proxies from closing it and tells when the client went away.
+const HEARTBEAT: Duration = Duration::from_secs(15);
+
+/// How long a stream lasts before it ends, the client then reconnecting with
+/// the `Last-Event-ID` it received, so that a worker is never held for good.
+const LIFETIME: Duration = Duration::from_secs(10 * 60);
+
+/// The clients streaming the events. A stream holds one of the workers of the
+/// server for as long as it lasts, so only half of them can be taken by the
+/// streams, the others being left to the requests, though a single worker
+/// still takes a stream.
+pub(super) struct Streams {
+    events: Arc<Events>,
+    open: Arc<AtomicUsize>,
+    max: usize,
+}
+
+impl Streams {
+    pub(super) fn new(events: Arc<Events>, workers: usize) -> Self {
+        Self {
+            events,
+            open: Arc::new(AtomicUsize::new(0)),
+            max: (workers / 2).max(1),
+        }
+    }
+
+    /// Opens a stream resuming after `last_event_id`, unless there are
+    /// already as many streams as allowed.
+    fn open(&self, last_event_id: Option<u64>) -> Option<Stream> {
+        self.open
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |open| {
+                (open < self.max).then_some(open + 1)
+            })
+            .ok()?;
+
+        Some(Stream {
+            events: self.events.clone(),
+            after: self.events.resume(last_event_id),
+            deadline: Instant::now() + LIFETIME,
+            open: self.open.clone(),
+        })
+    }
+}
+
+/// Streams the changes as server-sent events, from the one after the
+/// `Last-Event-ID` a reconnecting client sends, or from now on without one.
+pub(super) fn serve(streams: &Streams, req: &rouille::Request) -> rouille::Response {
+    let last_event_id = match req.header("Last-Event-ID").map(|id| id.trim().parse()) {
+        Some(Ok(id)) => Some(id),
+        Some(Err(_)) => {
+            return rouille::Response::from(Problem::malformed(String::from(
+                "the Last-Event-ID header must be the id of an event",
+            )))
+        }
+        None => None,
+    };
+
+    let Some(stream) = streams.open(last_event_id) else {
+        return rouille::Response::from(Problem::new(Status::ServiceUnavailable).with_detail(
+            format!(
+                "There are already {} clients streaming the events, try again later",
+                streams.max
+            ),
+        ))
+        .with_additional_header("Retry-After", HEARTBEAT.as_secs().to_string());
+    };
+
+    // A streamed body is only sent by chunks of 8 KiB, so the stream takes over
+    // the connection once the headers are sent, flushing every event written,
+    // and closes it when done.
+    rouille::Response {
+        status_code: 200,
+        headers: vec![
+            ("Content-Type".into(), CONTENT_TYPE.into()),
+            ("Cache-Control".into(), "no-cache".into()),
+        ],
+        data: rouille::ResponseBody::empty(),
+        upgrade: Some(Box::new(stream)),
+    }
+}
+
+struct Stream {
+    events: Arc<Events>,
+    after: u64,
+    deadline: Instant,
+    open: Arc<AtomicUsize>,
+}
+
+impl rouille::Upgrade for Stream {
+    /// Writes the events from the worker that served the request, until the
+    /// stream ends or the client goes away.
+    fn build(&mut self, mut socket: Box<dyn rouille::ReadWrite + Send>) {
+        loop {
+            let remaining = self.deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero()
+                || self
+                    .write_next(&mut socket, remaining.min(HEARTBEAT))
+                    .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+impl Stream {
+    /// Writes the events published after the last one written, waiting up to
+    /// `timeout` for one, or a heartbeat comment without any.
+    fn write_next(&mut self, out: &mut impl Write, timeout: Duration) -> io::Result<()> {
+        let batch = self.events.wait(self.after, timeout);
+        if batch.is_empty() {
+            out.write_all(b": keep-alive\n\n")?;
+        }
+        for event in batch {
+            self.after = event.id;
+            let data =
+                serde_json::to_string(&Snapshot::from(RetrieveResponse::from(event.pokemon)))?;
+            write!(
+                out,
+                "id: {}\nevent: {}\ndata: {}\n\n",
+                event.id,
+                String::from(event.kind),
+                data
+            )?;
+        }
+        out.flush()
+    }
+}
+
+impl Drop for Stream {
+    fn drop(&mut self) {
+        self.open.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::Pokemon;
+    use crate::events::EventKind;
+
+    fn request(headers: &[(&str, &str)]) -> rouille::Request {
+        rouille::Request::fake_http(
+            "GET",
+            "/events",
+            headers
+                .iter()
+                .map(|(name, value)| (String::from(*name), String::from(*value)))
+                .collect(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn it_should_write_the_events_after_the_last_one_received() {
+        let streams = Streams::new(Arc::new(Events::default()), 2);
+        let pikachu = Pokemon::pikachu().with_revision(Pokemon::FIRST_REVISION);
+        streams.events.publish(EventKind::Created, pikachu.clone());
+        streams.events.publish(EventKind::Deleted, pikachu);
+
+        let mut out = vec![];
+        match streams.open(Some(1)) {
+            Some(mut stream) => assert!(stream.write_next(&mut out, Duration::ZERO).is_ok()),
+            None => unreachable!(),
+        }
+
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "id: 2\nevent: deleted\ndata: {\"revision\":1,\"number\":25,\"form\":\"default\",\
+             \"name\":\"Pikachu\",\"types\":[\"Electric\"],\"abilities\":[]}\n\n"
+        );
+    }
+
+    #[test]
+    fn it_should_write_a_heartbeat_without_any_event() {
+        let streams = Streams::new(Arc::new(Events::default()), 2);
+
+        let mut out = vec![];
+        match streams.open(None) {
+            Some(mut stream) => assert!(stream.write_next(&mut out, Duration::ZERO).is_ok()),
+            None => unreachable!(),
+        }
+
+        assert_eq!(out, b": keep-alive\n\n");
+    }
+
+    #[test]
+    fn it_should_refuse_a_stream_once_half_of_the_workers_are_taken() {
+        let streams = Streams::new(Arc::new(Events::default()), 2);
+
+        let first = serve(&streams, &request(&[]));
+        assert_eq!(serve(&streams, &request(&[])).status_code, 503);
+        drop(first);
+        assert_eq!(serve(&streams, &request(&[])).status_code, 200);
+    }
+
+    #[test]
+    fn it_should_take_a_stream_with_a_single_worker() {
+        let streams = Streams::new(Arc::new(Events::default()), 1);
+
+        let first = serve(&streams, &request(&[]));
+        assert_eq!(first.status_code, 200);
+        assert_eq!(serve(&streams, &request(&[])).status_code, 503);
+    }
+
+    #[test]
+    fn it_should_refuse_a_last_event_id_that_is_not_a_number() {
+        let streams = Streams::new(Arc::new(Events::default()), 2);
+
+        assert_eq!(
+            serve(&streams, &request(&[("Last-Event-ID", "abc")])).status_code,
+            400
+        );
+    }
+}
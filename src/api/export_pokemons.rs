@@ -0,0 +1,74 @@
+use std::io::{self, Read};
+use std::sync::Arc;
+
+use crate::api::problem::Problem;
+use crate::domain::export_pokemons;
+use crate::repositories::pokemon::Repository;
+use tracing::{error, Span};
+
+/// Streams the export chunk by chunk. A failure midway can only be reported by
+/// cutting the response short, the status line being already sent.
+struct ExportReader {
+    export: export_pokemons::Export,
+    /// The span of the request, which is over by the time the body is read.
+    span: Span,
+    chunk: Vec<u8>,
+    position: usize,
+}
+
+impl Read for ExportReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let _span = self.span.enter();
+        while self.position == self.chunk.len() {
+            match self.export.next() {
+                Some(Ok(chunk)) => {
+                    self.chunk = chunk.into_bytes();
+                    self.position = 0;
+                }
+                Some(Err(e)) => {
+                    error!(error = %e, "the export has been interrupted");
+                    return Err(io::Error::other(e));
+                }
+                None => return Ok(0),
+            }
+        }
+
+        let len = buf.len().min(self.chunk.len() - self.position);
+        buf[..len].copy_from_slice(&self.chunk[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let format = req
+        .get_param("format")
+        .unwrap_or_else(|| String::from("json"));
+    let content_type = match format.as_str() {
+        "json" => "application/json",
+        "jsonl" => "application/jsonl",
+        "csv" => "text/csv; charset=utf-8",
+        _ => "application/sql",
+    };
+    let disposition = format!("attachment; filename=\"pokedex.{}\"", format);
+
+    match export_pokemons::execute(repo, export_pokemons::Request { format }) {
+        Ok(export) => rouille::Response {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".into(), content_type.into()),
+                ("Content-Disposition".into(), disposition.into()),
+            ],
+            data: rouille::ResponseBody::from_reader(ExportReader {
+                export,
+                span: Span::current(),
+                chunk: vec![],
+                position: 0,
+            }),
+            upgrade: None,
+        },
+        Err(export_pokemons::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+    }
+}
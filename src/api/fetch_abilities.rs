@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use crate::api::create_ability::Response;
+use crate::api::internal_error;
+use crate::domain::fetch_abilities;
+use crate::repositories::pokemon::Repository;
+
+pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_abilities::execute(repo) {
+        Ok(abilities) => rouille::Response::json(
+            &abilities
+                .into_iter()
+                .map(|ability| Response {
+                    name: ability.name,
+                    description: ability.description,
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Err(fetch_abilities::Error::Unknown(e)) => internal_error(e),
+    }
+}
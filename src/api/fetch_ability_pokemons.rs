@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::api::{internal_error, problem::Problem, Status};
+use crate::domain::fetch_ability_pokemons;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    form: String,
+    name: String,
+    slot: String,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, name: String) -> rouille::Response {
+    let req = fetch_ability_pokemons::Request { name: name.clone() };
+
+    match fetch_ability_pokemons::execute(repo, req) {
+        Ok(pokemons) => rouille::Response::json(
+            &pokemons
+                .into_iter()
+                .map(|p| Response {
+                    number: p.number,
+                    form: p.form,
+                    name: p.name,
+                    slot: p.slot,
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Err(fetch_ability_pokemons::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(fetch_ability_pokemons::Error::NotFound) => rouille::Response::from(
+            Problem::new(Status::NotFound)
+                .with_detail(format!("There is no ability named '{}'", name)),
+        ),
+        Err(fetch_ability_pokemons::Error::Unknown(e)) => internal_error(e),
+    }
+}
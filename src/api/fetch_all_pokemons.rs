@@ -1,29 +1,100 @@
 use std::sync::Arc;
 
+use crate::api::{internal_error, problem::Problem, Slot, Stats};
 use crate::domain::fetch_all_pokemons;
-use crate::{api::Status, repositories::pokemon::Repository};
+use crate::repositories::pokemon::Repository;
 use serde::Serialize;
 
+pub const DEFAULT_LIMIT: u32 = 100;
+
 #[derive(Serialize)]
 struct Response {
     number: u16,
     name: String,
     types: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<Stats>,
+    abilities: Vec<Slot>,
 }
 
-pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
-    match fetch_all_pokemons::execute(repo) {
-        Ok(res) => rouille::Response::json(
-            &res.into_iter()
-                .map(|p| Response {
-                    number: p.number,
-                    name: p.name,
-                    types: p.types,
-                })
-                .collect::<Vec<_>>(),
-        ),
-        Err(fetch_all_pokemons::Error::Unknown) => {
-            rouille::Response::from(Status::InternalServerError)
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let domain_req = match parse_request(req) {
+        Ok(domain_req) => domain_req,
+        Err(param) => {
+            return rouille::Response::from(Problem::malformed(format!(
+                "the query parameter '{}' must be a positive integer",
+                param
+            )))
+        }
+    };
+
+    match fetch_all_pokemons::execute(repo, domain_req) {
+        Ok(page) => {
+            let response = rouille::Response::json(
+                &page
+                    .pokemons
+                    .into_iter()
+                    .map(|p| Response {
+                        number: p.number,
+                        name: p.name,
+                        types: p.types,
+                        stats: p.stats.map(Stats::from),
+                        abilities: p.abilities.into_iter().map(Slot::from).collect(),
+                    })
+                    .collect::<Vec<_>>(),
+            );
+
+            match page.next_cursor {
+                Some(cursor) => response
+                    .with_additional_header(
+                        "Link",
+                        format!("<{}>; rel=\"next\"", next_url(req, &cursor)),
+                    )
+                    .with_additional_header("X-Next-Cursor", cursor),
+                None => response,
+            }
+        }
+        Err(fetch_all_pokemons::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
         }
+        Err(fetch_all_pokemons::Error::Unknown(e)) => internal_error(e),
+    }
+}
+
+/// Parses the query string, returning the name of the first malformed parameter on error.
+fn parse_request(req: &rouille::Request) -> Result<fetch_all_pokemons::Request, &'static str> {
+    fn number<T: std::str::FromStr>(
+        req: &rouille::Request,
+        name: &'static str,
+    ) -> Result<Option<T>, &'static str> {
+        req.get_param(name)
+            .map(|value| value.parse::<T>().map_err(|_| name))
+            .transpose()
     }
+
+    Ok(fetch_all_pokemons::Request {
+        limit: Some(number(req, "limit")?.unwrap_or(DEFAULT_LIMIT)),
+        offset: number(req, "offset")?,
+        cursor: req.get_param("cursor"),
+        sort: req.get_param("sort"),
+        order: req.get_param("order"),
+        pokemon_type: req.get_param("type"),
+        min_number: number(req, "min_number")?,
+        max_number: number(req, "max_number")?,
+    })
+}
+
+/// Builds the URL of the next page, keeping every query parameter but the position.
+fn next_url(req: &rouille::Request, cursor: &str) -> String {
+    let mut params = req
+        .raw_query_string()
+        .split('&')
+        .filter(|param| {
+            !param.is_empty() && !param.starts_with("cursor=") && !param.starts_with("offset=")
+        })
+        .collect::<Vec<_>>();
+    let cursor = format!("cursor={}", cursor);
+    params.push(&cursor);
+
+    format!("{}?{}", req.url(), params.join("&"))
 }
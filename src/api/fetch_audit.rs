@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::api::{fetch_history::Event, internal_error, problem::Problem};
+use crate::domain::fetch_audit;
+use crate::repositories::pokemon::Repository;
+
+pub const DEFAULT_LIMIT: u32 = 100;
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let limit = match req.get_param("limit").map(|limit| limit.parse::<u32>()) {
+        Some(Ok(limit)) => limit,
+        Some(Err(_)) => {
+            return rouille::Response::from(Problem::malformed(String::from(
+                "the query parameter 'limit' must be a positive integer",
+            )))
+        }
+        None => DEFAULT_LIMIT,
+    };
+
+    let domain_req = fetch_audit::Request {
+        since: req.get_param("since"),
+        limit,
+    };
+
+    match fetch_audit::execute(repo, domain_req) {
+        Ok(events) => {
+            rouille::Response::json(&events.into_iter().map(Event::from).collect::<Vec<_>>())
+        }
+        Err(fetch_audit::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(fetch_audit::Error::Unknown(e)) => internal_error(e),
+    }
+}
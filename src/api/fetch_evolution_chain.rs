@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::api::{internal_error, problem::Problem, Status, Trigger};
+use crate::domain::fetch_evolution_chain::{self, EvolutionNode};
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trigger: Option<Trigger>,
+    evolves_to: Vec<Response>,
+}
+
+impl From<EvolutionNode> for Response {
+    fn from(node: EvolutionNode) -> Self {
+        Self {
+            number: node.number,
+            name: node.name,
+            trigger: node.trigger.map(Trigger::from),
+            evolves_to: node.evolves_to.into_iter().map(Response::from).collect(),
+        }
+    }
+}
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
+    let req = fetch_evolution_chain::Request { number };
+
+    match fetch_evolution_chain::execute(repo, req) {
+        Ok(root) => rouille::Response::json(&Response::from(root)),
+        Err(fetch_evolution_chain::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(fetch_evolution_chain::Error::NotFound) => rouille::Response::from(
+            Problem::new(Status::NotFound)
+                .with_detail(format!("There is no Pokemon with number {}", number)),
+        ),
+        Err(fetch_evolution_chain::Error::Unknown(e)) => internal_error(e),
+    }
+}
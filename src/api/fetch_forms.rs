@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use crate::api::{fetch_pokemon::Response, internal_error, not_found, problem::Problem};
+use crate::domain::fetch_forms;
+use crate::repositories::pokemon::Repository;
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
+    match fetch_forms::execute(repo, fetch_forms::Request { number }) {
+        Ok(forms) => rouille::Response::json(
+            &forms
+                .into_iter()
+                .map(Response::from)
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_forms::Error::Unknown(e)) => internal_error(e),
+        Err(fetch_forms::Error::NotFound) => not_found(number, None),
+        Err(fetch_forms::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+    }
+}
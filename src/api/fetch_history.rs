@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use crate::api::{fetch_pokemon, internal_error, problem::Problem, Status};
+use crate::domain::fetch_history;
+use crate::domain::fetch_pokemon::RetrieveResponse;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+
+/// A form as it was at a revision.
+#[derive(Serialize)]
+pub(super) struct Snapshot {
+    revision: u32,
+    #[serde(flatten)]
+    pokemon: fetch_pokemon::Response,
+}
+
+impl From<RetrieveResponse> for Snapshot {
+    fn from(res: RetrieveResponse) -> Self {
+        Self {
+            revision: res.revision,
+            pokemon: fetch_pokemon::Response::from(res),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct Event {
+    id: u64,
+    action: String,
+    number: u16,
+    form: String,
+    actor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    timestamp: String,
+    before: Option<Snapshot>,
+    after: Option<Snapshot>,
+}
+
+impl From<fetch_history::AuditEventResponse> for Event {
+    fn from(res: fetch_history::AuditEventResponse) -> Self {
+        Self {
+            id: res.id,
+            action: res.action,
+            number: res.number,
+            form: res.form,
+            actor: res.actor,
+            request_id: res.request_id,
+            timestamp: res.timestamp,
+            before: res.before.map(Snapshot::from),
+            after: res.after.map(Snapshot::from),
+        }
+    }
+}
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
+    match fetch_history::execute(repo, fetch_history::Request { number }) {
+        Ok(events) => {
+            rouille::Response::json(&events.into_iter().map(Event::from).collect::<Vec<_>>())
+        }
+        Err(fetch_history::Error::Unknown(e)) => internal_error(e),
+        Err(fetch_history::Error::NotFound) => rouille::Response::from(
+            Problem::new(Status::NotFound)
+                .with_detail(format!("Pokemon {} has never been changed", number)),
+        ),
+        Err(fetch_history::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::api::create_learnset_entry::Response;
+use crate::api::{internal_error, problem::Problem, Status};
+use crate::domain::fetch_learnset;
+use crate::repositories::pokemon::Repository;
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16, req: &rouille::Request) -> rouille::Response {
+    let generation = match req.get_param("generation").map(|g| g.parse::<u8>()) {
+        Some(Ok(generation)) => Some(generation),
+        Some(Err(_)) => {
+            return rouille::Response::from(Problem::malformed(String::from(
+                "the query parameter 'generation' must be a positive integer",
+            )))
+        }
+        None => None,
+    };
+    let req = fetch_learnset::Request {
+        number,
+        method: req.get_param("method"),
+        generation,
+    };
+
+    match fetch_learnset::execute(repo, req) {
+        Ok(entries) => {
+            rouille::Response::json(&entries.into_iter().map(Response::from).collect::<Vec<_>>())
+        }
+        Err(fetch_learnset::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(fetch_learnset::Error::NotFound) => rouille::Response::from(
+            Problem::new(Status::NotFound)
+                .with_detail(format!("There is no Pokemon with number {}", number)),
+        ),
+        Err(fetch_learnset::Error::Unknown(e)) => internal_error(e),
+    }
+}
@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use crate::api::create_move::Response;
+use crate::api::internal_error;
+use crate::domain::fetch_moves;
+use crate::repositories::pokemon::Repository;
+
+pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_moves::execute(repo) {
+        Ok(moves) => {
+            rouille::Response::json(&moves.into_iter().map(Response::from).collect::<Vec<_>>())
+        }
+        Err(fetch_moves::Error::Unknown(e)) => internal_error(e),
+    }
+}
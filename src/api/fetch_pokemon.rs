@@ -1,26 +1,57 @@
 use std::sync::Arc;
 
+use crate::api::conditional;
+use crate::api::{internal_error, not_found, problem::Problem, Slot, Stats};
 use crate::domain::fetch_pokemon;
-use crate::{api::Status, repositories::pokemon::Repository};
+use crate::repositories::pokemon::Repository;
 use serde::Serialize;
 
 #[derive(Serialize)]
-struct Response {
+pub(super) struct Response {
     number: u16,
+    form: String,
     name: String,
     types: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<Stats>,
+    abilities: Vec<Slot>,
 }
 
-pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
-    let req = fetch_pokemon::Request { number };
-    match fetch_pokemon::execute(repo, req) {
-        Ok(res) => rouille::Response::json(&Response {
+impl From<fetch_pokemon::RetrieveResponse> for Response {
+    fn from(res: fetch_pokemon::RetrieveResponse) -> Self {
+        Self {
             number: res.number,
+            form: res.form,
             name: res.name,
             types: res.types,
-        }),
-        Err(fetch_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
-        Err(fetch_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
-        Err(fetch_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+            stats: res.stats.map(Stats::from),
+            abilities: res.abilities.into_iter().map(Slot::from).collect(),
+        }
+    }
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    form: Option<String>,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let request = fetch_pokemon::Request {
+        number,
+        form: form.clone(),
+    };
+    match fetch_pokemon::execute(repo, request) {
+        Ok(res) if conditional::is_fresh(req, res.revision) => {
+            conditional::not_modified(res.revision)
+        }
+        Ok(res) => {
+            let etag = conditional::etag(res.revision);
+            rouille::Response::json(&Response::from(res)).with_additional_header("ETag", etag)
+        }
+        Err(fetch_pokemon::Error::Unknown(e)) => internal_error(e),
+        Err(fetch_pokemon::Error::NotFound) => not_found(number, form.as_deref()),
+        Err(fetch_pokemon::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
     }
 }
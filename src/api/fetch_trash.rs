@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::api::{fetch_pokemon, internal_error};
+use crate::domain::fetch_trash;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Response {
+    #[serde(flatten)]
+    pokemon: fetch_pokemon::Response,
+    deleted_at: String,
+}
+
+impl From<fetch_trash::TrashResponse> for Response {
+    fn from(res: fetch_trash::TrashResponse) -> Self {
+        Self {
+            pokemon: fetch_pokemon::Response::from(res.pokemon),
+            deleted_at: res.deleted_at,
+        }
+    }
+}
+
+pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_trash::execute(repo) {
+        Ok(trash) => {
+            rouille::Response::json(&trash.into_iter().map(Response::from).collect::<Vec<_>>())
+        }
+        Err(fetch_trash::Error::Unknown(e)) => internal_error(e),
+    }
+}
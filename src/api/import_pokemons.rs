@@ -0,0 +1,77 @@
+use std::io::Read;
+use std::sync::Arc;
+
+use crate::api::{internal_error, problem::Problem, Status};
+use crate::domain::entities::Actor;
+use crate::domain::import_pokemons;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Response {
+    inserted: usize,
+    replaced: usize,
+    skipped: usize,
+    rejected: Vec<Rejection>,
+}
+
+#[derive(Serialize)]
+struct Rejection {
+    line: usize,
+    reason: String,
+}
+
+/// The body is the file itself, its format and the conflict policy being
+/// given by the `format` and `on_conflict` query parameters.
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    req: &rouille::Request,
+    actor: &Actor,
+) -> rouille::Response {
+    let mut content = String::new();
+    match req.data().map(|mut body| body.read_to_string(&mut content)) {
+        Some(Ok(_)) => {}
+        Some(Err(e)) => return rouille::Response::from(Problem::malformed(e.to_string())),
+        None => {
+            return rouille::Response::from(Problem::malformed(String::from(
+                "the request body has already been read",
+            )))
+        }
+    }
+
+    let domain_req = import_pokemons::Request {
+        format: req
+            .get_param("format")
+            .unwrap_or_else(|| String::from("jsonl")),
+        on_conflict: req
+            .get_param("on_conflict")
+            .unwrap_or_else(|| String::from("fail")),
+        content,
+    };
+
+    match import_pokemons::execute(repo, domain_req, actor) {
+        Ok(res) => rouille::Response::json(&Response {
+            inserted: res.inserted,
+            replaced: res.replaced,
+            skipped: res.skipped,
+            rejected: res
+                .rejected
+                .into_iter()
+                .map(|r| Rejection {
+                    line: r.line,
+                    reason: r.reason,
+                })
+                .collect(),
+        }),
+        Err(import_pokemons::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(import_pokemons::Error::Conflict { line }) => {
+            rouille::Response::from(Problem::new(Status::Conflict).with_detail(format!(
+                "The Pokemon on line {} already exists, nothing was imported",
+                line
+            )))
+        }
+        Err(import_pokemons::Error::Unknown(e)) => internal_error(e),
+    }
+}
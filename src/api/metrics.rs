@@ -0,0 +1,69 @@
+use std::sync::OnceLock;
+
+use crate::api::openapi;
+use crate::metrics::{Metrics, CONTENT_TYPE};
+
+/// The label of the requests matching no route.
+pub const UNMATCHED: &str = "unmatched";
+
+pub fn serve(metrics: &Metrics) -> rouille::Response {
+    rouille::Response::from_data(CONTENT_TYPE, metrics.render())
+}
+
+/// The path template of the route a request matches, e.g. `/{number}/forms/{form}`
+/// for `/25/forms/alola`. A literal segment wins over a parameter, as in the router.
+pub fn route(method: &str, url: &str) -> &'static str {
+    static ROUTES: OnceLock<Vec<(&'static str, &'static str)>> = OnceLock::new();
+
+    let segments = url.split('/').collect::<Vec<_>>();
+    ROUTES
+        .get_or_init(openapi::routes)
+        .iter()
+        .filter(|(route_method, _)| *route_method == method)
+        .filter_map(|(_, path)| {
+            let pattern = path.split('/').collect::<Vec<_>>();
+            if pattern.len() != segments.len() {
+                return None;
+            }
+
+            let mut parameters = 0;
+            for (expected, actual) in pattern.iter().zip(&segments) {
+                if expected.starts_with('{') {
+                    if actual.is_empty() {
+                        return None;
+                    }
+                    parameters += 1;
+                } else if expected != actual {
+                    return None;
+                }
+            }
+            Some((parameters, *path))
+        })
+        .min()
+        .map(|(_, path)| path)
+        .unwrap_or(UNMATCHED)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_should_label_a_request_with_its_route() {
+        assert_eq!(route("GET", "/"), "/");
+        assert_eq!(route("GET", "/25"), "/{number}");
+        assert_eq!(route("GET", "/abilities"), "/abilities");
+        assert_eq!(route("PATCH", "/25/forms/alola"), "/{number}/forms/{form}");
+        assert_eq!(
+            route("GET", "/abilities/static/pokemons"),
+            "/abilities/{name}/pokemons"
+        );
+    }
+
+    #[test]
+    fn it_should_label_the_other_requests_as_unmatched() {
+        assert_eq!(route("GET", "/25/unknown"), UNMATCHED);
+        assert_eq!(route("POST", "/25"), UNMATCHED);
+        assert_eq!(route("GET", "/25/forms/"), UNMATCHED);
+    }
+}
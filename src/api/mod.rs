@@ -1,61 +1,384 @@
 use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
 
-use crate::repositories::pokemon::Repository;
+use crate::domain::entities::{Actor, SlotValue, StatValues, TriggerValue};
+use crate::events::Events;
+use crate::metrics::Metrics;
+use crate::repositories::pokemon::{Repository, Source};
+use problem::Problem;
+use serde::{Deserialize, Serialize};
+use tracing::{error, field, info, info_span};
+use uuid::Uuid;
 
+mod auth;
+mod calculate_stats;
+mod conditional;
+mod create_ability;
+mod create_evolution;
+mod create_learnset_entry;
+mod create_move;
 mod create_pokemon;
 mod delete_pokemon;
+mod events;
+mod export_pokemons;
+mod fetch_abilities;
+mod fetch_ability_pokemons;
 mod fetch_all_pokemons;
+mod fetch_audit;
+mod fetch_evolution_chain;
+mod fetch_forms;
+mod fetch_history;
+mod fetch_learnset;
+mod fetch_moves;
 mod fetch_pokemon;
+mod fetch_trash;
 mod health;
+mod import_pokemons;
+mod metrics;
+mod openapi;
+mod problem;
+mod restore_pokemon;
+mod search_pokemons;
+mod update_pokemon;
 
-pub fn serve(url: &str, repo: Arc<dyn Repository>) {
-    rouille::start_server(url, move |req| {
-        router!(req,
-            (GET) (/health) => {
-                health::serve()
-            },
-            (POST) (/) => {
-              create_pokemon::serve(repo.clone(), req)
-            },
-            (GET) (/) => {
-                fetch_all_pokemons::serve(repo.clone())
-            },
-            (GET) (/{number: u16}) => {
-                fetch_pokemon::serve(repo.clone(), number)
-            },
-            (DELETE) (/{number: u16}) => {
-                delete_pokemon::serve(repo.clone(), number)
-            },
-            _ => {
-                rouille::Response::from(Status::NotFound)
+/// Serves the API with a pool of `threads` workers, 8 per CPU by default.
+/// Without `auth`, every route is open to anyone.
+pub fn serve(
+    url: &str,
+    threads: Option<usize>,
+    repo: Arc<dyn Repository>,
+    metrics: Arc<Metrics>,
+    events: Arc<Events>,
+    auth: bool,
+) {
+    info!("Listening on http://{}", url);
+
+    let workers = threads.unwrap_or_else(|| {
+        8 * thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+    });
+    let streams = events::Streams::new(events, workers);
+    rouille::start_server_with_pool(url, Some(workers), move |req| {
+        let request_id = Uuid::new_v4().to_string();
+        let span = info_span!(
+            "request",
+            request_id = %request_id,
+            method = %req.method(),
+            path = %req.url(),
+            api_key = field::Empty,
+            status = field::Empty,
+            duration_ms = field::Empty,
+        );
+        let _span = span.enter();
+
+        let start = Instant::now();
+        let route = metrics::route(req.method(), &req.url());
+        let response = match auth.then(|| auth::check(&repo, req, route)) {
+            Some(Err(response)) => response,
+            Some(Ok(Some(name))) => {
+                span.record("api_key", &name);
+                let actor = Actor::new(name, Some(request_id.clone()));
+                handle(&repo, &metrics, &streams, req, &actor)
+            }
+            Some(Ok(None)) | None => {
+                let actor = Actor::new(String::from(Actor::ANONYMOUS), Some(request_id.clone()));
+                handle(&repo, &metrics, &streams, req, &actor)
             }
-        )
+        };
+        // A streamed body is still being written, only its start is timed.
+        let duration = start.elapsed();
+
+        span.record("status", response.status_code);
+        span.record("duration_ms", duration.as_secs_f64() * 1000.0);
+        info!("request served");
+        metrics.observe_request(req.method(), route, response.status_code, duration);
+        response.with_additional_header("X-Request-Id", request_id)
     });
 }
 
+/// Serves a request, any change being made on behalf of `actor`.
+fn handle(
+    repo: &Arc<dyn Repository>,
+    metrics: &Metrics,
+    streams: &events::Streams,
+    req: &rouille::Request,
+    actor: &Actor,
+) -> rouille::Response {
+    // The router cannot match a dot in a path.
+    if req.method() == "GET" && req.url() == openapi::PATH {
+        return openapi::serve();
+    }
+
+    router!(req,
+        (GET) (/health) => {
+            health::serve()
+        },
+        (GET) (/metrics) => {
+            metrics::serve(metrics)
+        },
+        (POST) (/) => {
+          create_pokemon::serve(repo.clone(), req, actor)
+        },
+        (GET) (/) => {
+            fetch_all_pokemons::serve(repo.clone(), req)
+        },
+        (GET) (/events) => {
+            events::serve(streams, req)
+        },
+        (GET) (/search) => {
+            search_pokemons::serve(repo.clone(), req)
+        },
+        (GET) (/audit) => {
+            fetch_audit::serve(repo.clone(), req)
+        },
+        (GET) (/trash) => {
+            fetch_trash::serve(repo.clone())
+        },
+        (GET) (/export) => {
+            export_pokemons::serve(repo.clone(), req)
+        },
+        (POST) (/import) => {
+            import_pokemons::serve(repo.clone(), req, actor)
+        },
+        (POST) (/abilities) => {
+            create_ability::serve(repo.clone(), req)
+        },
+        (GET) (/abilities) => {
+            fetch_abilities::serve(repo.clone())
+        },
+        (GET) (/abilities/{name: String}/pokemons) => {
+            fetch_ability_pokemons::serve(repo.clone(), name)
+        },
+        (POST) (/moves) => {
+            create_move::serve(repo.clone(), req)
+        },
+        (GET) (/moves) => {
+            fetch_moves::serve(repo.clone())
+        },
+        (GET) (/{number: u16}) => {
+            fetch_pokemon::serve(repo.clone(), number, None, req)
+        },
+        (GET) (/{number: u16}/history) => {
+            fetch_history::serve(repo.clone(), number)
+        },
+        (POST) (/{number: u16}/restore) => {
+            restore_pokemon::serve(repo.clone(), number, None, actor)
+        },
+        (GET) (/{number: u16}/stats) => {
+            calculate_stats::serve(repo.clone(), number, req)
+        },
+        (POST) (/{number: u16}/evolutions) => {
+            create_evolution::serve(repo.clone(), number, req)
+        },
+        (GET) (/{number: u16}/evolutions) => {
+            fetch_evolution_chain::serve(repo.clone(), number)
+        },
+        (POST) (/{number: u16}/moves) => {
+            create_learnset_entry::serve(repo.clone(), number, req)
+        },
+        (GET) (/{number: u16}/moves) => {
+            fetch_learnset::serve(repo.clone(), number, req)
+        },
+        (POST) (/{number: u16}/forms) => {
+            create_pokemon::serve_form(repo.clone(), number, req, actor)
+        },
+        (GET) (/{number: u16}/forms) => {
+            fetch_forms::serve(repo.clone(), number)
+        },
+        (GET) (/{number: u16}/forms/{form: String}) => {
+            fetch_pokemon::serve(repo.clone(), number, Some(form), req)
+        },
+        (POST) (/{number: u16}/forms/{form: String}/restore) => {
+            restore_pokemon::serve(repo.clone(), number, Some(form), actor)
+        },
+        (DELETE) (/{number: u16}/forms/{form: String}) => {
+            delete_pokemon::serve(repo.clone(), number, Some(form), req, actor)
+        },
+        (PUT) (/{number: u16}/forms/{form: String}) => {
+            update_pokemon::serve(repo.clone(), number, Some(form), req, actor)
+        },
+        (PATCH) (/{number: u16}/forms/{form: String}) => {
+            update_pokemon::serve_partial(repo.clone(), number, Some(form), req, actor)
+        },
+        (DELETE) (/{number: u16}) => {
+            delete_pokemon::serve(repo.clone(), number, None, req, actor)
+        },
+        (PUT) (/{number: u16}) => {
+            update_pokemon::serve(repo.clone(), number, None, req, actor)
+        },
+        (PATCH) (/{number: u16}) => {
+            update_pokemon::serve_partial(repo.clone(), number, None, req, actor)
+        },
+        _ => {
+            rouille::Response::from(Status::NotFound)
+        }
+    )
+}
+
+/// Logs the cause of an unexpected failure, which is not disclosed to the client.
+fn internal_error(e: Source) -> rouille::Response {
+    error!(error = %e, "internal server error");
+    rouille::Response::from(Status::InternalServerError)
+}
+
+/// Answers that a Pokemon, or one of its alternate forms, does not exist.
+fn not_found(number: u16, form: Option<&str>) -> rouille::Response {
+    let detail = match form {
+        Some(form) => format!("There is no form '{}' of Pokemon {}", form, number),
+        None => format!("There is no Pokemon with number {}", number),
+    };
+
+    rouille::Response::from(Problem::new(Status::NotFound).with_detail(detail))
+}
+
+/// The JSON representation of a set of stats, shared by every endpoint.
+#[derive(Serialize, Deserialize)]
+struct Stats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    special_attack: u16,
+    special_defense: u16,
+    speed: u16,
+}
+
+impl From<StatValues> for Stats {
+    fn from(val: StatValues) -> Self {
+        Self {
+            hp: val.hp,
+            attack: val.attack,
+            defense: val.defense,
+            special_attack: val.special_attack,
+            special_defense: val.special_defense,
+            speed: val.speed,
+        }
+    }
+}
+
+impl From<Stats> for StatValues {
+    fn from(val: Stats) -> Self {
+        Self {
+            hp: val.hp,
+            attack: val.attack,
+            defense: val.defense,
+            special_attack: val.special_attack,
+            special_defense: val.special_defense,
+            speed: val.speed,
+        }
+    }
+}
+
+/// The JSON representation of an ability held by a Pokemon.
+#[derive(Serialize, Deserialize)]
+struct Slot {
+    slot: String,
+    name: String,
+}
+
+impl From<SlotValue> for Slot {
+    fn from(val: SlotValue) -> Self {
+        Self {
+            slot: val.slot,
+            name: val.name,
+        }
+    }
+}
+
+impl From<Slot> for SlotValue {
+    fn from(val: Slot) -> Self {
+        Self {
+            slot: val.slot,
+            name: val.name,
+        }
+    }
+}
+
+/// The JSON representation of an evolution trigger, `level` and `item` being
+/// only present for the kinds using them.
+#[derive(Serialize, Deserialize)]
+struct Trigger {
+    kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    level: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    item: Option<String>,
+}
+
+impl From<TriggerValue> for Trigger {
+    fn from(val: TriggerValue) -> Self {
+        Self {
+            kind: val.kind,
+            level: val.level,
+            item: val.item,
+        }
+    }
+}
+
+impl From<Trigger> for TriggerValue {
+    fn from(val: Trigger) -> Self {
+        Self {
+            kind: val.kind,
+            level: val.level,
+            item: val.item,
+        }
+    }
+}
+
 enum Status {
     Ok,
+    NotModified,
     BadRequest,
+    Unauthorized,
+    Forbidden,
     NotFound,
     Conflict,
+    PreconditionFailed,
     InternalServerError,
+    ServiceUnavailable,
 }
 
-impl From<Status> for rouille::Response {
-    fn from(val: Status) -> Self {
-        let status_code = match val {
+impl Status {
+    fn code(&self) -> u16 {
+        match self {
             Status::Ok => 200,
+            Status::NotModified => 304,
             Status::BadRequest => 400,
+            Status::Unauthorized => 401,
+            Status::Forbidden => 403,
             Status::NotFound => 404,
             Status::Conflict => 409,
+            Status::PreconditionFailed => 412,
             Status::InternalServerError => 500,
-        };
+            Status::ServiceUnavailable => 503,
+        }
+    }
 
-        Self {
-            status_code,
-            headers: vec![],
-            data: rouille::ResponseBody::empty(),
-            upgrade: None,
+    fn reason(&self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::NotModified => "Not Modified",
+            Status::BadRequest => "Bad Request",
+            Status::Unauthorized => "Unauthorized",
+            Status::Forbidden => "Forbidden",
+            Status::NotFound => "Not Found",
+            Status::Conflict => "Conflict",
+            Status::PreconditionFailed => "Precondition Failed",
+            Status::InternalServerError => "Internal Server Error",
+            Status::ServiceUnavailable => "Service Unavailable",
+        }
+    }
+}
+
+impl From<Status> for rouille::Response {
+    fn from(val: Status) -> Self {
+        match val {
+            Status::Ok | Status::NotModified => Self {
+                status_code: val.code(),
+                headers: vec![],
+                data: rouille::ResponseBody::empty(),
+                upgrade: None,
+            },
+            _ => rouille::Response::from(Problem::new(val)),
         }
     }
 }
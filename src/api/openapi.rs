@@ -0,0 +1,1334 @@
+use serde_json::{json, Map, Value};
+
+use crate::api::calculate_stats::{DEFAULT_IV, DEFAULT_LEVEL, DEFAULT_NATURE};
+use crate::api::events::CONTENT_TYPE as EVENTS_CONTENT_TYPE;
+use crate::api::fetch_all_pokemons::DEFAULT_LIMIT;
+use crate::api::fetch_audit;
+use crate::api::search_pokemons;
+use crate::api::{auth, problem, Status};
+use crate::domain::entities::{
+    AbilitySlot, AuditAction, BaseStats, Generation, LearnMethod, Level, MoveAccuracy,
+    MoveCategory, MovePower, Nature, PokemonNumber, PokemonType, PowerPoints,
+};
+use crate::domain::fetch_audit::MAX_LIMIT as MAX_AUDIT_LIMIT;
+use crate::domain::search_pokemons::MAX_LIMIT as MAX_SEARCH_LIMIT;
+
+/// A route of `api::serve`: what it reads and what it answers. Every error
+/// status is answered with a problem body.
+struct Operation {
+    method: &'static str,
+    path: &'static str,
+    summary: &'static str,
+    parameters: Vec<Value>,
+    body: Option<Value>,
+    response: Value,
+    errors: Vec<Status>,
+}
+
+/// Where the document is served, outside of the router.
+pub const PATH: &str = "/openapi.json";
+
+pub fn serve() -> rouille::Response {
+    rouille::Response::json(&document())
+}
+
+/// The method and path template of every route.
+pub(super) fn routes() -> Vec<(&'static str, &'static str)> {
+    operations()
+        .into_iter()
+        .map(|operation| (operation.method, operation.path))
+        .collect()
+}
+
+/// The OpenAPI 3.1 document describing every route.
+fn document() -> Value {
+    let mut paths = Map::new();
+    for operation in operations() {
+        let role = auth::required_role(operation.method, operation.path);
+        let mut errors = operation.errors;
+        if role.is_some() {
+            errors.extend([Status::Unauthorized, Status::Forbidden]);
+            errors.sort_by_key(Status::code);
+        }
+
+        let mut responses = Map::new();
+        responses.insert(Status::Ok.code().to_string(), operation.response);
+        for status in errors {
+            let response = match status {
+                Status::NotModified => json!({
+                    "description": status.reason(),
+                    "headers": { "ETag": etag_header() },
+                }),
+                _ => json!({
+                    "description": status.reason(),
+                    "content": { problem::CONTENT_TYPE: { "schema": schema("Problem") } },
+                }),
+            };
+            responses.insert(status.code().to_string(), response);
+        }
+
+        let mut item = json!({
+            "summary": operation.summary,
+            "responses": responses,
+        });
+        if !operation.parameters.is_empty() {
+            item["parameters"] = Value::Array(operation.parameters);
+        }
+        if let Some(body) = operation.body {
+            item["requestBody"] = body;
+        }
+        if let Some(role) = role {
+            item["description"] = json!(format!(
+                "Requires an API key with the {} role or above.",
+                String::from(role)
+            ));
+            item["security"] = json!([{ "apiKey": [] }]);
+        }
+
+        paths
+            .entry(operation.path)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .map(|path| path.insert(operation.method.to_lowercase(), item));
+    }
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "Pokedex",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": schemas(),
+            "securitySchemes": {
+                "apiKey": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "An API key created with `pokedex keys create`, also accepted in the X-Api-Key header",
+                },
+            },
+        },
+    })
+}
+
+fn operations() -> Vec<Operation> {
+    use Status::{
+        BadRequest, Conflict, InternalServerError, NotFound, NotModified, PreconditionFailed,
+        ServiceUnavailable,
+    };
+
+    vec![
+        Operation {
+            method: "GET",
+            path: "/health",
+            summary: "Tells whether the server is up",
+            parameters: vec![],
+            body: None,
+            response: json_response(schema("Health")),
+            errors: vec![],
+        },
+        Operation {
+            method: "GET",
+            path: PATH,
+            summary: "This document",
+            parameters: vec![],
+            body: None,
+            response: json_response(json!({ "type": "object" })),
+            errors: vec![],
+        },
+        Operation {
+            method: "GET",
+            path: "/metrics",
+            summary: "The request and repository metrics in the Prometheus text format",
+            parameters: vec![],
+            body: None,
+            response: json!({
+                "description": Status::Ok.reason(),
+                "content": { "text/plain": { "schema": string() } },
+            }),
+            errors: vec![],
+        },
+        Operation {
+            method: "POST",
+            path: "/",
+            summary: "Creates a Pokemon in its default form",
+            parameters: vec![],
+            body: Some(json_body(schema("PokemonRequest"))),
+            response: tagged_response(schema("Pokemon")),
+            errors: vec![BadRequest, Conflict, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/",
+            summary: "Lists the Pokemons a page at a time",
+            parameters: vec![
+                query(
+                    "limit",
+                    &format!("How many Pokemons to return, {} by default", DEFAULT_LIMIT),
+                    integer(),
+                ),
+                query("offset", "How many Pokemons to skip", integer()),
+                query("cursor", "The X-Next-Cursor of the previous page", string()),
+                query("sort", "The field to sort on", enumeration(["number", "name"])),
+                query("order", "The sort order", enumeration(["asc", "desc"])),
+                query("type", "Only the Pokemons of this type", types()),
+                query("min_number", "The lowest number", number()),
+                query("max_number", "The highest number", number()),
+            ],
+            body: None,
+            response: json!({
+                "description": Status::Ok.reason(),
+                "headers": {
+                    "Link": {
+                        "description": "The URL of the next page, when there is one",
+                        "schema": string(),
+                    },
+                    "X-Next-Cursor": {
+                        "description": "The cursor of the next page, when there is one",
+                        "schema": string(),
+                    },
+                },
+                "content": { "application/json": { "schema": array(schema("PokemonSummary")) } },
+            }),
+            errors: vec![BadRequest, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/events",
+            summary: "Streams the created, updated and deleted Pokemons as server-sent events",
+            parameters: vec![json!({
+                "name": "Last-Event-ID",
+                "in": "header",
+                "description": "The id of the last event received, the stream resuming after it \
+                                while it is still kept in memory",
+                "schema": { "type": "integer", "minimum": 0 },
+            })],
+            body: None,
+            response: json!({
+                "description": "One event per change, named after it, its data being a Snapshot",
+                "content": { EVENTS_CONTENT_TYPE: { "schema": string() } },
+            }),
+            errors: vec![BadRequest, ServiceUnavailable],
+        },
+        Operation {
+            method: "GET",
+            path: "/search",
+            summary: "Finds the Pokemons by name, despite typos, the best match first",
+            parameters: vec![
+                json!({
+                    "name": "q",
+                    "in": "query",
+                    "required": true,
+                    "description": "The name, or part of it",
+                    "schema": string(),
+                }),
+                query(
+                    "limit",
+                    &format!(
+                        "How many Pokemons to return, {} by default",
+                        search_pokemons::DEFAULT_LIMIT
+                    ),
+                    json!({ "type": "integer", "minimum": 1, "maximum": MAX_SEARCH_LIMIT }),
+                ),
+            ],
+            body: None,
+            response: json_response(array(schema("PokemonSummary"))),
+            errors: vec![BadRequest, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/audit",
+            summary: "Lists the changes made to every Pokemon, the oldest first",
+            parameters: vec![
+                query(
+                    "since",
+                    "Only the changes made at or after this RFC 3339 timestamp, in UTC without an offset",
+                    json!({ "type": "string", "format": "date-time" }),
+                ),
+                query(
+                    "limit",
+                    &format!(
+                        "How many changes to return, {} by default",
+                        fetch_audit::DEFAULT_LIMIT
+                    ),
+                    json!({ "type": "integer", "minimum": 1, "maximum": MAX_AUDIT_LIMIT }),
+                ),
+            ],
+            body: None,
+            response: json_response(array(schema("AuditEvent"))),
+            errors: vec![BadRequest, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/trash",
+            summary: "Lists the deleted forms, which can be restored until they are purged",
+            parameters: vec![],
+            body: None,
+            response: json_response(array(schema("DeletedPokemon"))),
+            errors: vec![InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/export",
+            summary: "Exports every form of every Pokemon",
+            parameters: vec![query(
+                "format",
+                "The format of the export, json by default",
+                enumeration(["json", "jsonl", "csv", "sql"]),
+            )],
+            body: None,
+            response: json!({
+                "description": Status::Ok.reason(),
+                "content": {
+                    "application/json": { "schema": array(schema("ImportRecord")) },
+                    "application/jsonl": { "schema": string() },
+                    "text/csv": { "schema": string() },
+                    "application/sql": { "schema": string() },
+                },
+            }),
+            errors: vec![BadRequest],
+        },
+        Operation {
+            method: "POST",
+            path: "/import",
+            summary: "Imports Pokemons from a JSON, JSON Lines or CSV file",
+            parameters: vec![
+                query(
+                    "format",
+                    "The format of the body, jsonl by default",
+                    enumeration(["json", "jsonl", "csv"]),
+                ),
+                query(
+                    "on_conflict",
+                    "What to do with a Pokemon that already exists, fail by default",
+                    enumeration(["fail", "skip", "replace"]),
+                ),
+            ],
+            body: Some(json!({
+                "required": true,
+                "content": {
+                    "application/json": { "schema": array(schema("ImportRecord")) },
+                    "application/jsonl": { "schema": string() },
+                    "text/csv": { "schema": string() },
+                },
+            })),
+            response: json_response(schema("ImportReport")),
+            errors: vec![BadRequest, Conflict, InternalServerError],
+        },
+        Operation {
+            method: "POST",
+            path: "/abilities",
+            summary: "Adds an ability to the catalogue",
+            parameters: vec![],
+            body: Some(json_body(schema("AbilityRequest"))),
+            response: json_response(schema("Ability")),
+            errors: vec![BadRequest, Conflict, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/abilities",
+            summary: "Lists the abilities of the catalogue",
+            parameters: vec![],
+            body: None,
+            response: json_response(array(schema("Ability"))),
+            errors: vec![InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/abilities/{name}/pokemons",
+            summary: "Lists the forms of the Pokemons holding an ability",
+            parameters: vec![path("name", string())],
+            body: None,
+            response: json_response(array(schema("AbilityHolder"))),
+            errors: vec![BadRequest, NotFound, InternalServerError],
+        },
+        Operation {
+            method: "POST",
+            path: "/moves",
+            summary: "Adds a move to the catalogue",
+            parameters: vec![],
+            body: Some(json_body(schema("Move"))),
+            response: json_response(schema("Move")),
+            errors: vec![BadRequest, Conflict, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/moves",
+            summary: "Lists the moves of the catalogue",
+            parameters: vec![],
+            body: None,
+            response: json_response(array(schema("Move"))),
+            errors: vec![InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/{number}",
+            summary: "Fetches the default form of a Pokemon",
+            parameters: vec![path("number", number()), if_none_match()],
+            body: None,
+            response: tagged_response(schema("Pokemon")),
+            errors: vec![NotModified, BadRequest, NotFound, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/{number}/history",
+            summary: "Lists every change made to every form of a Pokemon, the oldest first",
+            parameters: vec![path("number", number())],
+            body: None,
+            response: json_response(array(schema("AuditEvent"))),
+            errors: vec![BadRequest, NotFound, InternalServerError],
+        },
+        Operation {
+            method: "POST",
+            path: "/{number}/restore",
+            summary: "Restores a deleted Pokemon with the forms deleted along with it",
+            parameters: vec![path("number", number())],
+            body: None,
+            response: tagged_response(schema("Pokemon")),
+            errors: vec![BadRequest, NotFound, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/{number}/stats",
+            summary: "Calculates the stats of a Pokemon",
+            parameters: vec![
+                path("number", number()),
+                query("form", "The form, the default one if absent", string()),
+                query(
+                    "level",
+                    &format!("{} by default", DEFAULT_LEVEL),
+                    bounded(Level::MIN, Level::MAX),
+                ),
+                query(
+                    "nature",
+                    &format!("{} by default", DEFAULT_NATURE),
+                    enumeration(Nature::ALL.map(String::from)),
+                ),
+                query(
+                    "ivs",
+                    &format!(
+                        "Six comma separated values in HP/Atk/Def/SpA/SpD/Spe order, {} each by default",
+                        DEFAULT_IV
+                    ),
+                    string(),
+                ),
+                query(
+                    "evs",
+                    "Six comma separated values in HP/Atk/Def/SpA/SpD/Spe order, 0 each by default",
+                    string(),
+                ),
+            ],
+            body: None,
+            response: json_response(schema("CalculatedStats")),
+            errors: vec![BadRequest, NotFound, InternalServerError],
+        },
+        Operation {
+            method: "POST",
+            path: "/{number}/evolutions",
+            summary: "Adds an evolution to a Pokemon",
+            parameters: vec![path("number", number())],
+            body: Some(json_body(schema("EvolutionRequest"))),
+            response: json_response(schema("Evolution")),
+            errors: vec![BadRequest, NotFound, Conflict, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/{number}/evolutions",
+            summary: "Fetches the evolution chain a Pokemon belongs to",
+            parameters: vec![path("number", number())],
+            body: None,
+            response: json_response(schema("EvolutionNode")),
+            errors: vec![BadRequest, NotFound, InternalServerError],
+        },
+        Operation {
+            method: "POST",
+            path: "/{number}/moves",
+            summary: "Adds a move to the learnset of a Pokemon",
+            parameters: vec![path("number", number())],
+            body: Some(json_body(schema("LearnsetRequest"))),
+            response: json_response(schema("LearnsetEntry")),
+            errors: vec![BadRequest, NotFound, Conflict, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/{number}/moves",
+            summary: "Fetches the learnset of a Pokemon",
+            parameters: vec![
+                path("number", number()),
+                query(
+                    "method",
+                    "Only the moves learnt this way",
+                    enumeration(LearnMethod::ALL.map(String::from)),
+                ),
+                query(
+                    "generation",
+                    "Only the moves learnt in this generation",
+                    bounded(Generation::MIN, Generation::MAX),
+                ),
+            ],
+            body: None,
+            response: json_response(array(schema("LearnsetEntry"))),
+            errors: vec![BadRequest, NotFound, InternalServerError],
+        },
+        Operation {
+            method: "POST",
+            path: "/{number}/forms",
+            summary: "Adds an alternate form to a Pokemon",
+            parameters: vec![path("number", number())],
+            body: Some(json_body(schema("FormRequest"))),
+            response: tagged_response(schema("Pokemon")),
+            errors: vec![BadRequest, NotFound, Conflict, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/{number}/forms",
+            summary: "Lists every form of a Pokemon, the default one first",
+            parameters: vec![path("number", number())],
+            body: None,
+            response: json_response(array(schema("Pokemon"))),
+            errors: vec![BadRequest, NotFound, InternalServerError],
+        },
+        Operation {
+            method: "GET",
+            path: "/{number}/forms/{form}",
+            summary: "Fetches a form of a Pokemon",
+            parameters: vec![path("number", number()), path("form", form()), if_none_match()],
+            body: None,
+            response: tagged_response(schema("Pokemon")),
+            errors: vec![NotModified, BadRequest, NotFound, InternalServerError],
+        },
+        Operation {
+            method: "POST",
+            path: "/{number}/forms/{form}/restore",
+            summary: "Restores a deleted form of a Pokemon",
+            parameters: vec![path("number", number()), path("form", form())],
+            body: None,
+            response: tagged_response(schema("Pokemon")),
+            errors: vec![BadRequest, NotFound, Conflict, InternalServerError],
+        },
+        Operation {
+            method: "DELETE",
+            path: "/{number}/forms/{form}",
+            summary: "Moves a form of a Pokemon to the trash",
+            parameters: vec![path("number", number()), path("form", form()), if_match()],
+            body: None,
+            response: empty_response(),
+            errors: vec![BadRequest, NotFound, PreconditionFailed, InternalServerError],
+        },
+        Operation {
+            method: "PUT",
+            path: "/{number}/forms/{form}",
+            summary: "Replaces a form of a Pokemon",
+            parameters: vec![path("number", number()), path("form", form()), if_match()],
+            body: Some(json_body(schema("UpdateRequest"))),
+            response: tagged_response(schema("Pokemon")),
+            errors: vec![BadRequest, NotFound, PreconditionFailed, InternalServerError],
+        },
+        Operation {
+            method: "PATCH",
+            path: "/{number}/forms/{form}",
+            summary: "Updates the given fields of a form of a Pokemon",
+            parameters: vec![path("number", number()), path("form", form()), if_match()],
+            body: Some(json_body(schema("PartialUpdateRequest"))),
+            response: tagged_response(schema("Pokemon")),
+            errors: vec![BadRequest, NotFound, PreconditionFailed, InternalServerError],
+        },
+        Operation {
+            method: "DELETE",
+            path: "/{number}",
+            summary: "Moves a Pokemon and all its forms to the trash",
+            parameters: vec![path("number", number()), if_match()],
+            body: None,
+            response: empty_response(),
+            errors: vec![BadRequest, NotFound, PreconditionFailed, InternalServerError],
+        },
+        Operation {
+            method: "PUT",
+            path: "/{number}",
+            summary: "Replaces the default form of a Pokemon",
+            parameters: vec![path("number", number()), if_match()],
+            body: Some(json_body(schema("UpdateRequest"))),
+            response: tagged_response(schema("Pokemon")),
+            errors: vec![BadRequest, NotFound, PreconditionFailed, InternalServerError],
+        },
+        Operation {
+            method: "PATCH",
+            path: "/{number}",
+            summary: "Updates the given fields of the default form of a Pokemon",
+            parameters: vec![path("number", number()), if_match()],
+            body: Some(json_body(schema("PartialUpdateRequest"))),
+            response: tagged_response(schema("Pokemon")),
+            errors: vec![BadRequest, NotFound, PreconditionFailed, InternalServerError],
+        },
+    ]
+}
+
+/// The JSON schemas of the request and response bodies.
+fn schemas() -> Value {
+    let stat = bounded(BaseStats::MIN, BaseStats::MAX);
+    let pokemon = |required: &[&str], with_number: bool, with_form: bool| {
+        let mut properties = json!({
+            "name": string(),
+            "types": types_array(),
+            "stats": schema("Stats"),
+            "abilities": array(schema("Slot")),
+        });
+        if with_number {
+            properties["number"] = number();
+        }
+        if with_form {
+            properties["form"] = form();
+        }
+        object(required, properties)
+    };
+    let mut update_request = pokemon(&["name", "types"], false, false);
+    update_request["description"] = json!(
+        "The whole form, replacing the stored one: the stats and the abilities left out are \
+         removed, where PATCH keeps them"
+    );
+    let mut partial_update_request = pokemon(&[], false, false);
+    partial_update_request["properties"]["stats"] = nullable(schema("Stats"));
+    partial_update_request["description"] =
+        json!("The fields to change, the others being kept: the stats set to null are removed");
+    let move_properties = json!({
+        "name": string(),
+        "type": types(),
+        "category": enumeration(MoveCategory::ALL.map(String::from)),
+        "power": nullable(bounded(MovePower::MIN, MovePower::MAX)),
+        "accuracy": nullable(bounded(MoveAccuracy::MIN, MoveAccuracy::MAX)),
+        "pp": bounded(PowerPoints::MIN, PowerPoints::MAX),
+    });
+    let mut learn = json!({
+        "method": enumeration(LearnMethod::ALL.map(String::from)),
+        "level": bounded(Level::MIN, Level::MAX),
+        "generation": bounded(Generation::MIN, Generation::MAX),
+    });
+    let learnset_entry = object(&["method", "generation"], learn.clone());
+    learn["move"] = string();
+    let learnset_request = object(&["move", "method", "generation"], learn);
+
+    json!({
+        "Health": object(&["message"], json!({ "message": string() })),
+        "Problem": object(&["type", "title", "status"], json!({
+            "type": string(),
+            "title": string(),
+            "status": integer(),
+            "detail": string(),
+            "errors": array(object(&["field", "code", "detail"], json!({
+                "field": string(),
+                "code": string(),
+                "detail": string(),
+            }))),
+        })),
+        "Stats": object(
+            &["hp", "attack", "defense", "special_attack", "special_defense", "speed"],
+            json!({
+                "hp": stat,
+                "attack": stat,
+                "defense": stat,
+                "special_attack": stat,
+                "special_defense": stat,
+                "speed": stat,
+            }),
+        ),
+        "Slot": object(&["slot", "name"], json!({
+            "slot": enumeration(AbilitySlot::ALL.map(String::from)),
+            "name": string(),
+        })),
+        "Pokemon": pokemon(&["number", "form", "name", "types", "abilities"], true, true),
+        "PokemonSummary": pokemon(&["number", "name", "types", "abilities"], true, false),
+        "PokemonRequest": pokemon(&["number", "name", "types"], true, false),
+        "FormRequest": pokemon(&["form", "name", "types"], false, true),
+        "UpdateRequest": update_request,
+        "PartialUpdateRequest": partial_update_request,
+        "CalculatedStats": object(&["number", "name", "level", "nature", "stats"], json!({
+            "number": number(),
+            "name": string(),
+            "level": bounded(Level::MIN, Level::MAX),
+            "nature": enumeration(Nature::ALL.map(String::from)),
+            "stats": object(
+                &["hp", "attack", "defense", "special_attack", "special_defense", "speed"],
+                json!({
+                    "hp": integer(),
+                    "attack": integer(),
+                    "defense": integer(),
+                    "special_attack": integer(),
+                    "special_defense": integer(),
+                    "speed": integer(),
+                }),
+            ),
+        })),
+        "Ability": object(&["name", "description"], json!({
+            "name": string(),
+            "description": string(),
+        })),
+        "AbilityRequest": object(&["name"], json!({
+            "name": string(),
+            "description": string(),
+        })),
+        "AbilityHolder": object(&["number", "form", "name", "slot"], json!({
+            "number": number(),
+            "form": form(),
+            "name": string(),
+            "slot": enumeration(AbilitySlot::ALL.map(String::from)),
+        })),
+        "Trigger": object(&["kind"], json!({
+            "kind": enumeration(["level_up", "item", "trade", "friendship"]),
+            "level": bounded(Level::MIN, Level::MAX),
+            "item": string(),
+        })),
+        "Evolution": object(&["from", "to", "trigger"], json!({
+            "from": number(),
+            "to": number(),
+            "trigger": schema("Trigger"),
+        })),
+        "EvolutionRequest": object(&["to", "trigger"], json!({
+            "to": number(),
+            "trigger": schema("Trigger"),
+        })),
+        "EvolutionNode": object(&["number", "name", "evolves_to"], json!({
+            "number": number(),
+            "name": string(),
+            "trigger": schema("Trigger"),
+            "evolves_to": array(schema("EvolutionNode")),
+        })),
+        "Move": object(&["name", "type", "category", "pp"], move_properties),
+        "LearnsetEntry": {
+            "allOf": [
+                schema("Move"),
+                learnset_entry,
+            ],
+        },
+        "LearnsetRequest": learnset_request,
+        "ImportRecord": object(&["number", "name", "types"], json!({
+            "number": number(),
+            "form": form(),
+            "name": string(),
+            "types": types_array(),
+            "stats": schema("Stats"),
+            "abilities": array(schema("Slot")),
+        })),
+        "Snapshot": {
+            "allOf": [
+                schema("Pokemon"),
+                object(&["revision"], json!({ "revision": integer() })),
+            ],
+        },
+        "DeletedPokemon": {
+            "allOf": [
+                schema("Pokemon"),
+                object(&["deleted_at"], json!({
+                    "deleted_at": { "type": "string", "format": "date-time" },
+                })),
+            ],
+        },
+        "AuditEvent": object(
+            &["id", "action", "number", "form", "actor", "timestamp", "before", "after"],
+            json!({
+                "id": integer(),
+                "action": enumeration(AuditAction::ALL.map(String::from)),
+                "number": number(),
+                "form": form(),
+                "actor": string(),
+                "request_id": string(),
+                "timestamp": { "type": "string", "format": "date-time" },
+                "before": nullable(schema("Snapshot")),
+                "after": nullable(schema("Snapshot")),
+            }),
+        ),
+        "ImportReport": object(&["inserted", "replaced", "skipped", "rejected"], json!({
+            "inserted": integer(),
+            "replaced": integer(),
+            "skipped": integer(),
+            "rejected": array(object(&["line", "reason"], json!({
+                "line": integer(),
+                "reason": string(),
+            }))),
+        })),
+    })
+}
+
+fn schema(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+fn object(required: &[&str], properties: Value) -> Value {
+    json!({ "type": "object", "required": required, "properties": properties })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "oneOf": [schema, { "type": "null" }] })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+fn bounded<T: Into<u16>>(min: T, max: T) -> Value {
+    json!({ "type": "integer", "minimum": min.into(), "maximum": max.into() })
+}
+
+fn number() -> Value {
+    bounded(PokemonNumber::MIN, PokemonNumber::MAX)
+}
+
+fn form() -> Value {
+    json!({ "type": "string", "pattern": "^[a-z0-9]+(-[a-z0-9]+)*$" })
+}
+
+fn enumeration<T: Into<String>, const N: usize>(values: [T; N]) -> Value {
+    json!({ "type": "string", "enum": values.map(Into::<String>::into).to_vec() })
+}
+
+fn types() -> Value {
+    enumeration(PokemonType::ALL.map(String::from))
+}
+
+fn types_array() -> Value {
+    json!({ "type": "array", "items": types(), "minItems": 1, "maxItems": 2 })
+}
+
+fn path(name: &str, schema: Value) -> Value {
+    json!({ "name": name, "in": "path", "required": true, "schema": schema })
+}
+
+fn query(name: &str, description: &str, schema: Value) -> Value {
+    json!({ "name": name, "in": "query", "description": description, "schema": schema })
+}
+
+fn if_none_match() -> Value {
+    json!({
+        "name": "If-None-Match",
+        "in": "header",
+        "description": "The ETag of the copy held, answered with 304 while it is up to date",
+        "schema": string(),
+    })
+}
+
+fn if_match() -> Value {
+    json!({
+        "name": "If-Match",
+        "in": "header",
+        "description": "The ETags of the revisions to change, answered with 412 once none matches",
+        "schema": string(),
+    })
+}
+
+fn etag_header() -> Value {
+    json!({ "description": "The revision of the Pokemon, as a strong tag", "schema": string() })
+}
+
+fn json_body(schema: Value) -> Value {
+    json!({ "required": true, "content": { "application/json": { "schema": schema } } })
+}
+
+fn json_response(schema: Value) -> Value {
+    json!({
+        "description": Status::Ok.reason(),
+        "content": { "application/json": { "schema": schema } },
+    })
+}
+
+/// A Pokemon, tagged with its revision.
+fn tagged_response(schema: Value) -> Value {
+    json!({
+        "description": Status::Ok.reason(),
+        "headers": { "ETag": etag_header() },
+        "content": { "application/json": { "schema": schema } },
+    })
+}
+
+fn empty_response() -> Value {
+    json!({ "description": Status::Ok.reason() })
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::events::Streams;
+    use crate::api::handle;
+    use crate::domain::entities::Actor;
+    use crate::events::Events;
+    use crate::metrics::Metrics;
+    use crate::repositories::pokemon::{InMemoryRepository, Repository};
+
+    /// The `(METHOD) (/path)` routes of the router, the parameters' types
+    /// being dropped as in the document, and this document's own route.
+    fn router_routes() -> Vec<(String, String)> {
+        let mut routes = include_str!("mod.rs")
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix('('))
+            .filter_map(|line| line.strip_suffix(") => {"))
+            .filter_map(|line| line.split_once(") ("))
+            .map(|(method, path)| {
+                let mut documented = String::new();
+                let mut in_type = false;
+                for c in path.chars() {
+                    match c {
+                        ':' => in_type = true,
+                        '}' => {
+                            in_type = false;
+                            documented.push(c);
+                        }
+                        c if !in_type => documented.push(c),
+                        _ => {}
+                    }
+                }
+                (method.to_lowercase(), documented)
+            })
+            .collect::<Vec<_>>();
+        routes.push((String::from("get"), String::from(PATH)));
+        routes
+    }
+
+    #[test]
+    fn it_should_document_every_route() {
+        let document = document();
+        let routes = router_routes();
+
+        assert!(routes.len() > 1);
+        for (method, path) in &routes {
+            assert!(
+                document["paths"][path][method].is_object(),
+                "{} {} is not documented",
+                method.to_uppercase(),
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_only_document_existing_routes() {
+        let routes = router_routes();
+
+        match document()["paths"].as_object() {
+            Some(paths) => {
+                for (path, item) in paths {
+                    for method in item.as_object().into_iter().flat_map(|item| item.keys()) {
+                        assert!(
+                            routes.contains(&(method.clone(), path.clone())),
+                            "{} {} is not a route",
+                            method.to_uppercase(),
+                            path
+                        );
+                    }
+                }
+            }
+            None => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_document_the_key_required_by_every_change() {
+        let document = document();
+
+        for (method, path) in router_routes().iter().filter(|(method, _)| method != "get") {
+            let operation = &document["paths"][path][method];
+            assert_eq!(operation["security"][0]["apiKey"], json!([]));
+            assert!(operation["responses"]["401"].is_object());
+            assert!(operation["responses"]["403"].is_object());
+        }
+        assert!(document["paths"]["/"]["get"]["security"].is_null());
+    }
+
+    #[test]
+    fn it_should_document_the_conditional_requests_on_a_pokemon() {
+        let document = document();
+        let pokemon = &document["paths"]["/{number}"];
+
+        assert!(pokemon["get"]["responses"]["200"]["headers"]["ETag"].is_object());
+        assert!(pokemon["get"]["responses"]["304"]["content"].is_null());
+        for method in ["put", "patch", "delete"] {
+            assert_eq!(pokemon[method]["parameters"][1]["name"], "If-Match");
+            assert!(pokemon[method]["responses"]["412"].is_object());
+        }
+    }
+
+    #[test]
+    fn it_should_define_every_referenced_schema() {
+        fn references(value: &Value, found: &mut Vec<String>) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(reference)) = map.get("$ref") {
+                        found.push(reference.clone());
+                    }
+                    map.values().for_each(|v| references(v, found));
+                }
+                Value::Array(items) => items.iter().for_each(|v| references(v, found)),
+                _ => {}
+            }
+        }
+
+        let document = document();
+        let mut found = vec![];
+        references(&document, &mut found);
+
+        assert!(!found.is_empty());
+        for reference in found {
+            match reference.strip_prefix("#/components/schemas/") {
+                Some(name) => assert!(
+                    document["components"]["schemas"][name].is_object(),
+                    "{} is not defined",
+                    name
+                ),
+                None => unreachable!(),
+            }
+        }
+    }
+
+    /// Checks a body against a schema of the document. The objects are closed
+    /// to the properties they declare, so that an undocumented field fails too.
+    fn validate(document: &Value, schema: &Value, value: &Value, at: &str) -> Vec<String> {
+        let mut errors = vec![];
+        check(document, schema, value, at, true, &mut errors);
+        errors
+    }
+
+    fn resolve<'a>(document: &'a Value, schema: &'a Value) -> &'a Value {
+        match schema["$ref"]
+            .as_str()
+            .and_then(|reference| reference.strip_prefix("#/components/schemas/"))
+        {
+            Some(name) => resolve(document, &document["components"]["schemas"][name]),
+            None => schema,
+        }
+    }
+
+    /// The properties declared by a schema and by the ones it is made of.
+    fn declared(document: &Value, schema: &Value) -> Vec<String> {
+        let schema = resolve(document, schema);
+        let mut names = schema["properties"]
+            .as_object()
+            .map(|properties| properties.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        for part in schema["allOf"].as_array().into_iter().flatten() {
+            names.extend(declared(document, part));
+        }
+        names
+    }
+
+    fn check(
+        document: &Value,
+        schema: &Value,
+        value: &Value,
+        at: &str,
+        closed: bool,
+        errors: &mut Vec<String>,
+    ) {
+        let schema = resolve(document, schema);
+
+        if let Some(parts) = schema["allOf"].as_array() {
+            for part in parts {
+                check(document, part, value, at, false, errors);
+            }
+        }
+        if let Some(parts) = schema["oneOf"].as_array() {
+            let matching = parts
+                .iter()
+                .filter(|part| validate(document, part, value, at).is_empty())
+                .count();
+            if matching != 1 {
+                errors.push(format!("{} matches {} schemas of oneOf", at, matching));
+            }
+        }
+
+        let typed = match schema["type"].as_str() {
+            Some("string") => value.is_string(),
+            Some("integer") => value.is_u64() || value.is_i64(),
+            Some("array") => value.is_array(),
+            Some("object") => value.is_object(),
+            Some("null") => value.is_null(),
+            _ => true,
+        };
+        if !typed {
+            errors.push(format!("{} is not of type {}", at, schema["type"]));
+            return;
+        }
+
+        if let Some(values) = schema["enum"].as_array() {
+            if !values.contains(value) {
+                errors.push(format!("{} is not one of {:?}", at, values));
+            }
+        }
+        if let (Some(min), Some(n)) = (schema["minimum"].as_f64(), value.as_f64()) {
+            if n < min {
+                errors.push(format!("{} is below {}", at, min));
+            }
+        }
+        if let (Some(max), Some(n)) = (schema["maximum"].as_f64(), value.as_f64()) {
+            if n > max {
+                errors.push(format!("{} is above {}", at, max));
+            }
+        }
+
+        if let Some(items) = value.as_array() {
+            if let Some(min) = schema["minItems"].as_u64() {
+                if (items.len() as u64) < min {
+                    errors.push(format!("{} has less than {} items", at, min));
+                }
+            }
+            if let Some(max) = schema["maxItems"].as_u64() {
+                if items.len() as u64 > max {
+                    errors.push(format!("{} has more than {} items", at, max));
+                }
+            }
+            for (index, item) in items.iter().enumerate() {
+                let at = format!("{}[{}]", at, index);
+                check(document, &schema["items"], item, &at, true, errors);
+            }
+        }
+
+        if let Some(object) = value.as_object() {
+            for name in schema["required"].as_array().into_iter().flatten() {
+                if let Some(name) = name.as_str().filter(|name| !object.contains_key(*name)) {
+                    errors.push(format!("{}.{} is missing", at, name));
+                }
+            }
+            // Only the schemas listing properties describe the object itself.
+            let closed = closed && (!schema["properties"].is_null() || !schema["allOf"].is_null());
+            let declared = declared(document, schema);
+            for (name, field) in object {
+                let at = format!("{}.{}", at, name);
+                match schema["properties"].get(name) {
+                    Some(property) => check(document, property, field, &at, true, errors),
+                    None if closed && !declared.contains(name) => {
+                        errors.push(format!("{} is not documented", at))
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    struct Api {
+        repo: Arc<dyn Repository>,
+        metrics: Metrics,
+        streams: Streams,
+        document: Value,
+    }
+
+    impl Api {
+        fn new() -> Self {
+            Self {
+                repo: Arc::new(InMemoryRepository::new()),
+                metrics: Metrics::default(),
+                streams: Streams::new(Arc::new(Events::default()), 2),
+                document: document(),
+            }
+        }
+
+        /// Sends a request to `url`, routed to `template`, and checks that the
+        /// response is the one the document describes.
+        fn send(&self, method: &str, template: &str, url: &str, body: Option<&str>) -> u16 {
+            let req = rouille::Request::fake_http(
+                method,
+                url,
+                vec![(
+                    String::from("Content-Type"),
+                    String::from("application/json"),
+                )],
+                body.unwrap_or_default().as_bytes().to_vec(),
+            );
+            let res = handle(&self.repo, &self.metrics, &self.streams, &req, &Actor::ci());
+            let status = res.status_code;
+            let content_type = res
+                .headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("Content-Type"))
+                .map(|(_, value)| value.split(';').next().unwrap_or_default().to_string());
+            let mut content = String::new();
+            let _ = res
+                .data
+                .into_reader_and_size()
+                .0
+                .read_to_string(&mut content);
+
+            let response = &self.document["paths"][template][method.to_lowercase()]["responses"]
+                [status.to_string()];
+            assert!(
+                response.is_object(),
+                "{} {} answered an undocumented {}",
+                method,
+                url,
+                status
+            );
+            match content_type {
+                Some(content_type) if content_type.ends_with("json") => {
+                    let schema = &response["content"][content_type.as_str()]["schema"];
+                    assert!(
+                        schema.is_object(),
+                        "{} {} answered an undocumented {} {}",
+                        method,
+                        url,
+                        status,
+                        content_type
+                    );
+                    let value = serde_json::from_str::<Value>(&content).unwrap_or_default();
+                    let errors = validate(&self.document, schema, &value, "body");
+                    assert!(
+                        errors.is_empty(),
+                        "{} {} answered {}: {:?}",
+                        method,
+                        url,
+                        value,
+                        errors
+                    );
+                }
+                _ => {}
+            }
+            status
+        }
+    }
+
+    #[test]
+    fn it_should_describe_the_responses_actually_sent() {
+        let api = Api::new();
+        let pikachu = r#"{"number": 25, "name": "Pikachu", "types": ["Electric"],
+            "stats": {"hp": 35, "attack": 55, "defense": 40, "special_attack": 50,
+            "special_defense": 50, "speed": 90},
+            "abilities": [{"slot": "primary", "name": "Static"}]}"#;
+        let requests = [
+            ("GET", "/health", "/health", None, 200),
+            (
+                "POST",
+                "/abilities",
+                "/abilities",
+                Some(r#"{"name": "Static", "description": "May paralyze on contact"}"#),
+                200,
+            ),
+            (
+                "POST",
+                "/abilities",
+                "/abilities",
+                Some(r#"{"name": "Static"}"#),
+                409,
+            ),
+            ("GET", "/abilities", "/abilities", None, 200),
+            ("POST", "/", "/", Some(pikachu), 200),
+            (
+                "POST",
+                "/",
+                "/",
+                Some(r#"{"number": 0, "name": "", "types": []}"#),
+                400,
+            ),
+            (
+                "POST",
+                "/",
+                "/",
+                Some(r#"{"number": 26, "name": "Raichu", "types": ["Electric"]}"#),
+                200,
+            ),
+            (
+                "POST",
+                "/{number}/forms",
+                "/26/forms",
+                Some(r#"{"form": "alola", "name": "Raichu", "types": ["Electric", "Psychic"]}"#),
+                200,
+            ),
+            (
+                "POST",
+                "/{number}/evolutions",
+                "/25/evolutions",
+                Some(r#"{"to": 26, "trigger": {"kind": "item", "item": "Thunder Stone"}}"#),
+                200,
+            ),
+            ("GET", "/{number}/evolutions", "/26/evolutions", None, 200),
+            (
+                "POST",
+                "/moves",
+                "/moves",
+                Some(
+                    r#"{"name": "Thunderbolt", "type": "Electric", "category": "special",
+                    "power": 90, "accuracy": 100, "pp": 15}"#,
+                ),
+                200,
+            ),
+            (
+                "POST",
+                "/{number}/moves",
+                "/25/moves",
+                Some(r#"{"move": "Thunderbolt", "method": "tm", "generation": 8}"#),
+                200,
+            ),
+            ("GET", "/moves", "/moves", None, 200),
+            ("GET", "/{number}/moves", "/25/moves", None, 200),
+            ("GET", "/", "/?limit=1", None, 200),
+            ("GET", "/", "/?limit=0", None, 400),
+            ("GET", "/search", "/search?q=pikachu", None, 200),
+            ("GET", "/{number}", "/25", None, 200),
+            ("GET", "/{number}", "/151", None, 404),
+            ("GET", "/{number}/forms", "/26/forms", None, 200),
+            (
+                "GET",
+                "/{number}/forms/{form}",
+                "/26/forms/alola",
+                None,
+                200,
+            ),
+            ("GET", "/{number}/stats", "/25/stats?level=50", None, 200),
+            (
+                "GET",
+                "/abilities/{name}/pokemons",
+                "/abilities/Static/pokemons",
+                None,
+                200,
+            ),
+            ("PUT", "/{number}", "/25", Some(pikachu), 200),
+            (
+                "PATCH",
+                "/{number}",
+                "/25",
+                Some(r#"{"name": "Pikachu"}"#),
+                200,
+            ),
+            (
+                "DELETE",
+                "/{number}/forms/{form}",
+                "/26/forms/alola",
+                None,
+                200,
+            ),
+            ("GET", "/trash", "/trash", None, 200),
+            (
+                "POST",
+                "/{number}/forms/{form}/restore",
+                "/26/forms/alola/restore",
+                None,
+                200,
+            ),
+            ("GET", "/{number}/history", "/25/history", None, 200),
+            ("GET", "/audit", "/audit?limit=1000", None, 200),
+            (
+                "POST",
+                "/import",
+                "/import?format=json&on_conflict=replace",
+                Some(r#"[{"number": 4, "name": "Charmander", "types": ["Fire"]}]"#),
+                200,
+            ),
+            ("GET", "/export", "/export", None, 200),
+        ];
+
+        for (method, template, url, body, expected) in requests {
+            assert_eq!(
+                api.send(method, template, url, body),
+                expected,
+                "{} {}",
+                method,
+                url
+            );
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use serde::Serialize;
+
+use crate::api::Status;
+use crate::domain::entities::FieldError;
+
+pub(super) const CONTENT_TYPE: &str = "application/problem+json";
+
+/// An RFC 7807 problem details body.
+#[derive(Serialize)]
+pub(super) struct Problem {
+    #[serde(rename = "type")]
+    problem_type: &'static str,
+    title: &'static str,
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<InvalidParam>,
+}
+
+#[derive(Serialize)]
+struct InvalidParam {
+    field: String,
+    code: &'static str,
+    detail: String,
+}
+
+impl Problem {
+    pub(super) fn new(status: Status) -> Self {
+        let (problem_type, title) = match status {
+            Status::Ok => ("about:blank", "OK"),
+            Status::NotModified => ("about:blank", "Not Modified"),
+            Status::BadRequest => ("/problems/bad-request", "The request is invalid"),
+            Status::Unauthorized => (
+                "/problems/unauthorized",
+                "The request lacks a valid API key",
+            ),
+            Status::Forbidden => (
+                "/problems/forbidden",
+                "The API key does not allow this operation",
+            ),
+            Status::NotFound => ("/problems/not-found", "The resource does not exist"),
+            Status::Conflict => ("/problems/conflict", "The resource already exists"),
+            Status::PreconditionFailed => (
+                "/problems/precondition-failed",
+                "The resource has changed since it was last read",
+            ),
+            Status::InternalServerError => {
+                ("/problems/internal-error", "An unexpected error occurred")
+            }
+            Status::ServiceUnavailable => (
+                "/problems/service-unavailable",
+                "The server is too busy to take the request",
+            ),
+        };
+
+        Self {
+            problem_type,
+            title,
+            status: status.code(),
+            detail: None,
+            errors: vec![],
+        }
+    }
+
+    /// A 400 listing every field of the request that failed validation.
+    pub(super) fn validation(errors: Vec<FieldError>) -> Self {
+        Self {
+            problem_type: "/problems/validation-error",
+            title: "The request contains invalid fields",
+            detail: Some(
+                errors
+                    .iter()
+                    .map(|e| format!("{}: {}", e.field, e.error))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            ),
+            errors: errors
+                .into_iter()
+                .map(|e| InvalidParam {
+                    field: String::from(e.field),
+                    code: e.error.code(),
+                    detail: e.error.to_string(),
+                })
+                .collect(),
+            ..Self::new(Status::BadRequest)
+        }
+    }
+
+    /// A 400 for a request whose body or parameters could not be parsed at all.
+    pub(super) fn malformed(detail: String) -> Self {
+        Self {
+            problem_type: "/problems/malformed-request",
+            title: "The request could not be parsed",
+            detail: Some(detail),
+            ..Self::new(Status::BadRequest)
+        }
+    }
+
+    pub(super) fn with_detail(self, detail: String) -> Self {
+        Self {
+            detail: Some(detail),
+            ..self
+        }
+    }
+}
+
+impl From<Problem> for rouille::Response {
+    fn from(val: Problem) -> Self {
+        let status_code = val.status;
+        let body = serde_json::to_string(&val).unwrap_or_default();
+
+        rouille::Response::from_data(CONTENT_TYPE, body).with_status_code(status_code)
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::api::conditional;
+use crate::api::{fetch_pokemon, internal_error, problem::Problem, Status};
+use crate::domain::entities::Actor;
+use crate::domain::restore_pokemon;
+use crate::repositories::pokemon::Repository;
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    form: Option<String>,
+    actor: &Actor,
+) -> rouille::Response {
+    let req = restore_pokemon::Request {
+        number,
+        form: form.clone(),
+    };
+    match restore_pokemon::execute(repo, req, actor) {
+        Ok(res) => {
+            let etag = conditional::etag(res.revision);
+            rouille::Response::json(&fetch_pokemon::Response::from(res))
+                .with_additional_header("ETag", etag)
+        }
+        Err(restore_pokemon::Error::Unknown(e)) => internal_error(e),
+        Err(restore_pokemon::Error::NotFound) => {
+            let detail = match form {
+                Some(form) => format!("Form '{}' of Pokemon {} is not in the trash", form, number),
+                None => format!("Pokemon {} is not in the trash", number),
+            };
+            rouille::Response::from(Problem::new(Status::NotFound).with_detail(detail))
+        }
+        Err(restore_pokemon::Error::Conflict) => {
+            rouille::Response::from(Problem::new(Status::Conflict).with_detail(format!(
+                "Pokemon {} is in the trash, its default form must be restored first",
+                number
+            )))
+        }
+        Err(restore_pokemon::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use crate::api::{internal_error, problem::Problem, Slot, Stats};
+use crate::domain::search_pokemons;
+use crate::repositories::pokemon::Repository;
+use serde::Serialize;
+
+pub const DEFAULT_LIMIT: u32 = 10;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<Stats>,
+    abilities: Vec<Slot>,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let limit = match req.get_param("limit").map(|limit| limit.parse::<u32>()) {
+        Some(Ok(limit)) => limit,
+        Some(Err(_)) => {
+            return rouille::Response::from(Problem::malformed(String::from(
+                "the query parameter 'limit' must be a positive integer",
+            )))
+        }
+        None => DEFAULT_LIMIT,
+    };
+
+    let domain_req = search_pokemons::Request {
+        query: req.get_param("q").unwrap_or_default(),
+        limit,
+    };
+
+    match search_pokemons::execute(repo, domain_req) {
+        Ok(pokemons) => rouille::Response::json(
+            &pokemons
+                .into_iter()
+                .map(|p| Response {
+                    number: p.number,
+                    name: p.name,
+                    types: p.types,
+                    stats: p.stats.map(Stats::from),
+                    abilities: p.abilities.into_iter().map(Slot::from).collect(),
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Err(search_pokemons::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(search_pokemons::Error::Unknown(e)) => internal_error(e),
+    }
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use crate::api::conditional;
+use crate::api::{internal_error, not_found, problem::Problem, Slot, Stats};
+use crate::domain::entities::{Actor, SlotValue, StatValues};
+use crate::domain::update_pokemon;
+use crate::repositories::pokemon::Repository;
+use serde::{Deserialize, Deserializer, Serialize};
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    form: String,
+    name: String,
+    types: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<Stats>,
+    abilities: Vec<Slot>,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+    types: Vec<String>,
+    stats: Option<Stats>,
+    #[serde(default)]
+    abilities: Vec<Slot>,
+}
+
+#[derive(Deserialize)]
+struct PartialRequest {
+    name: Option<String>,
+    types: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "present")]
+    stats: Option<Option<Stats>>,
+    abilities: Option<Vec<Slot>>,
+}
+
+/// Tells a field set to `null`, `Some(None)`, from a field left out, `None`.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    form: Option<String>,
+    req: &rouille::Request,
+    actor: &Actor,
+) -> rouille::Response {
+    let revisions = conditional::expected_revisions(req);
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => update_pokemon::Request {
+            number,
+            form,
+            name: Some(req.name),
+            types: Some(req.types),
+            // The whole form is replaced, so stats left out are removed.
+            stats: Some(req.stats.map(StatValues::from)),
+            abilities: Some(req.abilities.into_iter().map(SlotValue::from).collect()),
+            revisions,
+        },
+        Err(e) => return rouille::Response::from(Problem::malformed(e.to_string())),
+    };
+
+    execute(repo, req, actor)
+}
+
+pub fn serve_partial(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    form: Option<String>,
+    req: &rouille::Request,
+    actor: &Actor,
+) -> rouille::Response {
+    let revisions = conditional::expected_revisions(req);
+    let req = match rouille::input::json_input::<PartialRequest>(req) {
+        Ok(req) => update_pokemon::Request {
+            number,
+            form,
+            name: req.name,
+            types: req.types,
+            // The stats set to null are removed, those left out are kept.
+            stats: req.stats.map(|stats| stats.map(StatValues::from)),
+            abilities: req
+                .abilities
+                .map(|abilities| abilities.into_iter().map(SlotValue::from).collect()),
+            revisions,
+        },
+        Err(e) => return rouille::Response::from(Problem::malformed(e.to_string())),
+    };
+
+    execute(repo, req, actor)
+}
+
+fn execute(
+    repo: Arc<dyn Repository>,
+    req: update_pokemon::Request,
+    actor: &Actor,
+) -> rouille::Response {
+    let number = req.number;
+    let requested_form = req.form.clone();
+
+    match update_pokemon::execute(repo, req, actor) {
+        Ok(update_pokemon::UpdateResponse {
+            number,
+            form,
+            name,
+            types,
+            stats,
+            abilities,
+            revision,
+        }) => rouille::Response::json(&Response {
+            number,
+            form,
+            name,
+            types,
+            stats: stats.map(Stats::from),
+            abilities: abilities.into_iter().map(Slot::from).collect(),
+        })
+        .with_additional_header("ETag", conditional::etag(revision)),
+        Err(update_pokemon::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation(errors))
+        }
+        Err(update_pokemon::Error::NotFound) => not_found(number, requested_form.as_deref()),
+        Err(update_pokemon::Error::PreconditionFailed(revision)) => {
+            conditional::precondition_failed(revision)
+        }
+        Err(update_pokemon::Error::Unknown(e)) => internal_error(e),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_should_tell_stats_set_to_null_from_stats_left_out() {
+        let stats = |body: &str| {
+            serde_json::from_str::<PartialRequest>(body)
+                .ok()
+                .map(|req| req.stats.map(|stats| stats.is_some()))
+        };
+
+        assert_eq!(stats(r#"{"name": "Pikachu"}"#), Some(None));
+        assert_eq!(stats(r#"{"stats": null}"#), Some(Some(false)));
+        assert_eq!(
+            stats(
+                r#"{"stats": {"hp": 35, "attack": 55, "defense": 40, "special_attack": 50, "special_defense": 50, "speed": 90}}"#
+            ),
+            Some(Some(true))
+        );
+    }
+}
@@ -0,0 +1,770 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+
+use crate::cli::output::{
+    HistoryRow, ImportReport, KeyRow, MoveRow, NewKey, Output, RejectedLine, Row, StatsRow,
+};
+use crate::cli::{print_field_errors, print_unknown_error};
+use crate::domain::create_api_key::ApiKeyResponse;
+use crate::domain::entities::{Actor, FieldError, StatValues};
+use crate::domain::{
+    calculate_stats, create_api_key, create_pokemon, delete_pokemon, export_pokemons,
+    fetch_all_pokemons, fetch_api_keys, fetch_history, fetch_learnset, fetch_pokemon,
+    import_pokemons, purge_pokemons, revoke_api_key,
+};
+use crate::repositories::pokemon::{Repository, Source};
+
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_UNKNOWN: i32 = 1;
+pub const EXIT_BAD_REQUEST: i32 = 2;
+pub const EXIT_NOT_FOUND: i32 = 3;
+pub const EXIT_CONFLICT: i32 = 4;
+
+const EXIT_CODES: &str = "Exit codes: 0 success, 1 unexpected error, 2 invalid input, \
+                          3 Pokemon not found, 4 Pokemon already exists";
+
+const KEY_EXIT_CODES: &str = "Exit codes: 0 success, 1 unexpected error, 2 invalid input, \
+                              3 key not found, 4 key already exists";
+
+/// The non-interactive subcommands, meant to be driven from scripts.
+pub fn subcommands() -> Vec<Command> {
+    vec![
+        Command::new("list")
+            .about("Lists the Pokemons")
+            .arg(
+                Arg::new("type")
+                    .long("type")
+                    .help("Only lists Pokemons of this type"),
+            )
+            .arg(
+                Arg::new("sort")
+                    .long("sort")
+                    .value_parser(["number", "name"])
+                    .help("Sorts the Pokemons by number or name"),
+            )
+            .arg(
+                Arg::new("order")
+                    .long("order")
+                    .value_parser(["asc", "desc"])
+                    .help("Sort order"),
+            )
+            .arg(
+                Arg::new("limit")
+                    .long("limit")
+                    .value_parser(value_parser!(u32))
+                    .help("Maximum number of Pokemons to list"),
+            )
+            .arg(output_arg())
+            .after_help(EXIT_CODES),
+        Command::new("get")
+            .about("Shows a Pokemon")
+            .arg(number_arg())
+            .arg(form_arg())
+            .arg(output_arg())
+            .after_help(EXIT_CODES),
+        Command::new("create")
+            .about("Creates a Pokemon")
+            .arg(
+                Arg::new("number")
+                    .long("number")
+                    .required(true)
+                    .value_parser(value_parser!(u16)),
+            )
+            .arg(form_arg())
+            .arg(Arg::new("name").long("name").required(true))
+            .arg(
+                Arg::new("type")
+                    .long("type")
+                    .required(true)
+                    .action(ArgAction::Append)
+                    .help("A type of the Pokemon, repeat for dual-type Pokemons"),
+            )
+            .arg(stats_arg("stats", "The base stats"))
+            .arg(output_arg())
+            .after_help(EXIT_CODES),
+        Command::new("stats")
+            .about("Calculates the stats of a Pokemon at a given level")
+            .arg(number_arg())
+            .arg(form_arg())
+            .arg(
+                Arg::new("level")
+                    .long("level")
+                    .value_parser(value_parser!(u8))
+                    .default_value("50"),
+            )
+            .arg(Arg::new("nature").long("nature").default_value("Hardy"))
+            .arg(stats_arg(
+                "ivs",
+                "The individual values, 31 each by default",
+            ))
+            .arg(stats_arg("evs", "The effort values, 0 each by default"))
+            .arg(output_arg())
+            .after_help(EXIT_CODES),
+        Command::new("moves")
+            .about("Lists the moves a Pokemon can learn")
+            .arg(number_arg())
+            .arg(
+                Arg::new("method")
+                    .long("method")
+                    .value_parser(["level", "tm", "egg", "tutor"])
+                    .help("Only lists the moves learnt this way"),
+            )
+            .arg(
+                Arg::new("generation")
+                    .long("generation")
+                    .value_parser(value_parser!(u8))
+                    .help("Only lists the moves learnt in this generation"),
+            )
+            .arg(output_arg())
+            .after_help(EXIT_CODES),
+        Command::new("import")
+            .about("Imports Pokemons from a file, in a single transaction")
+            .long_about(
+                "Imports Pokemons from a file, in a single transaction. Rejected lines are \
+                 reported with their reason and make the command exit with 2. With \
+                 --on-conflict fail, nothing is imported if a Pokemon already exists.",
+            )
+            .arg(
+                Arg::new("file")
+                    .required(true)
+                    .value_name("FILE")
+                    .help("The file to import, - to read the standard input"),
+            )
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .required(true)
+                    .value_parser(["json", "jsonl", "csv"])
+                    .help("A JSON array or JSON Lines shaped like the API bodies, or CSV with a header"),
+            )
+            .arg(
+                Arg::new("on-conflict")
+                    .long("on-conflict")
+                    .value_parser(["skip", "replace", "fail"])
+                    .default_value("fail")
+                    .help("What to do with a Pokemon that already exists"),
+            )
+            .arg(output_arg())
+            .after_help(EXIT_CODES),
+        Command::new("export")
+            .about("Writes every Pokemon to the standard output, ordered by number")
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .value_parser(["json", "jsonl", "csv", "sql"])
+                    .default_value("json")
+                    .help("The json, jsonl and csv files can be imported back"),
+            )
+            .after_help(EXIT_CODES),
+        Command::new("delete")
+            .about("Moves a Pokemon, or only one of its alternate forms, to the trash")
+            .arg(number_arg())
+            .arg(form_arg())
+            .arg(output_arg())
+            .after_help(EXIT_CODES),
+        Command::new("purge")
+            .about("Removes for good the Pokemons that have been in the trash for too long")
+            .arg(
+                Arg::new("older-than")
+                    .long("older-than")
+                    .value_name("DURATION")
+                    .default_value(purge_pokemons::DEFAULT_RETENTION)
+                    .help("How long deleted Pokemons are kept, such as 30d or 12h"),
+            )
+            .arg(output_arg())
+            .after_help(EXIT_CODES),
+        Command::new("history")
+            .about("Lists every change made to every form of a Pokemon, the oldest first")
+            .arg(number_arg())
+            .arg(output_arg())
+            .after_help(EXIT_CODES),
+        Command::new("keys")
+            .about("Manages the API keys, stored in the sqlite database")
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("create")
+                    .about("Creates a key and shows its secret, only this once")
+                    .arg(
+                        Arg::new("name")
+                            .long("name")
+                            .required(true)
+                            .help("A unique name telling who uses the key"),
+                    )
+                    .arg(
+                        Arg::new("role")
+                            .long("role")
+                            .required(true)
+                            .value_parser(["reader", "editor", "admin"])
+                            .help("An editor can change the Pokedex, an admin can also import"),
+                    )
+                    .arg(output_arg())
+                    .after_help(KEY_EXIT_CODES),
+            )
+            .subcommand(
+                Command::new("list")
+                    .about("Lists the keys, the revoked ones included")
+                    .arg(output_arg())
+                    .after_help(KEY_EXIT_CODES),
+            )
+            .subcommand(
+                Command::new("revoke")
+                    .about("Revokes a key, which is refused from then on")
+                    .arg(Arg::new("name").required(true).help("The name of the key"))
+                    .arg(output_arg())
+                    .after_help(KEY_EXIT_CODES),
+            ),
+    ]
+}
+
+fn number_arg() -> Arg {
+    Arg::new("number")
+        .required(true)
+        .value_parser(value_parser!(u16))
+        .help("The Pokemon number")
+}
+
+fn form_arg() -> Arg {
+    Arg::new("form")
+        .long("form")
+        .help("The form, the default one when omitted")
+}
+
+/// Six comma separated values, in HP/Atk/Def/SpA/SpD/Spe order.
+fn stats_arg(name: &'static str, help: &'static str) -> Arg {
+    Arg::new(name)
+        .long(name)
+        .value_parser(parse_stats)
+        .value_name("HP,ATK,DEF,SPA,SPD,SPE")
+        .help(help)
+}
+
+fn parse_stats(value: &str) -> Result<StatValues, String> {
+    match value
+        .split(',')
+        .map(|v| v.trim().parse::<u16>())
+        .collect::<Result<Vec<_>, _>>()
+        .map(<[u16; 6]>::try_from)
+    {
+        Ok(Ok(values)) => Ok(StatValues::from(values)),
+        _ => Err(String::from(
+            "expected six comma separated positive integers",
+        )),
+    }
+}
+
+fn output_arg() -> Arg {
+    Arg::new("output")
+        .long("output")
+        .short('o')
+        .value_parser(["table", "json", "csv"])
+        .default_value("table")
+        .help("Output format")
+}
+
+/// Runs a subcommand and returns the process exit code.
+pub fn run(repo: Arc<dyn Repository>, name: &str, matches: &ArgMatches) -> i32 {
+    let output = output_format(matches);
+
+    match name {
+        "list" => list(repo, matches, output),
+        "get" => get(repo, matches, output),
+        "create" => create(repo, matches, output),
+        "stats" => stats(repo, matches, output),
+        "moves" => moves(repo, matches, output),
+        "import" => import(repo, matches, output),
+        "export" => export(repo, matches),
+        "delete" => delete(repo, matches, output),
+        "purge" => purge(repo, matches, output),
+        "history" => history(repo, matches, output),
+        "keys" => match matches.subcommand() {
+            Some(("create", matches)) => create_key(repo, matches, output_format(matches)),
+            Some(("list", matches)) => list_keys(repo, output_format(matches)),
+            Some(("revoke", matches)) => revoke_key(repo, matches, output_format(matches)),
+            _ => EXIT_BAD_REQUEST,
+        },
+        _ => EXIT_BAD_REQUEST,
+    }
+}
+
+/// Not every subcommand has an output format, `export` writes its own.
+fn output_format(matches: &ArgMatches) -> Output {
+    match matches
+        .try_get_one::<String>("output")
+        .ok()
+        .flatten()
+        .map(String::as_str)
+    {
+        Some("json") => Output::Json,
+        Some("csv") => Output::Csv,
+        _ => Output::Table,
+    }
+}
+
+fn list(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let req = fetch_all_pokemons::Request {
+        limit: matches.get_one::<u32>("limit").copied(),
+        sort: matches.get_one::<String>("sort").cloned(),
+        order: matches.get_one::<String>("order").cloned(),
+        pokemon_type: matches.get_one::<String>("type").cloned(),
+        ..fetch_all_pokemons::Request::default()
+    };
+
+    match fetch_all_pokemons::execute(repo, req) {
+        Ok(page) => {
+            output.print_many(
+                page.pokemons
+                    .into_iter()
+                    .map(|p| Row {
+                        number: p.number,
+                        name: p.name,
+                        types: p.types,
+                    })
+                    .collect(),
+            );
+            EXIT_OK
+        }
+        Err(fetch_all_pokemons::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(fetch_all_pokemons::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn get(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let number = matches
+        .get_one::<u16>("number")
+        .copied()
+        .unwrap_or_default();
+
+    let req = fetch_pokemon::Request {
+        number,
+        form: matches.get_one::<String>("form").cloned(),
+    };
+
+    match fetch_pokemon::execute(repo, req) {
+        Ok(res) => {
+            output.print_one(Row {
+                number: res.number,
+                name: res.name,
+                types: res.types,
+            });
+            EXIT_OK
+        }
+        Err(fetch_pokemon::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(fetch_pokemon::Error::NotFound) => not_found(number),
+        Err(fetch_pokemon::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn create(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let req = create_pokemon::Request {
+        number: matches
+            .get_one::<u16>("number")
+            .copied()
+            .unwrap_or_default(),
+        form: matches.get_one::<String>("form").cloned(),
+        name: matches
+            .get_one::<String>("name")
+            .cloned()
+            .unwrap_or_default(),
+        types: matches
+            .get_many::<String>("type")
+            .map(|types| types.cloned().collect())
+            .unwrap_or_default(),
+        stats: matches.get_one::<StatValues>("stats").copied(),
+        abilities: vec![],
+    };
+    let number = req.number;
+    let form = req.form.clone();
+
+    match create_pokemon::execute(repo, req, &Actor::cli()) {
+        Ok(res) => {
+            output.print_one(Row {
+                number: res.number,
+                name: res.name,
+                types: res.types,
+            });
+            EXIT_OK
+        }
+        Err(create_pokemon::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(create_pokemon::Error::NotFound) => not_found(number),
+        Err(create_pokemon::Error::Conflict) => {
+            match form {
+                Some(form) => eprintln!("The form '{}' of Pokemon {} already exists", form, number),
+                None => eprintln!("A Pokemon with number {} already exists", number),
+            }
+            EXIT_CONFLICT
+        }
+        Err(create_pokemon::Error::Deleted) => {
+            match form {
+                Some(form) => eprintln!(
+                    "The form '{}' of Pokemon {} is in the trash, restore or purge it first",
+                    form, number
+                ),
+                None => eprintln!(
+                    "Pokemon {} is in the trash, restore or purge it first",
+                    number
+                ),
+            }
+            EXIT_CONFLICT
+        }
+        Err(create_pokemon::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn stats(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let number = matches
+        .get_one::<u16>("number")
+        .copied()
+        .unwrap_or_default();
+    let req = calculate_stats::Request {
+        number,
+        form: matches.get_one::<String>("form").cloned(),
+        level: matches.get_one::<u8>("level").copied().unwrap_or_default(),
+        nature: matches
+            .get_one::<String>("nature")
+            .cloned()
+            .unwrap_or_default(),
+        ivs: matches
+            .get_one::<StatValues>("ivs")
+            .copied()
+            .unwrap_or_else(|| StatValues::uniform(31)),
+        evs: matches
+            .get_one::<StatValues>("evs")
+            .copied()
+            .unwrap_or_default(),
+    };
+
+    match calculate_stats::execute(repo, req) {
+        Ok(res) => {
+            output.print_stats(StatsRow {
+                number: res.number,
+                name: res.name,
+                level: res.level,
+                nature: res.nature,
+                hp: res.stats.hp,
+                attack: res.stats.attack,
+                defense: res.stats.defense,
+                special_attack: res.stats.special_attack,
+                special_defense: res.stats.special_defense,
+                speed: res.stats.speed,
+            });
+            EXIT_OK
+        }
+        Err(calculate_stats::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(calculate_stats::Error::NotFound) => not_found(number),
+        Err(calculate_stats::Error::NoBaseStats) => {
+            eprintln!("The base stats of Pokemon {} are unknown", number);
+            EXIT_NOT_FOUND
+        }
+        Err(calculate_stats::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn moves(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let number = matches
+        .get_one::<u16>("number")
+        .copied()
+        .unwrap_or_default();
+    let req = fetch_learnset::Request {
+        number,
+        method: matches.get_one::<String>("method").cloned(),
+        generation: matches.get_one::<u8>("generation").copied(),
+    };
+
+    match fetch_learnset::execute(repo, req) {
+        Ok(entries) => {
+            output.print_moves(
+                entries
+                    .into_iter()
+                    .map(|e| MoveRow {
+                        generation: e.generation,
+                        method: e.method,
+                        level: e.level,
+                        name: e.details.name,
+                        move_type: e.details.move_type,
+                        category: e.details.category,
+                        power: e.details.power,
+                        accuracy: e.details.accuracy,
+                        pp: e.details.pp,
+                    })
+                    .collect(),
+            );
+            EXIT_OK
+        }
+        Err(fetch_learnset::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(fetch_learnset::Error::NotFound) => not_found(number),
+        Err(fetch_learnset::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn import(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let path = matches
+        .get_one::<String>("file")
+        .cloned()
+        .unwrap_or_default();
+    let content = match path.as_str() {
+        "-" => std::io::read_to_string(std::io::stdin()),
+        path => std::fs::read_to_string(path),
+    };
+    let content = match content {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Cannot read {}: {}", path, e);
+            return EXIT_BAD_REQUEST;
+        }
+    };
+
+    let req = import_pokemons::Request {
+        format: matches
+            .get_one::<String>("format")
+            .cloned()
+            .unwrap_or_default(),
+        on_conflict: matches
+            .get_one::<String>("on-conflict")
+            .cloned()
+            .unwrap_or_default(),
+        content,
+    };
+
+    match import_pokemons::execute(repo, req, &Actor::cli()) {
+        Ok(res) => {
+            let code = if res.rejected.is_empty() {
+                EXIT_OK
+            } else {
+                EXIT_BAD_REQUEST
+            };
+            output.print_import(ImportReport {
+                inserted: res.inserted,
+                replaced: res.replaced,
+                skipped: res.skipped,
+                rejected: res
+                    .rejected
+                    .into_iter()
+                    .map(|r| RejectedLine {
+                        line: r.line,
+                        reason: r.reason,
+                    })
+                    .collect(),
+            });
+            code
+        }
+        Err(import_pokemons::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(import_pokemons::Error::Conflict { line }) => {
+            eprintln!(
+                "The Pokemon on line {} already exists, nothing was imported",
+                line
+            );
+            EXIT_CONFLICT
+        }
+        Err(import_pokemons::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn export(repo: Arc<dyn Repository>, matches: &ArgMatches) -> i32 {
+    let req = export_pokemons::Request {
+        format: matches
+            .get_one::<String>("format")
+            .cloned()
+            .unwrap_or_default(),
+    };
+
+    let export = match export_pokemons::execute(repo, req) {
+        Ok(export) => export,
+        Err(export_pokemons::Error::BadRequest(errors)) => return bad_request(&errors),
+    };
+
+    let mut stdout = std::io::stdout().lock();
+    for chunk in export {
+        match chunk.map(|chunk| stdout.write_all(chunk.as_bytes())) {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                eprintln!("Cannot write the export: {}", e);
+                return EXIT_UNKNOWN;
+            }
+            Err(e) => return unknown(e),
+        }
+    }
+
+    EXIT_OK
+}
+
+fn delete(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let number = matches
+        .get_one::<u16>("number")
+        .copied()
+        .unwrap_or_default();
+
+    let req = delete_pokemon::Request {
+        number,
+        form: matches.get_one::<String>("form").cloned(),
+        revisions: None,
+    };
+
+    match delete_pokemon::execute(repo, req, &Actor::cli()) {
+        Ok(()) => {
+            if let Output::Table = output {
+                println!("The Pokemon #{} has been moved to the trash", number);
+            }
+            EXIT_OK
+        }
+        Err(delete_pokemon::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(delete_pokemon::Error::NotFound) => not_found(number),
+        Err(delete_pokemon::Error::PreconditionFailed(_)) => {
+            eprintln!("The Pokemon #{} has changed in the meantime", number);
+            EXIT_CONFLICT
+        }
+        Err(delete_pokemon::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn purge(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let req = purge_pokemons::Request {
+        older_than: matches
+            .get_one::<String>("older-than")
+            .cloned()
+            .unwrap_or_default(),
+    };
+
+    match purge_pokemons::execute(repo, req, &Actor::cli()) {
+        Ok(purged) => {
+            output.print_many(
+                purged
+                    .into_iter()
+                    .map(|p| Row {
+                        number: p.number,
+                        name: p.name,
+                        types: p.types,
+                    })
+                    .collect(),
+            );
+            EXIT_OK
+        }
+        Err(purge_pokemons::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(purge_pokemons::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn history(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let number = matches
+        .get_one::<u16>("number")
+        .copied()
+        .unwrap_or_default();
+
+    match fetch_history::execute(repo, fetch_history::Request { number }) {
+        Ok(events) => {
+            output.print_history(
+                events
+                    .into_iter()
+                    .map(|e| {
+                        let form = e.after.or(e.before);
+                        HistoryRow {
+                            id: e.id,
+                            timestamp: e.timestamp,
+                            action: e.action,
+                            form: e.form,
+                            revision: form.as_ref().map(|p| p.revision),
+                            name: form.map(|p| p.name),
+                            actor: e.actor,
+                            request_id: e.request_id,
+                        }
+                    })
+                    .collect(),
+            );
+            EXIT_OK
+        }
+        Err(fetch_history::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(fetch_history::Error::NotFound) => {
+            eprintln!("The Pokemon #{} has never been changed", number);
+            EXIT_NOT_FOUND
+        }
+        Err(fetch_history::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn create_key(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let req = create_api_key::Request {
+        name: matches
+            .get_one::<String>("name")
+            .cloned()
+            .unwrap_or_default(),
+        role: matches
+            .get_one::<String>("role")
+            .cloned()
+            .unwrap_or_default(),
+    };
+    let name = req.name.clone();
+
+    match create_api_key::execute(repo, req) {
+        Ok(res) => {
+            output.print_new_key(NewKey {
+                key: key_row(res.key),
+                secret: res.secret,
+            });
+            EXIT_OK
+        }
+        Err(create_api_key::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(create_api_key::Error::Conflict) => {
+            eprintln!("A key named '{}' already exists", name);
+            EXIT_CONFLICT
+        }
+        Err(create_api_key::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn list_keys(repo: Arc<dyn Repository>, output: Output) -> i32 {
+    match fetch_api_keys::execute(repo) {
+        Ok(keys) => {
+            output.print_keys(keys.into_iter().map(key_row).collect());
+            EXIT_OK
+        }
+        Err(fetch_api_keys::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn revoke_key(repo: Arc<dyn Repository>, matches: &ArgMatches, output: Output) -> i32 {
+    let name = matches
+        .get_one::<String>("name")
+        .cloned()
+        .unwrap_or_default();
+
+    match revoke_api_key::execute(repo, revoke_api_key::Request { name: name.clone() }) {
+        Ok(()) => {
+            if let Output::Table = output {
+                println!("The key '{}' has been revoked", name);
+            }
+            EXIT_OK
+        }
+        Err(revoke_api_key::Error::BadRequest(errors)) => bad_request(&errors),
+        Err(revoke_api_key::Error::NotFound) => {
+            eprintln!("There is no key named '{}'", name);
+            EXIT_NOT_FOUND
+        }
+        Err(revoke_api_key::Error::Unknown(e)) => unknown(e),
+    }
+}
+
+fn key_row(key: ApiKeyResponse) -> KeyRow {
+    KeyRow {
+        name: key.name,
+        role: key.role,
+        prefix: key.prefix,
+        created_at: key.created_at,
+        revoked: key.revoked,
+    }
+}
+
+fn bad_request(errors: &[FieldError]) -> i32 {
+    print_field_errors(errors);
+    EXIT_BAD_REQUEST
+}
+
+fn not_found(number: u16) -> i32 {
+    eprintln!("There is no Pokemon with number {}", number);
+    EXIT_NOT_FOUND
+}
+
+fn unknown(e: Source) -> i32 {
+    print_unknown_error(e);
+    EXIT_UNKNOWN
+}
@@ -1,44 +1,49 @@
 use std::sync::Arc;
 
-use crate::cli::{prompt_name, prompt_number, prompt_types};
+use crate::cli::output::{Output, Row};
+use crate::cli::{
+    print_error, print_field_errors, print_unknown_error, prompt_form, prompt_name, prompt_number,
+    prompt_types,
+};
 use crate::domain::create_pokemon;
+use crate::domain::entities::Actor;
 use crate::repositories::pokemon::Repository;
 
-#[derive(Debug)]
-struct Response {
-    number: u16,
-    name: String,
-    types: Vec<String>,
-}
-
 pub fn run(repo: Arc<dyn Repository>) {
     let number = prompt_number();
+    let form = prompt_form();
     let name = prompt_name();
     let types = prompt_types();
 
-    let req = match (number, name, types) {
-        (Ok(number), Ok(name), Ok(types)) => create_pokemon::Request {
+    let req = match (number, form, name, types) {
+        (Ok(number), Ok(form), Ok(name), Ok(types)) => create_pokemon::Request {
             number,
+            form,
             name,
             types,
+            stats: None,
+            abilities: vec![],
         },
         _ => {
-            println!("An error occured during the prompt");
+            eprintln!("An error occured during the prompt");
             return;
         }
     };
 
-    match create_pokemon::execute(repo, req) {
-        Ok(res) => println!(
-            "{:?}",
-            Response {
-                number: res.number,
-                name: res.name,
-                types: res.types,
-            }
-        ),
-        Err(create_pokemon::Error::BadRequest) => println!("The request is invalid"),
-        Err(create_pokemon::Error::Conflict) => println!("The pokemon already exists"),
-        Err(create_pokemon::Error::Unknown) => println!("An unknown error occured"),
+    match create_pokemon::execute(repo, req, &Actor::cli()) {
+        Ok(res) => Output::Table.print_one(Row {
+            number: res.number,
+            name: res.name,
+            types: res.types,
+        }),
+        Err(create_pokemon::Error::BadRequest(errors)) => print_field_errors(&errors),
+        Err(create_pokemon::Error::NotFound) => {
+            print_error("The default form of the Pokemon does not exist")
+        }
+        Err(create_pokemon::Error::Conflict) => print_error("The pokemon already exists"),
+        Err(create_pokemon::Error::Deleted) => {
+            print_error("The pokemon is in the trash, restore or purge it first")
+        }
+        Err(create_pokemon::Error::Unknown(e)) => print_unknown_error(e),
     }
 }
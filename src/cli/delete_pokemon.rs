@@ -1,21 +1,36 @@
 use std::sync::Arc;
 
+use crate::domain::entities::Actor;
 use crate::{domain::delete_pokemon, repositories::pokemon::Repository};
 
-use super::prompt_number;
+use super::{print_error, print_field_errors, print_unknown_error, prompt_form, prompt_number};
 
 pub fn run(repo: Arc<dyn Repository>) {
     let number = prompt_number();
+    let form = prompt_form();
 
-    match number {
-        Ok(number) => match delete_pokemon::execute(repo, delete_pokemon::Request { number }) {
-            Ok(_res) => println!("The pokemon has been deleted"),
-            Err(delete_pokemon::Error::BadRequest) => println!("The request is invalid"),
-            Err(delete_pokemon::Error::NotFound) => println!("The Pokemon does not exist"),
-            Err(delete_pokemon::Error::Unknown) => println!("An unknown error occured"),
-        },
-        Err(_) => {
-            println!("An error occured during the prompt");
+    match (number, form) {
+        (Ok(number), Ok(form)) => {
+            match delete_pokemon::execute(
+                repo,
+                delete_pokemon::Request {
+                    number,
+                    form,
+                    revisions: None,
+                },
+                &Actor::cli(),
+            ) {
+                Ok(_res) => println!("The Pokemon has been moved to the trash"),
+                Err(delete_pokemon::Error::BadRequest(errors)) => print_field_errors(&errors),
+                Err(delete_pokemon::Error::NotFound) => print_error("The Pokemon does not exist"),
+                Err(delete_pokemon::Error::PreconditionFailed(_)) => {
+                    print_error("The Pokemon has changed in the meantime")
+                }
+                Err(delete_pokemon::Error::Unknown(e)) => print_unknown_error(e),
+            }
+        }
+        _ => {
+            eprintln!("An error occured during the prompt");
         }
     }
 }
@@ -2,25 +2,22 @@ use std::sync::Arc;
 
 use crate::{domain::fetch_all_pokemons, repositories::pokemon::Repository};
 
-#[derive(Debug)]
-struct Response {
-    number: u16,
-    name: String,
-    types: Vec<String>,
-}
+use super::output::{Output, Row};
+use super::{print_field_errors, print_unknown_error};
 
 pub fn run(repo: Arc<dyn Repository>) {
-    match fetch_all_pokemons::execute(repo) {
-        Ok(res) => res.into_iter().for_each(|p| {
-            println!(
-                "{:?}",
-                Response {
+    match fetch_all_pokemons::execute(repo, fetch_all_pokemons::Request::default()) {
+        Ok(page) => Output::Table.print_many(
+            page.pokemons
+                .into_iter()
+                .map(|p| Row {
                     number: p.number,
                     name: p.name,
                     types: p.types,
-                }
-            )
-        }),
-        Err(fetch_all_pokemons::Error::Unknown) => println!("An unknown error occured."),
+                })
+                .collect(),
+        ),
+        Err(fetch_all_pokemons::Error::BadRequest(errors)) => print_field_errors(&errors),
+        Err(fetch_all_pokemons::Error::Unknown(e)) => print_unknown_error(e),
     }
 }
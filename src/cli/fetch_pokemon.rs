@@ -2,36 +2,28 @@ use std::sync::Arc;
 
 use crate::{domain::fetch_pokemon, repositories::pokemon::Repository};
 
-use super::prompt_number;
-
-#[derive(Debug)]
-struct Response {
-    number: u16,
-    name: String,
-    types: Vec<String>,
-}
+use super::output::{Output, Row};
+use super::{print_error, print_field_errors, print_unknown_error, prompt_form, prompt_number};
 
 pub fn run(repo: Arc<dyn Repository>) {
     let number = prompt_number();
+    let form = prompt_form();
 
-    match number {
-        Ok(number) => match fetch_pokemon::execute(repo, fetch_pokemon::Request { number }) {
-            Ok(res) => {
-                println!(
-                    "{:?}",
-                    Response {
-                        number: res.number,
-                        name: res.name,
-                        types: res.types,
-                    }
-                )
+    match (number, form) {
+        (Ok(number), Ok(form)) => {
+            match fetch_pokemon::execute(repo, fetch_pokemon::Request { number, form }) {
+                Ok(res) => Output::Table.print_one(Row {
+                    number: res.number,
+                    name: res.name,
+                    types: res.types,
+                }),
+                Err(fetch_pokemon::Error::BadRequest(errors)) => print_field_errors(&errors),
+                Err(fetch_pokemon::Error::NotFound) => print_error("The Pokemon does not exist"),
+                Err(fetch_pokemon::Error::Unknown(e)) => print_unknown_error(e),
             }
-            Err(fetch_pokemon::Error::BadRequest) => println!("The request is invalid"),
-            Err(fetch_pokemon::Error::NotFound) => println!("The Pokemon does not exist"),
-            Err(fetch_pokemon::Error::Unknown) => println!("An unknown error occured"),
-        },
-        Err(_) => {
-            println!("An error occured during the prompt");
+        }
+        _ => {
+            eprintln!("An error occured during the prompt");
         }
     }
 }
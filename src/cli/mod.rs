@@ -1,20 +1,30 @@
 use dialoguer::{theme::ColorfulTheme, Input, MultiSelect, Select};
 
-use crate::repositories::pokemon::Repository;
+use crate::domain::entities::{FieldError, PokemonType};
+use crate::repositories::pokemon::{Repository, Source};
 use std::sync::Arc;
+use tracing::debug;
 
+pub mod commands;
 mod create_pokemon;
 mod delete_pokemon;
 mod fetch_all_pokemons;
 mod fetch_pokemon;
+mod output;
+mod restore_pokemon;
+mod search_pokemons;
+mod update_pokemon;
 
 pub fn run(repo: Arc<dyn Repository>) {
     loop {
         let choices = [
             "Fetch all Pokemons",
             "Fetch a Pokemon",
+            "Search a Pokemon",
             "Create a Pokemon",
+            "Update a Pokemon",
             "Delete a Pokemon",
+            "Restore a Pokemon",
             "Exit",
         ];
 
@@ -31,9 +41,12 @@ pub fn run(repo: Arc<dyn Repository>) {
         match index {
             0 => fetch_all_pokemons::run(repo.clone()),
             1 => fetch_pokemon::run(repo.clone()),
-            2 => create_pokemon::run(repo.clone()),
-            3 => delete_pokemon::run(repo.clone()),
-            4 => break,
+            2 => search_pokemons::run(repo.clone()),
+            3 => create_pokemon::run(repo.clone()),
+            4 => update_pokemon::run(repo.clone()),
+            5 => delete_pokemon::run(repo.clone()),
+            6 => restore_pokemon::run(repo.clone()),
+            7 => break,
             _ => continue,
         }
     }
@@ -46,6 +59,18 @@ pub fn prompt_number() -> Result<u16, ()> {
     }
 }
 
+pub fn prompt_form() -> Result<Option<String>, ()> {
+    match Input::<String>::new()
+        .with_prompt("Form (leave empty for the default form)")
+        .allow_empty(true)
+        .interact_text()
+    {
+        Ok(form) if form.is_empty() => Ok(None),
+        Ok(form) => Ok(Some(form)),
+        _ => Err(()),
+    }
+}
+
 pub fn prompt_name() -> Result<String, ()> {
     match Input::new().with_prompt("Pokemon name").interact_text() {
         Ok(name) => Ok(name),
@@ -53,8 +78,20 @@ pub fn prompt_name() -> Result<String, ()> {
     }
 }
 
+pub fn prompt_optional_name() -> Result<Option<String>, ()> {
+    match Input::<String>::new()
+        .with_prompt("Pokemon name (leave empty to keep)")
+        .allow_empty(true)
+        .interact_text()
+    {
+        Ok(name) if name.is_empty() => Ok(None),
+        Ok(name) => Ok(Some(name)),
+        _ => Err(()),
+    }
+}
+
 pub fn prompt_types() -> Result<Vec<String>, ()> {
-    let types = ["Electric", "Fire"];
+    let types = PokemonType::ALL.map(String::from);
     match MultiSelect::new()
         .with_prompt("Pokemon types")
         .items(&types)
@@ -62,8 +99,27 @@ pub fn prompt_types() -> Result<Vec<String>, ()> {
     {
         Ok(indexs) => Ok(indexs
             .into_iter()
-            .map(|index| String::from(types[index]))
+            .map(|index| types[index].clone())
             .collect::<Vec<String>>()),
         _ => Err(()),
     }
 }
+
+/// Tells the user why their request failed, on stderr like the other errors.
+pub fn print_error(message: &str) {
+    eprintln!("{}", message);
+}
+
+pub fn print_field_errors(errors: &[FieldError]) {
+    eprintln!("The request is invalid:");
+    errors
+        .iter()
+        .for_each(|e| eprintln!("  - {}: {}", e.field, e.error));
+}
+
+/// Tells the user that something went wrong, the error itself being logged
+/// with its causes for whoever investigates.
+pub fn print_unknown_error(e: Source) {
+    debug!(error = ?e, "unknown error");
+    eprintln!("An unknown error occured: {}", e);
+}
@@ -0,0 +1,458 @@
+use serde::Serialize;
+
+use crate::domain::export_pokemons::csv_field;
+
+#[derive(Clone, Copy)]
+pub enum Output {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Serialize)]
+pub struct Row {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct StatsRow {
+    pub number: u16,
+    pub name: String,
+    pub level: u8,
+    pub nature: String,
+    pub hp: u16,
+    pub attack: u16,
+    pub defense: u16,
+    pub special_attack: u16,
+    pub special_defense: u16,
+    pub speed: u16,
+}
+
+/// A move of a learnset, `-` standing for a missing value in tables.
+#[derive(Serialize)]
+pub struct MoveRow {
+    pub generation: u8,
+    pub method: String,
+    pub level: Option<u8>,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub move_type: String,
+    pub category: String,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+}
+
+/// The outcome of an import, the rejected lines being listed one per row.
+#[derive(Serialize)]
+pub struct ImportReport {
+    pub inserted: usize,
+    pub replaced: usize,
+    pub skipped: usize,
+    pub rejected: Vec<RejectedLine>,
+}
+
+#[derive(Serialize)]
+pub struct RejectedLine {
+    pub line: usize,
+    pub reason: String,
+}
+
+/// An API key, without its secret.
+#[derive(Serialize)]
+pub struct KeyRow {
+    pub name: String,
+    pub role: String,
+    pub prefix: String,
+    pub created_at: String,
+    pub revoked: bool,
+}
+
+/// A key just created, the only time its secret is shown.
+#[derive(Serialize)]
+pub struct NewKey {
+    #[serde(flatten)]
+    pub key: KeyRow,
+    pub secret: String,
+}
+
+/// A change of a Pokemon, with the revision and name the form was left with,
+/// or had before it was deleted.
+#[derive(Serialize)]
+pub struct HistoryRow {
+    pub id: u64,
+    pub timestamp: String,
+    pub action: String,
+    pub form: String,
+    pub revision: Option<u32>,
+    pub name: Option<String>,
+    pub actor: String,
+    pub request_id: Option<String>,
+}
+
+impl Output {
+    pub fn print_one(&self, row: Row) {
+        match self {
+            Output::Json => println!("{}", serde_json::to_string(&row).unwrap_or_default()),
+            _ => self.print_many(vec![row]),
+        }
+    }
+
+    pub fn print_many(&self, rows: Vec<Row>) {
+        match self {
+            Output::Table => print!("{}", table(&rows)),
+            Output::Json => println!("{}", serde_json::to_string(&rows).unwrap_or_default()),
+            Output::Csv => print!("{}", csv(&rows)),
+        }
+    }
+
+    pub fn print_stats(&self, row: StatsRow) {
+        let values = [
+            row.hp,
+            row.attack,
+            row.defense,
+            row.special_attack,
+            row.special_defense,
+            row.speed,
+        ]
+        .map(|value| value.to_string());
+
+        match self {
+            Output::Table => println!(
+                "{} #{} at level {} ({})\nHP {}  Atk {}  Def {}  SpA {}  SpD {}  Spe {}",
+                row.name,
+                row.number,
+                row.level,
+                row.nature,
+                values[0],
+                values[1],
+                values[2],
+                values[3],
+                values[4],
+                values[5],
+            ),
+            Output::Json => println!("{}", serde_json::to_string(&row).unwrap_or_default()),
+            Output::Csv => print!(
+                "number,name,level,nature,hp,attack,defense,special_attack,special_defense,speed\n{},{},{},{},{}\n",
+                row.number,
+                csv_field(&row.name),
+                row.level,
+                row.nature,
+                values.join(","),
+            ),
+        }
+    }
+
+    pub fn print_import(&self, report: ImportReport) {
+        match self {
+            Output::Table => {
+                println!(
+                    "{} inserted, {} replaced, {} skipped, {} rejected",
+                    report.inserted,
+                    report.replaced,
+                    report.skipped,
+                    report.rejected.len()
+                );
+                for rejected in &report.rejected {
+                    println!("  - line {}: {}", rejected.line, rejected.reason);
+                }
+            }
+            Output::Json => println!("{}", serde_json::to_string(&report).unwrap_or_default()),
+            Output::Csv => {
+                let mut out = String::from("line,reason\n");
+                for rejected in &report.rejected {
+                    out.push_str(&format!(
+                        "{},{}\n",
+                        rejected.line,
+                        csv_field(&rejected.reason)
+                    ));
+                }
+                print!("{}", out);
+            }
+        }
+    }
+
+    pub fn print_moves(&self, rows: Vec<MoveRow>) {
+        match self {
+            Output::Table => print!("{}", moves_table(&rows)),
+            Output::Json => println!("{}", serde_json::to_string(&rows).unwrap_or_default()),
+            Output::Csv => print!("{}", moves_csv(&rows)),
+        }
+    }
+
+    pub fn print_keys(&self, rows: Vec<KeyRow>) {
+        match self {
+            Output::Table => print!("{}", keys_table(&rows)),
+            Output::Json => println!("{}", serde_json::to_string(&rows).unwrap_or_default()),
+            Output::Csv => {
+                let mut out = String::from("name,role,prefix,created_at,revoked\n");
+                for row in &rows {
+                    out.push_str(&format!(
+                        "{},{},{},{},{}\n",
+                        csv_field(&row.name),
+                        row.role,
+                        row.prefix,
+                        row.created_at,
+                        row.revoked
+                    ));
+                }
+                print!("{}", out);
+            }
+        }
+    }
+
+    pub fn print_history(&self, rows: Vec<HistoryRow>) {
+        match self {
+            Output::Table => print!("{}", history_table(&rows)),
+            Output::Json => println!("{}", serde_json::to_string(&rows).unwrap_or_default()),
+            Output::Csv => print!("{}", history_csv(&rows)),
+        }
+    }
+
+    pub fn print_new_key(&self, new_key: NewKey) {
+        match self {
+            Output::Table => println!(
+                "The {} key '{}' has been created, keep its secret as it will not be shown again:\n{}",
+                new_key.key.role, new_key.key.name, new_key.secret
+            ),
+            Output::Json => println!("{}", serde_json::to_string(&new_key).unwrap_or_default()),
+            Output::Csv => print!(
+                "name,role,prefix,created_at,secret\n{},{},{},{},{}\n",
+                csv_field(&new_key.key.name),
+                new_key.key.role,
+                new_key.key.prefix,
+                new_key.key.created_at,
+                new_key.secret
+            ),
+        }
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>, missing: &str) -> String {
+    value.map_or_else(|| String::from(missing), |v| v.to_string())
+}
+
+fn moves_table(rows: &[MoveRow]) -> String {
+    let name_width = rows
+        .iter()
+        .map(|row| row.name.chars().count())
+        .chain(["MOVE".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut out = format!(
+        "{:<3}  {:<6}  {:<5}  {:<name_width$}  {:<8}  {:<8}  {:<5}  {:<3}  PP\n",
+        "GEN", "METHOD", "LEVEL", "MOVE", "TYPE", "CATEGORY", "POWER", "ACC"
+    );
+    for row in rows {
+        out.push_str(&format!(
+            "{:<3}  {:<6}  {:<5}  {:<name_width$}  {:<8}  {:<8}  {:<5}  {:<3}  {}\n",
+            row.generation,
+            row.method,
+            optional(row.level, "-"),
+            row.name,
+            row.move_type,
+            row.category,
+            optional(row.power, "-"),
+            optional(row.accuracy, "-"),
+            row.pp,
+        ));
+    }
+    out
+}
+
+fn moves_csv(rows: &[MoveRow]) -> String {
+    let mut out = String::from("generation,method,level,name,type,category,power,accuracy,pp\n");
+    for row in rows {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            row.generation,
+            row.method,
+            optional(row.level, ""),
+            csv_field(&row.name),
+            row.move_type,
+            row.category,
+            optional(row.power, ""),
+            optional(row.accuracy, ""),
+            row.pp,
+        ));
+    }
+    out
+}
+
+fn keys_table(rows: &[KeyRow]) -> String {
+    let name_width = rows
+        .iter()
+        .map(|row| row.name.chars().count())
+        .chain(["NAME".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut out = format!(
+        "{:<name_width$}  {:<6}  {:<8}  {:<20}  STATUS\n",
+        "NAME", "ROLE", "PREFIX", "CREATED"
+    );
+    for row in rows {
+        out.push_str(&format!(
+            "{:<name_width$}  {:<6}  {:<8}  {:<20}  {}\n",
+            row.name,
+            row.role,
+            row.prefix,
+            row.created_at,
+            if row.revoked { "revoked" } else { "active" },
+        ));
+    }
+    out
+}
+
+fn history_table(rows: &[HistoryRow]) -> String {
+    let form_width = rows
+        .iter()
+        .map(|row| row.form.chars().count())
+        .chain(["FORM".len()])
+        .max()
+        .unwrap_or_default();
+    let name_width = rows
+        .iter()
+        .map(|row| row.name.as_deref().unwrap_or("-").chars().count())
+        .chain(["NAME".len()])
+        .max()
+        .unwrap_or_default();
+    let actor_width = rows
+        .iter()
+        .map(|row| row.actor.chars().count())
+        .chain(["ACTOR".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut out = format!(
+        "{:<4}  {:<24}  {:<6}  {:<form_width$}  {:<8}  {:<name_width$}  {:<actor_width$}  REQUEST\n",
+        "ID", "TIMESTAMP", "ACTION", "FORM", "REVISION", "NAME", "ACTOR"
+    );
+    for row in rows {
+        out.push_str(&format!(
+            "{:<4}  {:<24}  {:<6}  {:<form_width$}  {:<8}  {:<name_width$}  {:<actor_width$}  {}\n",
+            row.id,
+            row.timestamp,
+            row.action,
+            row.form,
+            optional(row.revision, "-"),
+            row.name.as_deref().unwrap_or("-"),
+            row.actor,
+            row.request_id.as_deref().unwrap_or("-"),
+        ));
+    }
+    out
+}
+
+fn history_csv(rows: &[HistoryRow]) -> String {
+    let mut out = String::from("id,timestamp,action,form,revision,name,actor,request_id\n");
+    for row in rows {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            row.id,
+            row.timestamp,
+            row.action,
+            row.form,
+            optional(row.revision, ""),
+            csv_field(row.name.as_deref().unwrap_or_default()),
+            csv_field(&row.actor),
+            row.request_id.as_deref().unwrap_or_default(),
+        ));
+    }
+    out
+}
+
+fn table(rows: &[Row]) -> String {
+    let name_width = rows
+        .iter()
+        .map(|row| row.name.chars().count())
+        .chain(["NAME".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut out = format!("{:<6}  {:<name_width$}  TYPES\n", "NUMBER", "NAME");
+    for row in rows {
+        out.push_str(&format!(
+            "{:<6}  {:<name_width$}  {}\n",
+            row.number,
+            row.name,
+            row.types.join(", "),
+        ));
+    }
+    out
+}
+
+/// Types are joined with `;` so that a Pokemon stays on a single record.
+fn csv(rows: &[Row]) -> String {
+    let mut out = String::from("number,name,types\n");
+    for row in rows {
+        out.push_str(&format!(
+            "{},{},{}\n",
+            row.number,
+            csv_field(&row.name),
+            csv_field(&row.types.join(";")),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                number: 6,
+                name: String::from("Charizard"),
+                types: vec![String::from("Fire"), String::from("Flying")],
+            },
+            Row {
+                number: 83,
+                name: String::from("Farfetch'd, \"the duck\""),
+                types: vec![String::from("Normal"), String::from("Flying")],
+            },
+        ]
+    }
+
+    #[test]
+    fn it_should_render_a_csv_with_quoted_fields() {
+        assert_eq!(
+            csv(&rows()),
+            "number,name,types\n6,Charizard,Fire;Flying\n83,\"Farfetch'd, \"\"the duck\"\"\",Normal;Flying\n"
+        );
+    }
+
+    #[test]
+    fn it_should_leave_the_missing_move_values_empty_in_a_csv() {
+        let rows = vec![MoveRow {
+            generation: 8,
+            method: String::from("tm"),
+            level: None,
+            name: String::from("Thunder Wave"),
+            move_type: String::from("Electric"),
+            category: String::from("status"),
+            power: None,
+            accuracy: Some(90),
+            pp: 20,
+        }];
+
+        assert_eq!(
+            moves_csv(&rows),
+            "generation,method,level,name,type,category,power,accuracy,pp\n8,tm,,Thunder Wave,Electric,status,,90,20\n"
+        );
+    }
+
+    #[test]
+    fn it_should_align_the_table_columns() {
+        let out = table(&rows()[..1]);
+
+        assert_eq!(
+            out,
+            "NUMBER  NAME       TYPES\n6       Charizard  Fire, Flying\n"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::domain::entities::Actor;
+use crate::{domain::restore_pokemon, repositories::pokemon::Repository};
+
+use super::{print_error, print_field_errors, print_unknown_error, prompt_form, prompt_number};
+
+pub fn run(repo: Arc<dyn Repository>) {
+    let number = prompt_number();
+    let form = prompt_form();
+
+    match (number, form) {
+        (Ok(number), Ok(form)) => {
+            match restore_pokemon::execute(
+                repo,
+                restore_pokemon::Request { number, form },
+                &Actor::cli(),
+            ) {
+                Ok(_res) => println!("The Pokemon has been restored"),
+                Err(restore_pokemon::Error::BadRequest(errors)) => print_field_errors(&errors),
+                Err(restore_pokemon::Error::NotFound) => {
+                    print_error("The Pokemon is not in the trash")
+                }
+                Err(restore_pokemon::Error::Conflict) => {
+                    print_error("The default form is in the trash, restore it first")
+                }
+                Err(restore_pokemon::Error::Unknown(e)) => print_unknown_error(e),
+            }
+        }
+        _ => {
+            eprintln!("An error occured during the prompt");
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use dialoguer::{theme::ColorfulTheme, FuzzySelect, Input};
+
+use crate::{domain::search_pokemons, repositories::pokemon::Repository};
+
+use super::output::{Output, Row};
+use super::{print_field_errors, print_unknown_error};
+
+/// Searches the Pokemons by name, then lets the user narrow the matches down
+/// by typing and pick one.
+pub fn run(repo: Arc<dyn Repository>) {
+    let query = match Input::<String>::new()
+        .with_prompt("Pokemon name, or part of it")
+        .interact_text()
+    {
+        Ok(query) => query,
+        _ => {
+            eprintln!("An error occured during the prompt");
+            return;
+        }
+    };
+
+    let req = search_pokemons::Request {
+        query: query.clone(),
+        limit: search_pokemons::MAX_LIMIT,
+    };
+
+    let pokemons = match search_pokemons::execute(repo, req) {
+        Ok(pokemons) if pokemons.is_empty() => {
+            println!("No Pokemon matches '{}'", query);
+            return;
+        }
+        Ok(pokemons) => pokemons,
+        Err(search_pokemons::Error::BadRequest(errors)) => {
+            print_field_errors(&errors);
+            return;
+        }
+        Err(search_pokemons::Error::Unknown(e)) => {
+            print_unknown_error(e);
+            return;
+        }
+    };
+
+    let items = pokemons
+        .iter()
+        .map(|p| format!("#{} {} ({})", p.number, p.name, p.types.join(", ")))
+        .collect::<Vec<_>>();
+
+    match FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Pick a Pokemon")
+        .items(&items)
+        .default(0)
+        .interact()
+    {
+        Ok(index) => {
+            let p = &pokemons[index];
+            Output::Table.print_one(Row {
+                number: p.number,
+                name: p.name.clone(),
+                types: p.types.clone(),
+            })
+        }
+        _ => eprintln!("An error occured during the prompt"),
+    }
+}
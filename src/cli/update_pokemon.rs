@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::cli::output::{Output, Row};
+use crate::cli::{
+    print_error, print_field_errors, print_unknown_error, prompt_form, prompt_number,
+    prompt_optional_name, prompt_types,
+};
+use crate::domain::entities::Actor;
+use crate::domain::update_pokemon;
+use crate::repositories::pokemon::Repository;
+
+pub fn run(repo: Arc<dyn Repository>) {
+    let number = prompt_number();
+    let form = prompt_form();
+    let name = prompt_optional_name();
+    let types = prompt_types();
+
+    let req = match (number, form, name, types) {
+        (Ok(number), Ok(form), Ok(name), Ok(types)) => update_pokemon::Request {
+            number,
+            form,
+            name,
+            types: if types.is_empty() { None } else { Some(types) },
+            stats: None,
+            abilities: None,
+            revisions: None,
+        },
+        _ => {
+            eprintln!("An error occured during the prompt");
+            return;
+        }
+    };
+
+    match update_pokemon::execute(repo, req, &Actor::cli()) {
+        Ok(res) => Output::Table.print_one(Row {
+            number: res.number,
+            name: res.name,
+            types: res.types,
+        }),
+        Err(update_pokemon::Error::BadRequest(errors)) => print_field_errors(&errors),
+        Err(update_pokemon::Error::NotFound) => print_error("The Pokemon does not exist"),
+        Err(update_pokemon::Error::PreconditionFailed(_)) => {
+            print_error("The Pokemon has changed in the meantime")
+        }
+        Err(update_pokemon::Error::Unknown(e)) => print_unknown_error(e),
+    }
+}
@@ -0,0 +1,281 @@
+//! Runtime settings of the Pokedex.
+//!
+//! Every setting is resolved with the following precedence, from highest to
+//! lowest: command line flag, `POKEDEX_*` environment variable, TOML config
+//! file (`--config` / `POKEDEX_CONFIG`), built-in default.
+
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::logging::{self, LogFormat};
+
+pub const DEFAULT_HOST: &str = "localhost";
+pub const DEFAULT_PORT: u16 = 8000;
+
+/// The fully resolved settings.
+#[derive(Debug, PartialEq)]
+pub struct Settings {
+    pub host: String,
+    pub port: u16,
+    /// Size of the worker pool, `None` lets the server pick one from the CPU count.
+    pub threads: Option<usize>,
+    /// Path of the sqlite database, `None` selects the in-memory repository.
+    pub sqlite: Option<String>,
+    /// A level such as `debug`, or per-module directives such as `warn,pokedex::api=debug`.
+    pub log_level: String,
+    pub log_format: LogFormat,
+    /// Whether the changes require an API key. Keys are only stored in a
+    /// sqlite database, so it defaults to whether there is one.
+    pub auth: bool,
+}
+
+/// Settings given on the command line or through the environment.
+#[derive(Debug, Default)]
+pub struct Overrides {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub threads: Option<usize>,
+    pub sqlite: Option<String>,
+    pub log_level: Option<String>,
+    pub log_format: Option<String>,
+    pub auth: Option<bool>,
+}
+
+/// The content of a TOML config file, every key being optional:
+///
+/// ```toml
+/// [server]
+/// host = "0.0.0.0"
+/// port = 8080
+/// threads = 4
+/// auth = false
+///
+/// [repository]
+/// sqlite = "pokedex.db"
+///
+/// [log]
+/// level = "debug"
+/// format = "json"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileConfig {
+    #[serde(default)]
+    server: ServerConfig,
+    #[serde(default)]
+    repository: RepositoryConfig,
+    #[serde(default)]
+    log: LogConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ServerConfig {
+    host: Option<String>,
+    port: Option<u16>,
+    threads: Option<usize>,
+    auth: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RepositoryConfig {
+    sqlite: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LogConfig {
+    level: Option<String>,
+    format: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(String, std::io::Error),
+    Parse(String, toml::de::Error),
+    ZeroThreads,
+    UnknownLogFormat(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read(path, e) => write!(f, "cannot read {}: {}", path, e),
+            ConfigError::Parse(path, e) => write!(f, "invalid config file {}: {}", path, e),
+            ConfigError::ZeroThreads => write!(f, "the worker pool needs at least one thread"),
+            ConfigError::UnknownLogFormat(format) => {
+                write!(f, "unknown log format '{}', expected text or json", format)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl FileConfig {
+    pub fn load(path: &str) -> Result<Self, ConfigError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(path, &content),
+            Err(e) => Err(ConfigError::Read(String::from(path), e)),
+        }
+    }
+
+    fn parse(path: &str, content: &str) -> Result<Self, ConfigError> {
+        toml::from_str(content).map_err(|e| ConfigError::Parse(String::from(path), e))
+    }
+}
+
+impl Settings {
+    pub fn resolve(overrides: Overrides, file: FileConfig) -> Result<Self, ConfigError> {
+        let threads = overrides.threads.or(file.server.threads);
+        if threads == Some(0) {
+            return Err(ConfigError::ZeroThreads);
+        }
+
+        let log_format = match overrides.log_format.or(file.log.format) {
+            Some(format) => LogFormat::try_from(format.as_str())
+                .map_err(|_| ConfigError::UnknownLogFormat(format))?,
+            None => LogFormat::default(),
+        };
+
+        let sqlite = overrides.sqlite.or(file.repository.sqlite);
+        let auth = overrides
+            .auth
+            .or(file.server.auth)
+            .unwrap_or(sqlite.is_some());
+
+        Ok(Self {
+            host: overrides
+                .host
+                .or(file.server.host)
+                .unwrap_or_else(|| String::from(DEFAULT_HOST)),
+            port: overrides.port.or(file.server.port).unwrap_or(DEFAULT_PORT),
+            threads,
+            sqlite,
+            log_level: overrides
+                .log_level
+                .or(file.log.level)
+                .unwrap_or_else(|| String::from(logging::DEFAULT_LEVEL)),
+            log_format,
+            auth,
+        })
+    }
+
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_should_use_the_defaults_without_any_setting() {
+        let settings = Settings::resolve(Overrides::default(), FileConfig::default());
+
+        match settings {
+            Ok(settings) => {
+                assert_eq!(settings.address(), "localhost:8000");
+                assert_eq!(settings.threads, None);
+                assert_eq!(settings.sqlite, None);
+                assert_eq!(settings.log_level, "info");
+                assert_eq!(settings.log_format, LogFormat::Text);
+                assert!(!settings.auth);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_require_api_keys_by_default_with_a_sqlite_database() {
+        let overrides = Overrides {
+            sqlite: Some(String::from("pokedex.db")),
+            ..Overrides::default()
+        };
+
+        match Settings::resolve(overrides, FileConfig::default()) {
+            Ok(settings) => assert!(settings.auth),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_prefer_the_overrides_to_the_config_file() {
+        let file = FileConfig::parse(
+            "pokedex.toml",
+            r#"
+                [server]
+                host = "0.0.0.0"
+                port = 9000
+                threads = 4
+                auth = true
+
+                [repository]
+                sqlite = "file.db"
+
+                [log]
+                level = "debug"
+                format = "json"
+            "#,
+        );
+        let overrides = Overrides {
+            port: Some(8080),
+            sqlite: Some(String::from("flag.db")),
+            log_level: Some(String::from("warn")),
+            auth: Some(false),
+            ..Overrides::default()
+        };
+
+        match file.and_then(|file| Settings::resolve(overrides, file)) {
+            Ok(settings) => assert_eq!(
+                settings,
+                Settings {
+                    host: String::from("0.0.0.0"),
+                    port: 8080,
+                    threads: Some(4),
+                    sqlite: Some(String::from("flag.db")),
+                    log_level: String::from("warn"),
+                    log_format: LogFormat::Json,
+                    auth: false,
+                }
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_reject_an_unknown_key_in_the_config_file() {
+        let file = FileConfig::parse("pokedex.toml", "[server]\nhots = \"0.0.0.0\"\n");
+
+        assert!(matches!(file, Err(ConfigError::Parse(_, _))));
+    }
+
+    #[test]
+    fn it_should_reject_an_empty_worker_pool() {
+        let overrides = Overrides {
+            threads: Some(0),
+            ..Overrides::default()
+        };
+
+        assert!(matches!(
+            Settings::resolve(overrides, FileConfig::default()),
+            Err(ConfigError::ZeroThreads)
+        ));
+    }
+
+    #[test]
+    fn it_should_reject_an_unknown_log_format() {
+        let overrides = Overrides {
+            log_format: Some(String::from("xml")),
+            ..Overrides::default()
+        };
+
+        assert!(matches!(
+            Settings::resolve(overrides, FileConfig::default()),
+            Err(ConfigError::UnknownLogFormat(_))
+        ));
+    }
+}
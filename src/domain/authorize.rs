@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{ApiKey, Role};
+use crate::repositories::pokemon::{Repository, RetrieveError, Source};
+
+pub struct Request {
+    /// The secret presented by the client, if any.
+    pub secret: Option<String>,
+    /// The least role allowed to do the operation, `None` when it is public.
+    pub required: Option<Role>,
+}
+
+pub enum Error {
+    /// No usable key was presented. The reason is meant for the client.
+    Unauthenticated(&'static str),
+    Forbidden {
+        name: String,
+        role: Role,
+        required: Role,
+    },
+    Unknown(Source),
+}
+
+/// The key the request was authenticated with.
+pub struct Response {
+    pub name: String,
+}
+
+/// A public operation needs no key, but a key that is presented anyway must
+/// still be valid.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Option<Response>, Error> {
+    let secret = match (req.secret, req.required) {
+        (Some(secret), _) => secret,
+        (None, None) => return Ok(None),
+        (None, Some(_)) => return Err(Error::Unauthenticated("An API key is required")),
+    };
+
+    let key = match repo.fetch_key(ApiKey::hash(&secret)) {
+        Ok(key) if !key.revoked => key,
+        Ok(_) => return Err(Error::Unauthenticated("The API key is revoked")),
+        Err(RetrieveError::NotFound) => {
+            return Err(Error::Unauthenticated("The API key is not valid"))
+        }
+        Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+    };
+
+    match req.required {
+        Some(required) if key.role < required => Err(Error::Forbidden {
+            name: String::from(key.name),
+            role: key.role,
+            required,
+        }),
+        _ => Ok(Some(Response {
+            name: String::from(key.name),
+        })),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::KeyName;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        fn new(secret: Option<&str>, required: Option<Role>) -> Self {
+            Self {
+                secret: secret.map(String::from),
+                required,
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_let_a_public_operation_through_without_a_key() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(None, None)) {
+            Ok(None) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unauthenticated_error_when_the_key_is_missing_or_unknown() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        for req in [
+            Request::new(None, Some(Role::Editor)),
+            Request::new(Some("pdx_unknown"), None),
+        ] {
+            match execute(repo.clone(), req) {
+                Err(Error::Unauthenticated(_)) => {}
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unauthenticated_error_when_the_key_is_revoked() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert_key(ApiKey::editor());
+        let _ = repo.revoke_key(ApiKey::editor().name);
+
+        match execute(repo, Request::new(Some("pdx_editor"), Some(Role::Editor))) {
+            Err(Error::Unauthenticated("The API key is revoked")) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_forbidden_error_when_the_role_is_too_low() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert_key(ApiKey::editor());
+
+        match execute(repo, Request::new(Some("pdx_editor"), Some(Role::Admin))) {
+            Err(Error::Forbidden { role, required, .. }) => {
+                assert_eq!(role, Role::Editor);
+                assert_eq!(required, Role::Admin);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_let_a_higher_role_through() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let key = ApiKey::new(
+            KeyName::try_from(String::from("root")).unwrap(),
+            Role::Admin,
+            "pdx_admin",
+            String::from("2024-01-01T00:00:00Z"),
+        );
+        let _ = repo.insert_key(key);
+
+        match execute(repo, Request::new(Some("pdx_admin"), Some(Role::Editor))) {
+            Ok(Some(res)) => assert_eq!(res.name, "root"),
+            _ => unreachable!(),
+        }
+    }
+}
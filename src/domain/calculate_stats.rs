@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{
+    BaseStats, EffortValues, FieldError, FormSlug, IndividualValues, Level, Nature, PokemonNumber,
+    Stat, StatValues,
+};
+use crate::repositories::pokemon::{Repository, RetrieveError, Source};
+
+pub struct Request {
+    pub number: u16,
+    /// `None` uses the base stats of the default form.
+    pub form: Option<String>,
+    pub level: u8,
+    pub nature: String,
+    pub ivs: StatValues,
+    pub evs: StatValues,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    NoBaseStats,
+    Unknown(Source),
+}
+
+pub struct CalculateResponse {
+    pub number: u16,
+    pub name: String,
+    pub level: u8,
+    pub nature: String,
+    pub stats: StatValues,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<CalculateResponse, Error> {
+    let (number, form, level, nature, ivs, evs) = match (
+        PokemonNumber::try_from(req.number),
+        req.form.map(FormSlug::try_from).transpose(),
+        Level::try_from(req.level),
+        Nature::try_from(req.nature),
+        IndividualValues::try_from(req.ivs),
+        EffortValues::try_from(req.evs),
+    ) {
+        (Ok(number), Ok(form), Ok(level), Ok(nature), Ok(ivs), Ok(evs)) => {
+            (number, form, level, nature, ivs, evs)
+        }
+        (number, form, level, nature, ivs, evs) => {
+            return Err(Error::BadRequest(
+                [
+                    number.err().map(|e| FieldError::new("number", e)),
+                    form.err().map(|e| FieldError::new("form", e)),
+                    level.err().map(|e| FieldError::new("level", e)),
+                    nature.err().map(|e| FieldError::new("nature", e)),
+                    ivs.err().map(|e| FieldError::new("ivs", e)),
+                    evs.err().map(|e| FieldError::new("evs", e)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ))
+        }
+    };
+
+    match repo.fetch_one(number, form.unwrap_or_default()) {
+        Ok(pokemon) => match pokemon.stats {
+            Some(base) => Ok(CalculateResponse {
+                number: u16::from(pokemon.number),
+                name: String::from(pokemon.name),
+                level: u8::from(level),
+                nature: String::from(nature),
+                stats: calculate(&base, level, nature, &ivs, &evs),
+            }),
+            None => Err(Error::NoBaseStats),
+        },
+        Err(RetrieveError::NotFound) => Err(Error::NotFound),
+        Err(RetrieveError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+/// Computes the actual stats with the formulas used since generation III,
+/// rounding down after every step like the games do.
+pub fn calculate(
+    base: &BaseStats,
+    level: Level,
+    nature: Nature,
+    ivs: &IndividualValues,
+    evs: &EffortValues,
+) -> StatValues {
+    let level = u32::from(u8::from(level));
+
+    StatValues::from(Stat::ALL.map(|stat| {
+        let base_value = u32::from(base.get(stat));
+        let scaled = (2 * base_value + u32::from(ivs.get(stat)) + u32::from(evs.get(stat)) / 4)
+            * level
+            / 100;
+
+        let value = match stat {
+            // Shedinja always has a single hit point.
+            Stat::Hp if base_value == 1 => 1,
+            Stat::Hp => scaled + level + 10,
+            _ => (scaled + 5) * u32::from(nature.percentage(stat)) / 100,
+        };
+
+        value as u16
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{Actor, Pokemon, ValidationError};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        fn new(number: PokemonNumber) -> Self {
+            Self {
+                number: u16::from(number),
+                form: None,
+                level: 50,
+                nature: String::from("Hardy"),
+                ivs: StatValues::uniform(31),
+                evs: StatValues::default(),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_apply_the_official_formulas() {
+        // Garchomp, level 100, 31 IVs, Adamant, 252 Atk / 4 SpD / 252 Spe.
+        let base = BaseStats::try_from(StatValues::from([108, 130, 95, 80, 85, 102])).unwrap();
+        let ivs = IndividualValues::try_from(StatValues::uniform(31)).unwrap();
+        let evs = EffortValues::try_from(StatValues::from([0, 252, 0, 0, 4, 252])).unwrap();
+
+        let stats = calculate(
+            &base,
+            Level::try_from(100).unwrap(),
+            Nature::Adamant,
+            &ivs,
+            &evs,
+        );
+
+        assert_eq!(stats, StatValues::from([357, 394, 226, 176, 207, 303]));
+    }
+
+    #[test]
+    fn it_should_give_a_single_hit_point_to_shedinja() {
+        let base = BaseStats::try_from(StatValues::from([1, 90, 45, 30, 30, 40])).unwrap();
+        let ivs = IndividualValues::try_from(StatValues::uniform(31)).unwrap();
+        let evs = EffortValues::try_from(StatValues::from([252, 0, 0, 0, 0, 0])).unwrap();
+
+        let stats = calculate(
+            &base,
+            Level::try_from(50).unwrap(),
+            Nature::Hardy,
+            &ivs,
+            &evs,
+        );
+
+        assert_eq!(stats.hp, 1);
+    }
+
+    #[test]
+    fn it_should_return_the_stats_of_a_stored_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(
+            Pokemon::pikachu().with_stats(Some(BaseStats::pikachu())),
+            &Actor::ci(),
+        );
+
+        match execute(repo, Request::new(PokemonNumber::pikachu())) {
+            Ok(res) => {
+                assert_eq!(res.level, 50);
+                assert_eq!(res.nature, "Hardy");
+                assert_eq!(res.stats, StatValues::from([110, 75, 60, 70, 70, 110]));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_for_every_invalid_field() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            level: 0,
+            nature: String::from("Grumpy"),
+            ..Request::new(PokemonNumber::pikachu())
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![
+                    FieldError::new(
+                        "level",
+                        ValidationError::LevelOutOfRange {
+                            min: 1,
+                            max: 100,
+                            got: 0
+                        }
+                    ),
+                    FieldError::new(
+                        "nature",
+                        ValidationError::UnknownNature(String::from("Grumpy"))
+                    ),
+                ]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_no_base_stats_error_when_they_are_unknown() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+
+        match execute(repo, Request::new(PokemonNumber::pikachu())) {
+            Err(Error::NoBaseStats) => {}
+            _ => unreachable!(),
+        }
+    }
+}
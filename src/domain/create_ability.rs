@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{Ability, AbilityName, FieldError};
+use crate::repositories::pokemon::{InsertError, Repository, Source};
+
+pub struct Request {
+    pub name: String,
+    pub description: String,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    Conflict,
+    Unknown(Source),
+}
+
+pub struct AbilityResponse {
+    pub name: String,
+    pub description: String,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<AbilityResponse, Error> {
+    let name = match AbilityName::try_from(req.name) {
+        Ok(name) => name,
+        Err(e) => return Err(Error::BadRequest(vec![FieldError::new("name", e)])),
+    };
+
+    match repo.insert_ability(Ability::new(name, req.description)) {
+        Ok(ability) => Ok(AbilityResponse {
+            name: String::from(ability.name),
+            description: ability.description,
+        }),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown(e)) => Err(Error::Unknown(e)),
+        Err(
+            e @ (InsertError::UnknownAbility(_) | InsertError::Deleted | InsertError::Invalid(_)),
+        ) => Err(Error::Unknown(e.into())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::ValidationError;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        fn new(name: AbilityName) -> Self {
+            Self {
+                name: String::from(name),
+                description: String::from("May paralyze on contact."),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_ability_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(AbilityName::static_())) {
+            Ok(res) => {
+                assert_eq!(res.name, "Static");
+                assert_eq!(res.description, "May paralyze on contact.");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_name_is_empty() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            name: String::from(" "),
+            ..Request::new(AbilityName::static_())
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![FieldError::new("name", ValidationError::EmptyAbilityName)]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_ability_already_exists() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = execute(repo.clone(), Request::new(AbilityName::static_()));
+
+        match execute(repo, Request::new(AbilityName::static_())) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use uuid::Uuid;
+
+use crate::domain::entities::{ApiKey, FieldError, KeyName, Role};
+use crate::repositories::pokemon::{InsertError, Repository, Source};
+
+pub struct Request {
+    pub name: String,
+    pub role: String,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    Conflict,
+    Unknown(Source),
+}
+
+pub struct ApiKeyResponse {
+    pub name: String,
+    pub role: String,
+    pub prefix: String,
+    pub created_at: String,
+    pub revoked: bool,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            name: String::from(key.name),
+            role: String::from(key.role),
+            prefix: key.prefix,
+            created_at: key.created_at,
+            revoked: key.revoked,
+        }
+    }
+}
+
+pub struct Response {
+    pub key: ApiKeyResponse,
+    /// Only its hash is stored, so it cannot be shown again.
+    pub secret: String,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let (name, role) = match (KeyName::try_from(req.name), Role::try_from(req.role)) {
+        (Ok(name), Ok(role)) => (name, role),
+        (name, role) => {
+            return Err(Error::BadRequest(
+                [
+                    name.err().map(|e| FieldError::new("name", e)),
+                    role.err().map(|e| FieldError::new("role", e)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ))
+        }
+    };
+
+    let secret = format!("pdx_{}", Uuid::new_v4().simple());
+    let created_at = humantime::format_rfc3339_seconds(SystemTime::now()).to_string();
+
+    match repo.insert_key(ApiKey::new(name, role, &secret, created_at)) {
+        Ok(key) => Ok(Response {
+            key: ApiKeyResponse::from(key),
+            secret,
+        }),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown(e)) => Err(Error::Unknown(e)),
+        Err(
+            e @ (InsertError::UnknownAbility(_) | InsertError::Deleted | InsertError::Invalid(_)),
+        ) => Err(Error::Unknown(e.into())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        fn new(name: &str, role: &str) -> Self {
+            Self {
+                name: String::from(name),
+                role: String::from(role),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_only_store_the_hash_of_the_secret() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match (
+            execute(repo.clone(), Request::new("ci", "editor")),
+            repo.fetch_keys(),
+        ) {
+            (Ok(res), Ok(keys)) => {
+                assert!(res.secret.starts_with(&res.key.prefix));
+                assert_eq!(res.key.role, "editor");
+                assert_eq!(keys[0].hash, ApiKey::hash(&res.secret));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_name_and_role_are_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(" ", "owner")) {
+            Err(Error::BadRequest(errors)) => assert_eq!(errors.len(), 2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_name_is_taken() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert_key(ApiKey::editor());
+
+        match execute(repo, Request::new("ci", "reader")) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{
+    Evolution, EvolutionTrigger, FieldError, FormSlug, PokemonNumber, TriggerValue, ValidationError,
+};
+use crate::repositories::pokemon::{InsertError, Repository, RetrieveError, Source};
+
+pub struct Request {
+    pub from: u16,
+    pub to: u16,
+    pub trigger: TriggerValue,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Conflict,
+    Unknown(Source),
+}
+
+pub struct EvolutionResponse {
+    pub from: u16,
+    pub to: u16,
+    pub trigger: TriggerValue,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<EvolutionResponse, Error> {
+    let evolution = match (
+        PokemonNumber::try_from(req.from),
+        PokemonNumber::try_from(req.to),
+        EvolutionTrigger::try_from(req.trigger),
+    ) {
+        (Ok(from), Ok(to), Ok(trigger)) => Evolution::new(from, to, trigger),
+        (from, to, trigger) => {
+            return Err(Error::BadRequest(
+                [
+                    from.err().map(|e| FieldError::new("from", e)),
+                    to.err().map(|e| FieldError::new("to", e)),
+                    trigger.err().map(|e| FieldError::new("trigger", e)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ))
+        }
+    };
+
+    match repo.fetch_one(evolution.from.clone(), FormSlug::default()) {
+        Ok(_) => {}
+        Err(RetrieveError::NotFound) => return Err(Error::NotFound),
+        Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+
+    match repo.fetch_one(evolution.to.clone(), FormSlug::default()) {
+        Ok(_) => {}
+        Err(RetrieveError::NotFound) => {
+            return Err(Error::BadRequest(vec![FieldError::new(
+                "to",
+                ValidationError::UnknownPokemon(req.to),
+            )]))
+        }
+        Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+
+    match repo.insert_evolution(evolution) {
+        Ok(evolution) => Ok(EvolutionResponse {
+            from: u16::from(evolution.from),
+            to: u16::from(evolution.to),
+            trigger: TriggerValue::from(evolution.trigger),
+        }),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Invalid(e)) => Err(Error::BadRequest(vec![FieldError::new("to", e)])),
+        Err(InsertError::Unknown(e)) => Err(Error::Unknown(e)),
+        Err(e @ (InsertError::UnknownAbility(_) | InsertError::Deleted)) => {
+            Err(Error::Unknown(e.into()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{Actor, Pokemon, PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn seed(numbers: &[u16]) -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        for number in numbers {
+            let _ = repo.insert(
+                Pokemon::new(
+                    PokemonNumber::try_from(*number).unwrap(),
+                    PokemonName::try_from(format!("Pokemon #{}", number)).unwrap(),
+                    PokemonTypes::pikachu(),
+                ),
+                &Actor::ci(),
+            );
+        }
+        repo
+    }
+
+    fn level_up(from: u16, to: u16, level: u8) -> Request {
+        Request {
+            from,
+            to,
+            trigger: TriggerValue {
+                kind: String::from("level_up"),
+                level: Some(level),
+                item: None,
+            },
+        }
+    }
+
+    #[test]
+    fn it_should_accept_a_branching_chain() {
+        let repo = seed(&[133, 134, 135]);
+
+        assert!(execute(repo.clone(), level_up(133, 134, 20)).is_ok());
+        assert!(execute(repo.clone(), level_up(133, 135, 20)).is_ok());
+        assert!(matches!(repo.fetch_evolutions(), Ok(e) if e.len() == 2));
+    }
+
+    #[test]
+    fn it_should_reject_a_cycle() {
+        let repo = seed(&[1, 2, 3]);
+        let _ = execute(repo.clone(), level_up(1, 2, 16));
+        let _ = execute(repo.clone(), level_up(2, 3, 32));
+
+        match execute(repo.clone(), level_up(3, 1, 50)) {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![FieldError::new(
+                    "to",
+                    ValidationError::EvolutionCycle { from: 3, to: 1 }
+                )]
+            ),
+            _ => unreachable!(),
+        }
+
+        match execute(repo, level_up(2, 2, 50)) {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(errors[0].error.code(), "has_pre_evolution")
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_reject_a_second_pre_evolution() {
+        let repo = seed(&[1, 2, 3]);
+        let _ = execute(repo.clone(), level_up(1, 3, 16));
+
+        match execute(repo.clone(), level_up(2, 3, 16)) {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![FieldError::new(
+                    "to",
+                    ValidationError::HasPreEvolution { number: 3, from: 1 }
+                )]
+            ),
+            _ => unreachable!(),
+        }
+
+        match execute(repo, level_up(1, 3, 16)) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_reject_an_unknown_pokemon() {
+        let repo = seed(&[1]);
+
+        match execute(repo.clone(), level_up(1, 2, 16)) {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![FieldError::new("to", ValidationError::UnknownPokemon(2))]
+            ),
+            _ => unreachable!(),
+        }
+
+        match execute(repo, level_up(4, 1, 16)) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use crate::domain::create_move::MoveResponse;
+use crate::domain::entities::{
+    FieldError, FormSlug, Generation, LearnMethod, LearnsetEntry, Level, MoveName, PokemonNumber,
+    ValidationError,
+};
+use crate::repositories::pokemon::{InsertError, Repository, RetrieveError, Source};
+
+pub struct Request {
+    pub number: u16,
+    pub move_name: String,
+    pub method: String,
+    pub level: Option<u8>,
+    pub generation: u8,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Conflict,
+    Unknown(Source),
+}
+
+/// A move of a learnset along with its details from the catalogue.
+pub struct LearnsetResponse {
+    pub method: String,
+    pub level: Option<u8>,
+    pub generation: u8,
+    pub details: MoveResponse,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<LearnsetResponse, Error> {
+    let (number, entry) = match (
+        PokemonNumber::try_from(req.number),
+        MoveName::try_from(req.move_name),
+        LearnMethod::try_from(req.method),
+        req.level.map(Level::try_from).transpose(),
+        Generation::try_from(req.generation),
+    ) {
+        (Ok(number), Ok(move_name), Ok(method), Ok(level), Ok(generation)) => {
+            match LearnsetEntry::new(move_name, method, level, generation) {
+                Ok(entry) => (number, entry),
+                Err(e) => return Err(Error::BadRequest(vec![FieldError::new("level", e)])),
+            }
+        }
+        (number, move_name, method, level, generation) => {
+            return Err(Error::BadRequest(
+                [
+                    number.err().map(|e| FieldError::new("number", e)),
+                    move_name.err().map(|e| FieldError::new("move", e)),
+                    method.err().map(|e| FieldError::new("method", e)),
+                    level.err().map(|e| FieldError::new("level", e)),
+                    generation.err().map(|e| FieldError::new("generation", e)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ))
+        }
+    };
+
+    match repo.fetch_one(number.clone(), FormSlug::default()) {
+        Ok(_) => {}
+        Err(RetrieveError::NotFound) => return Err(Error::NotFound),
+        Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+
+    let details = match repo.fetch_move(entry.move_name.clone()) {
+        Ok(pokemon_move) => MoveResponse::from(pokemon_move),
+        Err(RetrieveError::NotFound) => {
+            return Err(Error::BadRequest(vec![FieldError::new(
+                "move",
+                ValidationError::UnknownMove(String::from(entry.move_name)),
+            )]))
+        }
+        Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+    };
+
+    match repo.insert_learnset_entry(number, entry) {
+        Ok(entry) => Ok(LearnsetResponse {
+            method: String::from(entry.method),
+            level: entry.level.map(u8::from),
+            generation: u8::from(entry.generation),
+            details,
+        }),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown(e)) => Err(Error::Unknown(e)),
+        Err(
+            e @ (InsertError::UnknownAbility(_) | InsertError::Deleted | InsertError::Invalid(_)),
+        ) => Err(Error::Unknown(e.into())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{Actor, Move, Pokemon};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        fn new(number: PokemonNumber, move_name: MoveName) -> Self {
+            Self {
+                number: u16::from(number),
+                move_name: String::from(move_name),
+                method: String::from("level"),
+                level: Some(26),
+                generation: 8,
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_entry_with_the_move_details_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert_move(Move::thunderbolt());
+
+        match execute(
+            repo,
+            Request::new(PokemonNumber::pikachu(), MoveName::thunderbolt()),
+        ) {
+            Ok(res) => {
+                assert_eq!(res.method, "level");
+                assert_eq!(res.level, Some(26));
+                assert_eq!(res.details.name, "Thunderbolt");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_level_is_missing() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            level: None,
+            ..Request::new(PokemonNumber::pikachu(), MoveName::thunderbolt())
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![FieldError::new("level", ValidationError::MissingLevel)]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_move_is_unknown() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+
+        match execute(
+            repo,
+            Request::new(PokemonNumber::pikachu(), MoveName::thunderbolt()),
+        ) {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![FieldError::new(
+                    "move",
+                    ValidationError::UnknownMove(String::from("Thunderbolt"))
+                )]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_entry_already_exists() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert_move(Move::thunderbolt());
+        let req = || Request::new(PokemonNumber::pikachu(), MoveName::thunderbolt());
+        let _ = execute(repo.clone(), req());
+
+        match execute(repo, req()) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{
+    FieldError, Move, MoveAccuracy, MoveCategory, MoveName, MovePower, PokemonType, PowerPoints,
+};
+use crate::repositories::pokemon::{InsertError, Repository, Source};
+
+pub struct Request {
+    pub name: String,
+    pub move_type: String,
+    pub category: String,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    Conflict,
+    Unknown(Source),
+}
+
+pub struct MoveResponse {
+    pub name: String,
+    pub move_type: String,
+    pub category: String,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+}
+
+impl From<Move> for MoveResponse {
+    fn from(val: Move) -> Self {
+        Self {
+            name: String::from(val.name),
+            move_type: String::from(val.move_type),
+            category: String::from(val.category),
+            power: val.power.map(u8::from),
+            accuracy: val.accuracy.map(u8::from),
+            pp: u8::from(val.pp),
+        }
+    }
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<MoveResponse, Error> {
+    let pokemon_move = match (
+        MoveName::try_from(req.name),
+        PokemonType::try_from(req.move_type),
+        MoveCategory::try_from(req.category),
+        req.power.map(MovePower::try_from).transpose(),
+        req.accuracy.map(MoveAccuracy::try_from).transpose(),
+        PowerPoints::try_from(req.pp),
+    ) {
+        (Ok(name), Ok(move_type), Ok(category), Ok(power), Ok(accuracy), Ok(pp)) => Move {
+            name,
+            move_type,
+            category,
+            power,
+            accuracy,
+            pp,
+        },
+        (name, move_type, category, power, accuracy, pp) => {
+            return Err(Error::BadRequest(
+                [
+                    name.err().map(|e| FieldError::new("name", e)),
+                    move_type.err().map(|e| FieldError::new("type", e)),
+                    category.err().map(|e| FieldError::new("category", e)),
+                    power.err().map(|e| FieldError::new("power", e)),
+                    accuracy.err().map(|e| FieldError::new("accuracy", e)),
+                    pp.err().map(|e| FieldError::new("pp", e)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ))
+        }
+    };
+
+    match repo.insert_move(pokemon_move) {
+        Ok(pokemon_move) => Ok(MoveResponse::from(pokemon_move)),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown(e)) => Err(Error::Unknown(e)),
+        Err(
+            e @ (InsertError::UnknownAbility(_) | InsertError::Deleted | InsertError::Invalid(_)),
+        ) => Err(Error::Unknown(e.into())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::ValidationError;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        fn new(pokemon_move: Move) -> Self {
+            let res = MoveResponse::from(pokemon_move);
+            Self {
+                name: res.name,
+                move_type: res.move_type,
+                category: res.category,
+                power: res.power,
+                accuracy: res.accuracy,
+                pp: res.pp,
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_move_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(Move::thunderbolt())) {
+            Ok(res) => {
+                assert_eq!(res.name, "Thunderbolt");
+                assert_eq!(res.move_type, "Electric");
+                assert_eq!(res.category, "special");
+                assert_eq!(res.power, Some(90));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_for_every_invalid_field() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            category: String::from("magic"),
+            accuracy: Some(101),
+            pp: 0,
+            ..Request::new(Move::thunderbolt())
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![
+                    FieldError::new(
+                        "category",
+                        ValidationError::UnknownMoveCategory(String::from("magic"))
+                    ),
+                    FieldError::new(
+                        "accuracy",
+                        ValidationError::AccuracyOutOfRange {
+                            min: 1,
+                            max: 100,
+                            got: 101
+                        }
+                    ),
+                    FieldError::new(
+                        "pp",
+                        ValidationError::PowerPointsOutOfRange {
+                            min: 1,
+                            max: 40,
+                            got: 0
+                        }
+                    ),
+                ]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_move_already_exists() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = execute(repo.clone(), Request::new(Move::thunderbolt()));
+
+        match execute(repo, Request::new(Move::thunderbolt())) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+}
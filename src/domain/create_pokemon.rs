@@ -1,43 +1,111 @@
 use std::sync::Arc;
 
-use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
-use crate::repositories::pokemon::{InsertError, Repository};
+use crate::domain::entities::{
+    Actor, BaseStats, FieldError, FormSlug, Pokemon, PokemonAbilities, PokemonName, PokemonNumber,
+    PokemonTypes, SlotValue, StatValues, ValidationError,
+};
+use crate::repositories::pokemon::{InsertError, Repository, RetrieveError, Source};
 
 pub struct Request {
     pub number: u16,
+    /// `None` creates the default form, any other form requires it to exist.
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<StatValues>,
+    pub abilities: Vec<SlotValue>,
 }
 
 pub enum Error {
-    BadRequest,
+    BadRequest(Vec<FieldError>),
+    NotFound,
     Conflict,
-    Unknown,
+    /// The form is in the trash, and must be restored or purged first.
+    Deleted,
+    Unknown(Source),
 }
 
 #[derive(Debug)]
 pub struct InsertResponse {
     pub number: u16,
+    pub form: String,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<StatValues>,
+    pub abilities: Vec<SlotValue>,
+    pub revision: u32,
 }
 
-pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<InsertResponse, Error> {
-    match (
-        PokemonNumber::try_from(req.number),
-        PokemonName::try_from(req.name),
-        PokemonTypes::try_from(req.types),
-    ) {
-        (Ok(id), Ok(name), Ok(types)) => match repo.insert(id, name, types) {
-            Ok(pokemon) => Ok(InsertResponse {
-                number: u16::from(pokemon.number),
-                name: String::from(pokemon.name),
-                types: Vec::<String>::from(pokemon.types),
-            }),
-            Err(InsertError::Conflict) => Err(Error::Conflict),
-            Err(InsertError::Unknown) => Err(Error::Unknown),
-        },
-        _ => Err(Error::BadRequest),
+/// Creates the form on behalf of `actor`, who is recorded in the audit log.
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+    actor: &Actor,
+) -> Result<InsertResponse, Error> {
+    let pokemon = match Pokemon::try_from(req) {
+        Ok(pokemon) => pokemon,
+        Err(errors) => return Err(Error::BadRequest(errors)),
+    };
+
+    if !pokemon.form.is_default() {
+        match repo.fetch_one(pokemon.number.clone(), FormSlug::default()) {
+            Ok(_) => {}
+            Err(RetrieveError::NotFound) => return Err(Error::NotFound),
+            Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+        }
+    }
+
+    match repo.insert(pokemon, actor) {
+        Ok(pokemon) => Ok(InsertResponse {
+            number: u16::from(pokemon.number),
+            form: String::from(pokemon.form),
+            name: String::from(pokemon.name),
+            types: Vec::<String>::from(pokemon.types),
+            stats: pokemon.stats.map(StatValues::from),
+            abilities: Vec::<SlotValue>::from(pokemon.abilities),
+            revision: pokemon.revision,
+        }),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Deleted) => Err(Error::Deleted),
+        Err(InsertError::UnknownAbility(name)) => Err(Error::BadRequest(vec![FieldError::new(
+            "abilities",
+            ValidationError::UnknownAbility(String::from(name)),
+        )])),
+        Err(InsertError::Unknown(e)) => Err(Error::Unknown(e)),
+        Err(e @ InsertError::Invalid(_)) => Err(Error::Unknown(e.into())),
+    }
+}
+
+impl TryFrom<Request> for Pokemon {
+    type Error = Vec<FieldError>;
+
+    fn try_from(req: Request) -> Result<Self, Self::Error> {
+        match (
+            PokemonNumber::try_from(req.number),
+            req.form.map(FormSlug::try_from).transpose(),
+            PokemonName::try_from(req.name),
+            PokemonTypes::try_from(req.types),
+            req.stats.map(BaseStats::try_from).transpose(),
+            PokemonAbilities::try_from(req.abilities),
+        ) {
+            (Ok(id), Ok(form), Ok(name), Ok(types), Ok(stats), Ok(abilities)) => {
+                Ok(Pokemon::new(id, name, types)
+                    .with_form(form.unwrap_or_default())
+                    .with_stats(stats)
+                    .with_abilities(abilities))
+            }
+            (number, form, name, types, stats, abilities) => Err([
+                number.err().map(|e| FieldError::new("number", e)),
+                form.err().map(|e| FieldError::new("form", e)),
+                name.err().map(|e| FieldError::new("name", e)),
+                types.err().map(|e| FieldError::new("types", e)),
+                stats.err().map(|e| FieldError::new("stats", e)),
+                abilities.err().map(|e| FieldError::new("abilities", e)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect()),
+        }
     }
 }
 
@@ -50,8 +118,11 @@ mod test {
         fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
             Self {
                 number: u16::from(number),
+                form: None,
                 name: String::from(name),
                 types: Vec::<String>::from(types),
+                stats: None,
+                abilities: vec![],
             }
         }
     }
@@ -64,13 +135,14 @@ mod test {
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
         );
-        let res = execute(repo, req);
+        let res = execute(repo, req, &Actor::ci());
 
         match res {
             Ok(InsertResponse {
                 number,
                 name,
                 types,
+                ..
             }) => {
                 assert_eq!(number, 25);
                 assert_eq!(name, String::from("Pikachu"));
@@ -89,10 +161,79 @@ mod test {
             PokemonTypes::pikachu(),
         );
 
-        let res = execute(repo, req);
+        let res = execute(repo, req, &Actor::ci());
 
         match res {
-            Err(Error::BadRequest) => {}
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec![FieldError::new("name", ValidationError::EmptyName)]
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_report_every_invalid_field() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 0,
+            form: None,
+            name: String::from("Pikachu"),
+            types: vec![String::from("Shadow")],
+            stats: None,
+            abilities: vec![],
+        };
+
+        let res = execute(repo, req, &Actor::ci());
+
+        match res {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec![
+                        FieldError::new(
+                            "number",
+                            ValidationError::NumberOutOfRange {
+                                min: 1,
+                                max: 898,
+                                got: 0
+                            }
+                        ),
+                        FieldError::new(
+                            "types",
+                            ValidationError::UnknownType(String::from("Shadow"))
+                        ),
+                    ]
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_store_the_base_stats() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            stats: Some(StatValues::from(BaseStats::pikachu())),
+            ..Request::new(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            )
+        };
+
+        let res = execute(repo.clone(), req, &Actor::ci());
+
+        match (
+            res,
+            repo.fetch_one(PokemonNumber::pikachu(), FormSlug::default()),
+        ) {
+            (Ok(InsertResponse { stats, .. }), Ok(pokemon)) => {
+                assert_eq!(stats, Some(StatValues::from([35, 55, 40, 50, 50, 90])));
+                assert_eq!(pokemon.stats, Some(BaseStats::pikachu()));
+            }
             _ => unreachable!(),
         }
     }
@@ -105,7 +246,7 @@ mod test {
         let name = PokemonName::pikachu();
         let types = PokemonTypes::pikachu();
         let repo = Arc::new(InMemoryRepository::new());
-        let _ = repo.insert(number, name, types);
+        let _ = repo.insert(Pokemon::new(number, name, types), &Actor::ci());
 
         // Act
         // create a Pokemon with the same number.
@@ -114,7 +255,7 @@ mod test {
             PokemonName::charmader(),
             PokemonTypes::charmander(),
         );
-        let res = execute(repo, req);
+        let res = execute(repo, req, &Actor::ci());
 
         // Assert
         match res {
@@ -123,6 +264,101 @@ mod test {
         }
     }
 
+    #[test]
+    fn it_should_return_a_deleted_error_when_the_pokemon_is_in_the_trash() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+
+        match execute(repo.clone(), req, &Actor::ci()) {
+            Err(Error::Deleted) => {}
+            _ => unreachable!(),
+        }
+        match repo.fetch_trash() {
+            Ok(trash) => assert_eq!(trash.len(), 1),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_continue_the_revisions_of_a_purged_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.update(Pokemon::pikachu(), None, &Actor::ci());
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+        let _ = repo.purge(String::from("9999-12-31T00:00:00.000Z"), &Actor::ci());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+
+        match execute(repo, req, &Actor::ci()) {
+            Ok(res) => assert_eq!(res.revision, 3),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_store_an_alternate_form_next_to_the_default_one() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::charmander(),
+            )
+        };
+
+        match execute(repo.clone(), req, &Actor::ci()) {
+            Ok(res) => assert_eq!(res.form, "alola"),
+            _ => unreachable!(),
+        }
+
+        match repo.fetch_forms(PokemonNumber::pikachu()) {
+            Ok(forms) => assert_eq!(
+                forms.into_iter().map(|p| p.form).collect::<Vec<_>>(),
+                vec![FormSlug::default(), FormSlug::alola()]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_for_a_form_without_its_default_one() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            )
+        };
+
+        match execute(repo, req, &Actor::ci()) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn it_should_return_an_error_when_an_unexpected_error_happen() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
@@ -132,9 +368,9 @@ mod test {
             PokemonTypes::pikachu(),
         );
 
-        let res = execute(repo, req);
+        let res = execute(repo, req, &Actor::ci());
         match res {
-            Err(Error::Unknown) => {}
+            Err(Error::Unknown(_)) => {}
             _ => unreachable!(),
         }
     }
@@ -1,42 +1,64 @@
 use std::sync::Arc;
 
-use crate::repositories::pokemon::{DeleteError, Repository};
+use crate::repositories::pokemon::{DeleteError, Repository, Source};
 
-use super::entities::PokemonNumber;
+use super::entities::{Actor, FieldError, FormSlug, PokemonNumber};
 
 pub struct Request {
     pub number: u16,
+    /// `None` deletes the default form, and with it every other one.
+    pub form: Option<String>,
+    /// The revisions the client accepts, `None` deleting the form whatever
+    /// its revision.
+    pub revisions: Option<Vec<u32>>,
 }
 
 pub enum Error {
-    Unknown,
-    BadRequest,
+    Unknown(Source),
+    BadRequest(Vec<FieldError>),
     NotFound,
+    /// The form is at another revision, given here.
+    PreconditionFailed(u32),
 }
 
-pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
-    match PokemonNumber::try_from(req.number) {
-        Ok(number) => match repo.delete_pokemon(number) {
-            Ok(_) => Ok(()),
-            Err(DeleteError::NotFound) => Err(Error::NotFound),
-            Err(DeleteError::Unknown) => Err(Error::Unknown),
-        },
-        _ => Err(Error::BadRequest),
+/// Moves the form to the trash on behalf of `actor`, who is recorded in the
+/// audit log. It stays there until it is restored or purged.
+pub fn execute(repo: Arc<dyn Repository>, req: Request, actor: &Actor) -> Result<(), Error> {
+    match (
+        PokemonNumber::try_from(req.number),
+        req.form.map(FormSlug::try_from).transpose(),
+    ) {
+        (Ok(number), Ok(form)) => {
+            match repo.delete_pokemon(number, form.unwrap_or_default(), req.revisions, actor) {
+                Ok(_) => Ok(()),
+                Err(DeleteError::NotFound) => Err(Error::NotFound),
+                Err(DeleteError::Stale(revision)) => Err(Error::PreconditionFailed(revision)),
+                Err(DeleteError::Unknown(e)) => Err(Error::Unknown(e)),
+            }
+        }
+        (number, form) => Err(Error::BadRequest(
+            [
+                number.err().map(|e| FieldError::new("number", e)),
+                form.err().map(|e| FieldError::new("form", e)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        )),
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{
-        domain::entities::{PokemonName, PokemonTypes},
-        repositories::pokemon::InMemoryRepository,
-    };
+    use crate::{domain::entities::Pokemon, repositories::pokemon::InMemoryRepository};
 
     impl Request {
         pub fn new(number: PokemonNumber) -> Self {
             Self {
                 number: u16::from(number),
+                form: None,
+                revisions: None,
             }
         }
     }
@@ -45,10 +67,10 @@ mod test {
     fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
         let req = Request::new(PokemonNumber::pikachu());
-        let res = execute(repo, req);
+        let res = execute(repo, req, &Actor::ci());
 
         match res {
-            Err(Error::Unknown) => {}
+            Err(Error::Unknown(_)) => {}
             _ => unreachable!(),
         }
     }
@@ -58,10 +80,10 @@ mod test {
         let repo = Arc::new(InMemoryRepository::new());
         let req = Request::new(PokemonNumber::bad());
 
-        let res = execute(repo, req);
+        let res = execute(repo, req, &Actor::ci());
 
         match res {
-            Err(Error::BadRequest) => {}
+            Err(Error::BadRequest(_)) => {}
             _ => unreachable!(),
         }
     }
@@ -71,7 +93,7 @@ mod test {
         let repo = Arc::new(InMemoryRepository::new());
         let req = Request::new(PokemonNumber::pikachu());
 
-        let res = execute(repo, req);
+        let res = execute(repo, req, &Actor::ci());
 
         match res {
             Err(Error::NotFound) => {}
@@ -82,18 +104,73 @@ mod test {
     #[test]
     fn it_should_return_ok_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
-        let _ = repo.insert(
-            PokemonNumber::pikachu(),
-            PokemonName::pikachu(),
-            PokemonTypes::pikachu(),
-        );
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
         let req = Request::new(PokemonNumber::pikachu());
 
-        let res = execute(repo, req);
+        let res = execute(repo, req, &Actor::ci());
 
         match res {
             Ok(()) => {}
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn it_should_only_delete_the_given_alternate_form() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(
+            Pokemon::pikachu().with_form(FormSlug::alola()),
+            &Actor::ci(),
+        );
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(PokemonNumber::pikachu())
+        };
+
+        match (
+            execute(repo.clone(), req, &Actor::ci()),
+            repo.fetch_forms(PokemonNumber::pikachu()),
+        ) {
+            (Ok(()), Ok(forms)) => assert_eq!(forms.len(), 1),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_precondition_failed_error_when_the_revision_is_stale() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let req = Request {
+            revisions: Some(vec![2]),
+            ..Request::new(PokemonNumber::pikachu())
+        };
+
+        match execute(repo, req, &Actor::ci()) {
+            Err(Error::PreconditionFailed(1)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_delete_every_form_with_the_default_one() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(
+            Pokemon::pikachu().with_form(FormSlug::alola()),
+            &Actor::ci(),
+        );
+
+        match (
+            execute(
+                repo.clone(),
+                Request::new(PokemonNumber::pikachu()),
+                &Actor::ci(),
+            ),
+            repo.fetch_forms(PokemonNumber::pikachu()),
+        ) {
+            (Ok(()), Ok(forms)) => assert!(forms.is_empty()),
+            _ => unreachable!(),
+        }
+    }
 }
@@ -1,6 +1,331 @@
-use crate::domain::entities::PokemonType::{Electric, Fire};
+use std::error::Error;
+use std::fmt;
+use std::time::SystemTime;
 
-#[derive(PartialEq, Clone, PartialOrd, Eq, Ord)]
+use sha2::{Digest, Sha256};
+
+/// Why a value was rejected while building a domain entity.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    NumberOutOfRange {
+        min: u16,
+        max: u16,
+        got: u16,
+    },
+    EmptyName,
+    NoTypes,
+    UnknownType(String),
+    DuplicateType(String),
+    LimitOutOfRange {
+        min: u32,
+        max: u32,
+        got: u32,
+    },
+    InvalidCursor,
+    CursorWithOffset,
+    UnknownSortField(String),
+    UnknownSortOrder(String),
+    StatOutOfRange {
+        stat: Stat,
+        min: u16,
+        max: u16,
+        got: u16,
+    },
+    EffortValuesTotal {
+        max: u16,
+        got: u16,
+    },
+    LevelOutOfRange {
+        min: u8,
+        max: u8,
+        got: u8,
+    },
+    UnknownNature(String),
+    EmptyAbilityName,
+    UnknownAbilitySlot(String),
+    DuplicateAbilitySlot(String),
+    UnknownAbility(String),
+    UnknownTrigger(String),
+    MissingTriggerDetail(&'static str),
+    UnknownPokemon(u16),
+    HasPreEvolution {
+        number: u16,
+        from: u16,
+    },
+    EvolutionCycle {
+        from: u16,
+        to: u16,
+    },
+    EmptyMoveName,
+    UnknownMoveCategory(String),
+    PowerOutOfRange {
+        min: u8,
+        max: u8,
+        got: u8,
+    },
+    AccuracyOutOfRange {
+        min: u8,
+        max: u8,
+        got: u8,
+    },
+    PowerPointsOutOfRange {
+        min: u8,
+        max: u8,
+        got: u8,
+    },
+    UnknownMove(String),
+    UnknownLearnMethod(String),
+    MissingLevel,
+    GenerationOutOfRange {
+        min: u8,
+        max: u8,
+        got: u8,
+    },
+    InvalidForm(String),
+    UnknownImportFormat(String),
+    UnknownExportFormat(String),
+    UnknownConflictPolicy(String),
+    MissingColumn(&'static str),
+    MalformedContent(String),
+    EmptyKeyName,
+    UnknownRole(String),
+    EmptySearch,
+    InvalidTimestamp(String),
+    InvalidDuration(String),
+}
+
+impl ValidationError {
+    /// A stable, machine readable identifier of the error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValidationError::NumberOutOfRange { .. } => "number_out_of_range",
+            ValidationError::EmptyName => "empty_name",
+            ValidationError::NoTypes => "no_types",
+            ValidationError::UnknownType(_) => "unknown_type",
+            ValidationError::DuplicateType(_) => "duplicate_type",
+            ValidationError::LimitOutOfRange { .. } => "limit_out_of_range",
+            ValidationError::InvalidCursor => "invalid_cursor",
+            ValidationError::CursorWithOffset => "cursor_with_offset",
+            ValidationError::UnknownSortField(_) => "unknown_sort_field",
+            ValidationError::UnknownSortOrder(_) => "unknown_sort_order",
+            ValidationError::StatOutOfRange { .. } => "stat_out_of_range",
+            ValidationError::EffortValuesTotal { .. } => "effort_values_total",
+            ValidationError::LevelOutOfRange { .. } => "level_out_of_range",
+            ValidationError::UnknownNature(_) => "unknown_nature",
+            ValidationError::EmptyAbilityName => "empty_ability_name",
+            ValidationError::UnknownAbilitySlot(_) => "unknown_ability_slot",
+            ValidationError::DuplicateAbilitySlot(_) => "duplicate_ability_slot",
+            ValidationError::UnknownAbility(_) => "unknown_ability",
+            ValidationError::UnknownTrigger(_) => "unknown_trigger",
+            ValidationError::MissingTriggerDetail(_) => "missing_trigger_detail",
+            ValidationError::UnknownPokemon(_) => "unknown_pokemon",
+            ValidationError::HasPreEvolution { .. } => "has_pre_evolution",
+            ValidationError::EvolutionCycle { .. } => "evolution_cycle",
+            ValidationError::EmptyMoveName => "empty_move_name",
+            ValidationError::UnknownMoveCategory(_) => "unknown_move_category",
+            ValidationError::PowerOutOfRange { .. } => "power_out_of_range",
+            ValidationError::AccuracyOutOfRange { .. } => "accuracy_out_of_range",
+            ValidationError::PowerPointsOutOfRange { .. } => "power_points_out_of_range",
+            ValidationError::UnknownMove(_) => "unknown_move",
+            ValidationError::UnknownLearnMethod(_) => "unknown_learn_method",
+            ValidationError::MissingLevel => "missing_level",
+            ValidationError::GenerationOutOfRange { .. } => "generation_out_of_range",
+            ValidationError::InvalidForm(_) => "invalid_form",
+            ValidationError::UnknownImportFormat(_) => "unknown_import_format",
+            ValidationError::UnknownExportFormat(_) => "unknown_export_format",
+            ValidationError::UnknownConflictPolicy(_) => "unknown_conflict_policy",
+            ValidationError::MissingColumn(_) => "missing_column",
+            ValidationError::MalformedContent(_) => "malformed_content",
+            ValidationError::EmptyKeyName => "empty_key_name",
+            ValidationError::UnknownRole(_) => "unknown_role",
+            ValidationError::EmptySearch => "empty_search",
+            ValidationError::InvalidTimestamp(_) => "invalid_timestamp",
+            ValidationError::InvalidDuration(_) => "invalid_duration",
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::NumberOutOfRange { min, max, got } => write!(
+                f,
+                "the Pokemon number must be between {} and {}, got {}",
+                min, max, got
+            ),
+            ValidationError::EmptyName => write!(f, "the Pokemon name must not be empty"),
+            ValidationError::NoTypes => write!(f, "a Pokemon must have at least one type"),
+            ValidationError::UnknownType(name) => write!(f, "'{}' is not a Pokemon type", name),
+            ValidationError::DuplicateType(name) => {
+                write!(f, "the type '{}' is listed more than once", name)
+            }
+            ValidationError::LimitOutOfRange { min, max, got } => write!(
+                f,
+                "the limit must be between {} and {}, got {}",
+                min, max, got
+            ),
+            ValidationError::InvalidCursor => write!(f, "the cursor is not valid"),
+            ValidationError::CursorWithOffset => {
+                write!(f, "a cursor and an offset cannot be used together")
+            }
+            ValidationError::UnknownSortField(field) => {
+                write!(f, "cannot sort by '{}', expected 'number' or 'name'", field)
+            }
+            ValidationError::UnknownSortOrder(order) => {
+                write!(
+                    f,
+                    "unknown sort order '{}', expected 'asc' or 'desc'",
+                    order
+                )
+            }
+            ValidationError::StatOutOfRange {
+                stat,
+                min,
+                max,
+                got,
+            } => write!(
+                f,
+                "the {} must be between {} and {}, got {}",
+                stat, min, max, got
+            ),
+            ValidationError::EffortValuesTotal { max, got } => write!(
+                f,
+                "the effort values must not add up to more than {}, got {}",
+                max, got
+            ),
+            ValidationError::LevelOutOfRange { min, max, got } => write!(
+                f,
+                "the level must be between {} and {}, got {}",
+                min, max, got
+            ),
+            ValidationError::UnknownNature(name) => write!(f, "'{}' is not a nature", name),
+            ValidationError::EmptyAbilityName => write!(f, "the ability name must not be empty"),
+            ValidationError::UnknownAbilitySlot(slot) => write!(
+                f,
+                "unknown ability slot '{}', expected 'primary', 'secondary' or 'hidden'",
+                slot
+            ),
+            ValidationError::DuplicateAbilitySlot(slot) => {
+                write!(f, "the ability slot '{}' is listed more than once", slot)
+            }
+            ValidationError::UnknownAbility(name) => {
+                write!(f, "there is no ability named '{}'", name)
+            }
+            ValidationError::UnknownTrigger(kind) => write!(
+                f,
+                "unknown trigger '{}', expected 'level_up', 'item', 'trade' or 'friendship'",
+                kind
+            ),
+            ValidationError::MissingTriggerDetail(detail) => {
+                write!(f, "this trigger requires a {}", detail)
+            }
+            ValidationError::UnknownPokemon(number) => {
+                write!(f, "there is no Pokemon with number {}", number)
+            }
+            ValidationError::HasPreEvolution { number, from } => {
+                write!(f, "the Pokemon #{} already evolves from #{}", number, from)
+            }
+            ValidationError::EvolutionCycle { from, to } => write!(
+                f,
+                "#{} cannot evolve into #{} as it would form a cycle",
+                from, to
+            ),
+            ValidationError::EmptyMoveName => write!(f, "the move name must not be empty"),
+            ValidationError::UnknownMoveCategory(category) => write!(
+                f,
+                "unknown move category '{}', expected 'physical', 'special' or 'status'",
+                category
+            ),
+            ValidationError::PowerOutOfRange { min, max, got } => write!(
+                f,
+                "the power must be between {} and {}, got {}",
+                min, max, got
+            ),
+            ValidationError::AccuracyOutOfRange { min, max, got } => write!(
+                f,
+                "the accuracy must be between {} and {}, got {}",
+                min, max, got
+            ),
+            ValidationError::PowerPointsOutOfRange { min, max, got } => {
+                write!(f, "the PP must be between {} and {}, got {}", min, max, got)
+            }
+            ValidationError::UnknownMove(name) => write!(f, "there is no move named '{}'", name),
+            ValidationError::UnknownLearnMethod(method) => write!(
+                f,
+                "unknown learn method '{}', expected 'level', 'tm', 'egg' or 'tutor'",
+                method
+            ),
+            ValidationError::MissingLevel => {
+                write!(f, "a move learnt by level up requires a level")
+            }
+            ValidationError::GenerationOutOfRange { min, max, got } => write!(
+                f,
+                "the generation must be between {} and {}, got {}",
+                min, max, got
+            ),
+            ValidationError::InvalidForm(form) => write!(
+                f,
+                "'{}' is not a valid form, expected lowercase letters and digits separated by dashes",
+                form
+            ),
+            ValidationError::UnknownImportFormat(format) => write!(
+                f,
+                "unknown import format '{}', expected 'json', 'jsonl' or 'csv'",
+                format
+            ),
+            ValidationError::UnknownExportFormat(format) => write!(
+                f,
+                "unknown export format '{}', expected 'json', 'jsonl', 'csv' or 'sql'",
+                format
+            ),
+            ValidationError::UnknownConflictPolicy(policy) => write!(
+                f,
+                "unknown conflict policy '{}', expected 'skip', 'replace' or 'fail'",
+                policy
+            ),
+            ValidationError::MissingColumn(column) => {
+                write!(f, "the CSV header has no '{}' column", column)
+            }
+            ValidationError::MalformedContent(e) => {
+                write!(f, "the content is not a JSON array: {}", e)
+            }
+            ValidationError::EmptyKeyName => write!(f, "the API key name must not be empty"),
+            ValidationError::UnknownRole(role) => write!(
+                f,
+                "unknown role '{}', expected 'reader', 'editor' or 'admin'",
+                role
+            ),
+            ValidationError::EmptySearch => write!(f, "the search must not be empty"),
+            ValidationError::InvalidTimestamp(timestamp) => write!(
+                f,
+                "invalid timestamp '{}', expected RFC 3339 such as 2024-01-01T00:00:00Z",
+                timestamp
+            ),
+            ValidationError::InvalidDuration(duration) => write!(
+                f,
+                "invalid duration '{}', expected a number of units such as 30d or 12h",
+                duration
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// A validation error attached to the request field it was raised for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub error: ValidationError,
+}
+
+impl FieldError {
+    pub fn new(field: &'static str, error: ValidationError) -> Self {
+        Self { field, error }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord)]
 pub struct PokemonNumber(u16);
 
 #[cfg(test)]
@@ -18,14 +343,23 @@ impl PokemonNumber {
     }
 }
 
+impl PokemonNumber {
+    pub const MIN: u16 = 1;
+    pub const MAX: u16 = 898;
+}
+
 impl TryFrom<u16> for PokemonNumber {
-    type Error = ();
+    type Error = ValidationError;
 
     fn try_from(val: u16) -> Result<Self, Self::Error> {
-        if val > 0 && val < 899 {
+        if (Self::MIN..=Self::MAX).contains(&val) {
             Ok(Self(val))
         } else {
-            Err(())
+            Err(ValidationError::NumberOutOfRange {
+                min: Self::MIN,
+                max: Self::MAX,
+                got: val,
+            })
         }
     }
 }
@@ -36,7 +370,7 @@ impl From<PokemonNumber> for u16 {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord)]
 pub struct PokemonName(String);
 
 #[cfg(test)]
@@ -55,11 +389,11 @@ impl PokemonName {
 }
 
 impl TryFrom<String> for PokemonName {
-    type Error = ();
+    type Error = ValidationError;
 
     fn try_from(val: String) -> Result<Self, Self::Error> {
         if val.is_empty() {
-            return Err(());
+            return Err(ValidationError::EmptyName);
         }
 
         Ok(Self(val))
@@ -72,6 +406,57 @@ impl From<PokemonName> for String {
     }
 }
 
+/// Identifies a form among those sharing a number, such as `alola` or
+/// `mega-x`. Every Pokemon has a `default` form.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FormSlug(String);
+
+impl FormSlug {
+    pub const DEFAULT: &'static str = "default";
+
+    pub fn is_default(&self) -> bool {
+        self.0 == Self::DEFAULT
+    }
+}
+
+impl Default for FormSlug {
+    fn default() -> Self {
+        Self(String::from(Self::DEFAULT))
+    }
+}
+
+#[cfg(test)]
+impl FormSlug {
+    pub fn alola() -> Self {
+        Self(String::from("alola"))
+    }
+}
+
+impl TryFrom<String> for FormSlug {
+    type Error = ValidationError;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        let valid = val.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        });
+
+        if valid {
+            Ok(Self(val))
+        } else {
+            Err(ValidationError::InvalidForm(val))
+        }
+    }
+}
+
+impl From<FormSlug> for String {
+    fn from(val: FormSlug) -> Self {
+        val.0
+    }
+}
+
 #[derive(Clone)]
 pub struct PokemonTypes(Vec<PokemonType>);
 
@@ -87,45 +472,171 @@ impl PokemonTypes {
 }
 
 impl TryFrom<Vec<String>> for PokemonTypes {
-    type Error = ();
+    type Error = ValidationError;
 
     fn try_from(val: Vec<String>) -> Result<Self, Self::Error> {
         if val.is_empty() {
-            return Err(());
+            return Err(ValidationError::NoTypes);
         }
 
         let mut pts = vec![];
-        for t in val.iter() {
-            match PokemonType::try_from(String::from(t)) {
-                Ok(pt) => pts.push(pt),
-                Err(_) => return Err(()),
+        for t in val.into_iter() {
+            let pt = PokemonType::try_from(t)?;
+            if pts.contains(&pt) {
+                return Err(ValidationError::DuplicateType(String::from(pt)));
             }
+            pts.push(pt);
         }
 
         Ok(Self(pts))
     }
 }
 
+impl PokemonTypes {
+    pub fn contains(&self, pokemon_type: &PokemonType) -> bool {
+        self.0.contains(pokemon_type)
+    }
+
+    /// Damage multiplier of an attack of the given type against a Pokemon with these types.
+    pub fn effectiveness_of(&self, attacker: &PokemonType) -> f32 {
+        self.0
+            .iter()
+            .map(|defender| attacker.effectiveness_against(defender))
+            .product()
+    }
+}
+
 impl From<PokemonTypes> for Vec<String> {
     fn from(val: PokemonTypes) -> Self {
         val.0.into_iter().map(String::from).collect::<_>()
     }
 }
 
-#[derive(Clone)]
-enum PokemonType {
-    Electric,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PokemonType {
+    Normal,
     Fire,
+    Water,
+    Electric,
+    Grass,
+    Ice,
+    Fighting,
+    Poison,
+    Ground,
+    Flying,
+    Psychic,
+    Bug,
+    Rock,
+    Ghost,
+    Dragon,
+    Dark,
+    Steel,
+    Fairy,
+}
+
+impl PokemonType {
+    pub const ALL: [PokemonType; 18] = [
+        PokemonType::Normal,
+        PokemonType::Fire,
+        PokemonType::Water,
+        PokemonType::Electric,
+        PokemonType::Grass,
+        PokemonType::Ice,
+        PokemonType::Fighting,
+        PokemonType::Poison,
+        PokemonType::Ground,
+        PokemonType::Flying,
+        PokemonType::Psychic,
+        PokemonType::Bug,
+        PokemonType::Rock,
+        PokemonType::Ghost,
+        PokemonType::Dragon,
+        PokemonType::Dark,
+        PokemonType::Steel,
+        PokemonType::Fairy,
+    ];
+
+    /// Damage multiplier of an attack of this type against a single-typed defender.
+    pub fn effectiveness_against(&self, defender: &PokemonType) -> f32 {
+        use PokemonType::*;
+
+        match (self, defender) {
+            (Normal, Rock | Steel) => 0.5,
+            (Normal, Ghost) => 0.0,
+
+            (Fire, Grass | Ice | Bug | Steel) => 2.0,
+            (Fire, Fire | Water | Rock | Dragon) => 0.5,
+
+            (Water, Fire | Ground | Rock) => 2.0,
+            (Water, Water | Grass | Dragon) => 0.5,
+
+            (Electric, Water | Flying) => 2.0,
+            (Electric, Electric | Grass | Dragon) => 0.5,
+            (Electric, Ground) => 0.0,
+
+            (Grass, Water | Ground | Rock) => 2.0,
+            (Grass, Fire | Grass | Poison | Flying | Bug | Dragon | Steel) => 0.5,
+
+            (Ice, Grass | Ground | Flying | Dragon) => 2.0,
+            (Ice, Fire | Water | Ice | Steel) => 0.5,
+
+            (Fighting, Normal | Ice | Rock | Dark | Steel) => 2.0,
+            (Fighting, Poison | Flying | Psychic | Bug | Fairy) => 0.5,
+            (Fighting, Ghost) => 0.0,
+
+            (Poison, Grass | Fairy) => 2.0,
+            (Poison, Poison | Ground | Rock | Ghost) => 0.5,
+            (Poison, Steel) => 0.0,
+
+            (Ground, Fire | Electric | Poison | Rock | Steel) => 2.0,
+            (Ground, Grass | Bug) => 0.5,
+            (Ground, Flying) => 0.0,
+
+            (Flying, Grass | Fighting | Bug) => 2.0,
+            (Flying, Electric | Rock | Steel) => 0.5,
+
+            (Psychic, Fighting | Poison) => 2.0,
+            (Psychic, Psychic | Steel) => 0.5,
+            (Psychic, Dark) => 0.0,
+
+            (Bug, Grass | Psychic | Dark) => 2.0,
+            (Bug, Fire | Fighting | Poison | Flying | Ghost | Steel | Fairy) => 0.5,
+
+            (Rock, Fire | Ice | Flying | Bug) => 2.0,
+            (Rock, Fighting | Ground | Steel) => 0.5,
+
+            (Ghost, Psychic | Ghost) => 2.0,
+            (Ghost, Dark) => 0.5,
+            (Ghost, Normal) => 0.0,
+
+            (Dragon, Dragon) => 2.0,
+            (Dragon, Steel) => 0.5,
+            (Dragon, Fairy) => 0.0,
+
+            (Dark, Psychic | Ghost) => 2.0,
+            (Dark, Fighting | Dark | Fairy) => 0.5,
+
+            (Steel, Ice | Rock | Fairy) => 2.0,
+            (Steel, Fire | Water | Electric | Steel) => 0.5,
+
+            (Fairy, Fighting | Dragon | Dark) => 2.0,
+            (Fairy, Fire | Poison | Steel) => 0.5,
+
+            _ => 1.0,
+        }
+    }
 }
 
 impl TryFrom<String> for PokemonType {
-    type Error = ();
+    type Error = ValidationError;
 
     fn try_from(val: String) -> Result<Self, Self::Error> {
-        match val.as_str() {
-            "Electric" => Ok(Electric),
-            "Fire" => Ok(Fire),
-            _ => Err(()),
+        match PokemonType::ALL
+            .into_iter()
+            .find(|t| String::from(*t) == val)
+        {
+            Some(pokemon_type) => Ok(pokemon_type),
+            None => Err(ValidationError::UnknownType(val)),
         }
     }
 }
@@ -133,25 +644,1470 @@ impl TryFrom<String> for PokemonType {
 impl From<PokemonType> for String {
     fn from(val: PokemonType) -> Self {
         String::from(match val {
-            Electric => "Electric",
-            Fire => "Fire",
+            PokemonType::Normal => "Normal",
+            PokemonType::Fire => "Fire",
+            PokemonType::Water => "Water",
+            PokemonType::Electric => "Electric",
+            PokemonType::Grass => "Grass",
+            PokemonType::Ice => "Ice",
+            PokemonType::Fighting => "Fighting",
+            PokemonType::Poison => "Poison",
+            PokemonType::Ground => "Ground",
+            PokemonType::Flying => "Flying",
+            PokemonType::Psychic => "Psychic",
+            PokemonType::Bug => "Bug",
+            PokemonType::Rock => "Rock",
+            PokemonType::Ghost => "Ghost",
+            PokemonType::Dragon => "Dragon",
+            PokemonType::Dark => "Dark",
+            PokemonType::Steel => "Steel",
+            PokemonType::Fairy => "Fairy",
         })
     }
 }
 
-#[derive(Clone)]
-pub struct Pokemon {
-    pub number: PokemonNumber,
-    pub name: PokemonName,
-    pub types: PokemonTypes,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stat {
+    Hp,
+    Attack,
+    Defense,
+    SpecialAttack,
+    SpecialDefense,
+    Speed,
 }
 
-impl Pokemon {
-    pub fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+impl Stat {
+    pub const ALL: [Stat; 6] = [
+        Stat::Hp,
+        Stat::Attack,
+        Stat::Defense,
+        Stat::SpecialAttack,
+        Stat::SpecialDefense,
+        Stat::Speed,
+    ];
+}
+
+impl fmt::Display for Stat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Stat::Hp => "HP",
+            Stat::Attack => "Attack",
+            Stat::Defense => "Defense",
+            Stat::SpecialAttack => "Special Attack",
+            Stat::SpecialDefense => "Special Defense",
+            Stat::Speed => "Speed",
+        })
+    }
+}
+
+/// One value per stat, in HP/Atk/Def/SpA/SpD/Spe order. Used to carry raw,
+/// unvalidated values in and out of the domain.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StatValues {
+    pub hp: u16,
+    pub attack: u16,
+    pub defense: u16,
+    pub special_attack: u16,
+    pub special_defense: u16,
+    pub speed: u16,
+}
+
+impl StatValues {
+    pub fn uniform(value: u16) -> Self {
+        Self::from([value; 6])
+    }
+
+    pub fn get(&self, stat: Stat) -> u16 {
+        match stat {
+            Stat::Hp => self.hp,
+            Stat::Attack => self.attack,
+            Stat::Defense => self.defense,
+            Stat::SpecialAttack => self.special_attack,
+            Stat::SpecialDefense => self.special_defense,
+            Stat::Speed => self.speed,
+        }
+    }
+
+    pub fn total(&self) -> u16 {
+        Stat::ALL.iter().map(|stat| self.get(*stat)).sum()
+    }
+
+    /// Checks that every stat lies within `min..=max`.
+    pub fn check_range(&self, min: u16, max: u16) -> Result<(), ValidationError> {
+        match Stat::ALL
+            .into_iter()
+            .find(|stat| !(min..=max).contains(&self.get(*stat)))
+        {
+            Some(stat) => Err(ValidationError::StatOutOfRange {
+                stat,
+                min,
+                max,
+                got: self.get(stat),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl From<[u16; 6]> for StatValues {
+    fn from(val: [u16; 6]) -> Self {
         Self {
-            number,
-            name,
-            types,
+            hp: val[0],
+            attack: val[1],
+            defense: val[2],
+            special_attack: val[3],
+            special_defense: val[4],
+            speed: val[5],
+        }
+    }
+}
+
+impl From<StatValues> for [u16; 6] {
+    fn from(val: StatValues) -> Self {
+        Stat::ALL.map(|stat| val.get(stat))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BaseStats(StatValues);
+
+#[cfg(test)]
+impl BaseStats {
+    pub fn pikachu() -> Self {
+        Self(StatValues::from([35, 55, 40, 50, 50, 90]))
+    }
+}
+
+impl BaseStats {
+    pub const MIN: u16 = 1;
+    pub const MAX: u16 = 255;
+
+    pub fn get(&self, stat: Stat) -> u16 {
+        self.0.get(stat)
+    }
+}
+
+impl TryFrom<StatValues> for BaseStats {
+    type Error = ValidationError;
+
+    fn try_from(val: StatValues) -> Result<Self, Self::Error> {
+        val.check_range(Self::MIN, Self::MAX)?;
+        Ok(Self(val))
+    }
+}
+
+impl From<BaseStats> for StatValues {
+    fn from(val: BaseStats) -> Self {
+        val.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Level(u8);
+
+impl Level {
+    pub const MIN: u8 = 1;
+    pub const MAX: u8 = 100;
+}
+
+impl TryFrom<u8> for Level {
+    type Error = ValidationError;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&val) {
+            Ok(Self(val))
+        } else {
+            Err(ValidationError::LevelOutOfRange {
+                min: Self::MIN,
+                max: Self::MAX,
+                got: val,
+            })
+        }
+    }
+}
+
+impl From<Level> for u8 {
+    fn from(val: Level) -> Self {
+        val.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Nature {
+    Hardy,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
+impl Nature {
+    pub const ALL: [Nature; 25] = [
+        Nature::Hardy,
+        Nature::Lonely,
+        Nature::Brave,
+        Nature::Adamant,
+        Nature::Naughty,
+        Nature::Bold,
+        Nature::Docile,
+        Nature::Relaxed,
+        Nature::Impish,
+        Nature::Lax,
+        Nature::Timid,
+        Nature::Hasty,
+        Nature::Serious,
+        Nature::Jolly,
+        Nature::Naive,
+        Nature::Modest,
+        Nature::Mild,
+        Nature::Quiet,
+        Nature::Bashful,
+        Nature::Rash,
+        Nature::Calm,
+        Nature::Gentle,
+        Nature::Sassy,
+        Nature::Careful,
+        Nature::Quirky,
+    ];
+
+    /// The stats raised and lowered by 10% by this nature, `None` for the
+    /// five neutral natures.
+    pub fn modifiers(&self) -> Option<(Stat, Stat)> {
+        use Stat::*;
+
+        match self {
+            Nature::Hardy | Nature::Docile | Nature::Serious | Nature::Bashful | Nature::Quirky => {
+                None
+            }
+            Nature::Lonely => Some((Attack, Defense)),
+            Nature::Brave => Some((Attack, Speed)),
+            Nature::Adamant => Some((Attack, SpecialAttack)),
+            Nature::Naughty => Some((Attack, SpecialDefense)),
+            Nature::Bold => Some((Defense, Attack)),
+            Nature::Relaxed => Some((Defense, Speed)),
+            Nature::Impish => Some((Defense, SpecialAttack)),
+            Nature::Lax => Some((Defense, SpecialDefense)),
+            Nature::Timid => Some((Speed, Attack)),
+            Nature::Hasty => Some((Speed, Defense)),
+            Nature::Jolly => Some((Speed, SpecialAttack)),
+            Nature::Naive => Some((Speed, SpecialDefense)),
+            Nature::Modest => Some((SpecialAttack, Attack)),
+            Nature::Mild => Some((SpecialAttack, Defense)),
+            Nature::Quiet => Some((SpecialAttack, Speed)),
+            Nature::Rash => Some((SpecialAttack, SpecialDefense)),
+            Nature::Calm => Some((SpecialDefense, Attack)),
+            Nature::Gentle => Some((SpecialDefense, Defense)),
+            Nature::Sassy => Some((SpecialDefense, Speed)),
+            Nature::Careful => Some((SpecialDefense, SpecialAttack)),
+        }
+    }
+
+    /// The multiplier applied to `stat`, as a percentage.
+    pub fn percentage(&self, stat: Stat) -> u16 {
+        match self.modifiers() {
+            Some((raised, _)) if raised == stat => 110,
+            Some((_, lowered)) if lowered == stat => 90,
+            _ => 100,
+        }
+    }
+}
+
+impl TryFrom<String> for Nature {
+    type Error = ValidationError;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match Nature::ALL
+            .into_iter()
+            .find(|nature| String::from(*nature).eq_ignore_ascii_case(&val))
+        {
+            Some(nature) => Ok(nature),
+            None => Err(ValidationError::UnknownNature(val)),
+        }
+    }
+}
+
+impl From<Nature> for String {
+    fn from(val: Nature) -> Self {
+        format!("{:?}", val)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IndividualValues(StatValues);
+
+impl IndividualValues {
+    pub const MAX: u16 = 31;
+
+    pub fn get(&self, stat: Stat) -> u16 {
+        self.0.get(stat)
+    }
+}
+
+impl TryFrom<StatValues> for IndividualValues {
+    type Error = ValidationError;
+
+    fn try_from(val: StatValues) -> Result<Self, Self::Error> {
+        val.check_range(0, Self::MAX)?;
+        Ok(Self(val))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EffortValues(StatValues);
+
+impl EffortValues {
+    pub const MAX: u16 = 252;
+    pub const MAX_TOTAL: u16 = 510;
+
+    pub fn get(&self, stat: Stat) -> u16 {
+        self.0.get(stat)
+    }
+}
+
+impl TryFrom<StatValues> for EffortValues {
+    type Error = ValidationError;
+
+    fn try_from(val: StatValues) -> Result<Self, Self::Error> {
+        val.check_range(0, Self::MAX)?;
+        match val.total() {
+            total if total > Self::MAX_TOTAL => Err(ValidationError::EffortValuesTotal {
+                max: Self::MAX_TOTAL,
+                got: total,
+            }),
+            _ => Ok(Self(val)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AbilityName(String);
+
+#[cfg(test)]
+impl AbilityName {
+    pub fn static_() -> Self {
+        Self(String::from("Static"))
+    }
+
+    pub fn lightning_rod() -> Self {
+        Self(String::from("Lightning Rod"))
+    }
+}
+
+impl TryFrom<String> for AbilityName {
+    type Error = ValidationError;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        if val.trim().is_empty() {
+            return Err(ValidationError::EmptyAbilityName);
+        }
+
+        Ok(Self(val))
+    }
+}
+
+impl From<AbilityName> for String {
+    fn from(val: AbilityName) -> Self {
+        val.0
+    }
+}
+
+#[derive(Clone)]
+pub struct Ability {
+    pub name: AbilityName,
+    pub description: String,
+}
+
+impl Ability {
+    pub fn new(name: AbilityName, description: String) -> Self {
+        Self { name, description }
+    }
+}
+
+/// Where an ability sits on a Pokemon. The hidden ability is only obtainable
+/// through special means.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AbilitySlot {
+    Primary,
+    Secondary,
+    Hidden,
+}
+
+impl AbilitySlot {
+    pub const ALL: [AbilitySlot; 3] = [
+        AbilitySlot::Primary,
+        AbilitySlot::Secondary,
+        AbilitySlot::Hidden,
+    ];
+}
+
+impl TryFrom<String> for AbilitySlot {
+    type Error = ValidationError;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match AbilitySlot::ALL
+            .into_iter()
+            .find(|slot| String::from(*slot) == val)
+        {
+            Some(slot) => Ok(slot),
+            None => Err(ValidationError::UnknownAbilitySlot(val)),
         }
     }
 }
+
+impl From<AbilitySlot> for String {
+    fn from(val: AbilitySlot) -> Self {
+        String::from(match val {
+            AbilitySlot::Primary => "primary",
+            AbilitySlot::Secondary => "secondary",
+            AbilitySlot::Hidden => "hidden",
+        })
+    }
+}
+
+/// An ability of a Pokemon as carried in and out of the domain, unvalidated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlotValue {
+    pub slot: String,
+    pub name: String,
+}
+
+/// The abilities of a Pokemon, at most one per slot, ordered by slot.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PokemonAbilities(Vec<(AbilitySlot, AbilityName)>);
+
+#[cfg(test)]
+impl PokemonAbilities {
+    pub fn pikachu() -> Self {
+        Self(vec![
+            (AbilitySlot::Primary, AbilityName::static_()),
+            (AbilitySlot::Hidden, AbilityName::lightning_rod()),
+        ])
+    }
+}
+
+impl PokemonAbilities {
+    pub fn iter(&self) -> impl Iterator<Item = &(AbilitySlot, AbilityName)> {
+        self.0.iter()
+    }
+
+    pub fn contains(&self, name: &AbilityName) -> Option<AbilitySlot> {
+        self.0
+            .iter()
+            .find(|(_, ability)| ability == name)
+            .map(|(slot, _)| *slot)
+    }
+}
+
+impl TryFrom<Vec<SlotValue>> for PokemonAbilities {
+    type Error = ValidationError;
+
+    fn try_from(val: Vec<SlotValue>) -> Result<Self, Self::Error> {
+        let mut abilities = vec![];
+        for value in val.into_iter() {
+            let slot = AbilitySlot::try_from(value.slot)?;
+            if abilities.iter().any(|(s, _)| *s == slot) {
+                return Err(ValidationError::DuplicateAbilitySlot(String::from(slot)));
+            }
+            abilities.push((slot, AbilityName::try_from(value.name)?));
+        }
+        abilities.sort();
+
+        Ok(Self(abilities))
+    }
+}
+
+impl From<PokemonAbilities> for Vec<SlotValue> {
+    fn from(val: PokemonAbilities) -> Self {
+        val.0
+            .into_iter()
+            .map(|(slot, name)| SlotValue {
+                slot: String::from(slot),
+                name: String::from(name),
+            })
+            .collect()
+    }
+}
+
+/// What makes a Pokemon evolve.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvolutionTrigger {
+    LevelUp(Level),
+    Item(String),
+    Trade,
+    Friendship,
+}
+
+/// A trigger as carried in and out of the domain, unvalidated. `level` is only
+/// used by `level_up` and `item` only by `item`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TriggerValue {
+    pub kind: String,
+    pub level: Option<u8>,
+    pub item: Option<String>,
+}
+
+impl TryFrom<TriggerValue> for EvolutionTrigger {
+    type Error = ValidationError;
+
+    fn try_from(val: TriggerValue) -> Result<Self, Self::Error> {
+        match val.kind.as_str() {
+            "level_up" => match val.level {
+                Some(level) => Ok(EvolutionTrigger::LevelUp(Level::try_from(level)?)),
+                None => Err(ValidationError::MissingTriggerDetail("level")),
+            },
+            "item" => match val.item {
+                Some(item) if !item.trim().is_empty() => Ok(EvolutionTrigger::Item(item)),
+                _ => Err(ValidationError::MissingTriggerDetail("item")),
+            },
+            "trade" => Ok(EvolutionTrigger::Trade),
+            "friendship" => Ok(EvolutionTrigger::Friendship),
+            _ => Err(ValidationError::UnknownTrigger(val.kind)),
+        }
+    }
+}
+
+impl From<EvolutionTrigger> for TriggerValue {
+    fn from(val: EvolutionTrigger) -> Self {
+        match val {
+            EvolutionTrigger::LevelUp(level) => Self {
+                kind: String::from("level_up"),
+                level: Some(u8::from(level)),
+                item: None,
+            },
+            EvolutionTrigger::Item(item) => Self {
+                kind: String::from("item"),
+                level: None,
+                item: Some(item),
+            },
+            EvolutionTrigger::Trade => Self {
+                kind: String::from("trade"),
+                ..Self::default()
+            },
+            EvolutionTrigger::Friendship => Self {
+                kind: String::from("friendship"),
+                ..Self::default()
+            },
+        }
+    }
+}
+
+/// An edge of an evolution chain. A Pokemon evolves from at most one other,
+/// but may evolve into several (Eevee).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evolution {
+    pub from: PokemonNumber,
+    pub to: PokemonNumber,
+    pub trigger: EvolutionTrigger,
+}
+
+impl Evolution {
+    pub fn new(from: PokemonNumber, to: PokemonNumber, trigger: EvolutionTrigger) -> Self {
+        Self { from, to, trigger }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MoveName(String);
+
+#[cfg(test)]
+impl MoveName {
+    pub fn thunderbolt() -> Self {
+        Self(String::from("Thunderbolt"))
+    }
+
+    pub fn quick_attack() -> Self {
+        Self(String::from("Quick Attack"))
+    }
+}
+
+impl TryFrom<String> for MoveName {
+    type Error = ValidationError;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        if val.trim().is_empty() {
+            return Err(ValidationError::EmptyMoveName);
+        }
+
+        Ok(Self(val))
+    }
+}
+
+impl From<MoveName> for String {
+    fn from(val: MoveName) -> Self {
+        val.0
+    }
+}
+
+/// Whether a move deals physical or special damage, or none at all.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveCategory {
+    Physical,
+    Special,
+    Status,
+}
+
+impl MoveCategory {
+    pub const ALL: [MoveCategory; 3] = [
+        MoveCategory::Physical,
+        MoveCategory::Special,
+        MoveCategory::Status,
+    ];
+}
+
+impl TryFrom<String> for MoveCategory {
+    type Error = ValidationError;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match MoveCategory::ALL
+            .into_iter()
+            .find(|category| String::from(*category) == val)
+        {
+            Some(category) => Ok(category),
+            None => Err(ValidationError::UnknownMoveCategory(val)),
+        }
+    }
+}
+
+impl From<MoveCategory> for String {
+    fn from(val: MoveCategory) -> Self {
+        String::from(match val {
+            MoveCategory::Physical => "physical",
+            MoveCategory::Special => "special",
+            MoveCategory::Status => "status",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MovePower(u8);
+
+impl MovePower {
+    pub const MIN: u8 = 1;
+    pub const MAX: u8 = 250;
+}
+
+impl TryFrom<u8> for MovePower {
+    type Error = ValidationError;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&val) {
+            Ok(Self(val))
+        } else {
+            Err(ValidationError::PowerOutOfRange {
+                min: Self::MIN,
+                max: Self::MAX,
+                got: val,
+            })
+        }
+    }
+}
+
+impl From<MovePower> for u8 {
+    fn from(val: MovePower) -> Self {
+        val.0
+    }
+}
+
+/// The chance of a move to hit, in percent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MoveAccuracy(u8);
+
+impl MoveAccuracy {
+    pub const MIN: u8 = 1;
+    pub const MAX: u8 = 100;
+}
+
+impl TryFrom<u8> for MoveAccuracy {
+    type Error = ValidationError;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&val) {
+            Ok(Self(val))
+        } else {
+            Err(ValidationError::AccuracyOutOfRange {
+                min: Self::MIN,
+                max: Self::MAX,
+                got: val,
+            })
+        }
+    }
+}
+
+impl From<MoveAccuracy> for u8 {
+    fn from(val: MoveAccuracy) -> Self {
+        val.0
+    }
+}
+
+/// How many times a move can be used before resting, without PP Ups.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PowerPoints(u8);
+
+impl PowerPoints {
+    pub const MIN: u8 = 1;
+    pub const MAX: u8 = 40;
+}
+
+impl TryFrom<u8> for PowerPoints {
+    type Error = ValidationError;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&val) {
+            Ok(Self(val))
+        } else {
+            Err(ValidationError::PowerPointsOutOfRange {
+                min: Self::MIN,
+                max: Self::MAX,
+                got: val,
+            })
+        }
+    }
+}
+
+impl From<PowerPoints> for u8 {
+    fn from(val: PowerPoints) -> Self {
+        val.0
+    }
+}
+
+/// A move of the catalogue. Status moves have no power, and moves that never
+/// miss have no accuracy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Move {
+    pub name: MoveName,
+    pub move_type: PokemonType,
+    pub category: MoveCategory,
+    pub power: Option<MovePower>,
+    pub accuracy: Option<MoveAccuracy>,
+    pub pp: PowerPoints,
+}
+
+#[cfg(test)]
+impl Move {
+    pub fn thunderbolt() -> Self {
+        Self {
+            name: MoveName::thunderbolt(),
+            move_type: PokemonType::Electric,
+            category: MoveCategory::Special,
+            power: Some(MovePower(90)),
+            accuracy: Some(MoveAccuracy(100)),
+            pp: PowerPoints(15),
+        }
+    }
+
+    pub fn quick_attack() -> Self {
+        Self {
+            name: MoveName::quick_attack(),
+            move_type: PokemonType::Normal,
+            category: MoveCategory::Physical,
+            power: Some(MovePower(40)),
+            accuracy: Some(MoveAccuracy(100)),
+            pp: PowerPoints(30),
+        }
+    }
+}
+
+/// How a Pokemon learns a move, in the order learnsets are listed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LearnMethod {
+    Level,
+    Tm,
+    Egg,
+    Tutor,
+}
+
+impl LearnMethod {
+    pub const ALL: [LearnMethod; 4] = [
+        LearnMethod::Level,
+        LearnMethod::Tm,
+        LearnMethod::Egg,
+        LearnMethod::Tutor,
+    ];
+}
+
+impl TryFrom<String> for LearnMethod {
+    type Error = ValidationError;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match LearnMethod::ALL
+            .into_iter()
+            .find(|method| String::from(*method) == val)
+        {
+            Some(method) => Ok(method),
+            None => Err(ValidationError::UnknownLearnMethod(val)),
+        }
+    }
+}
+
+impl From<LearnMethod> for String {
+    fn from(val: LearnMethod) -> Self {
+        String::from(match val {
+            LearnMethod::Level => "level",
+            LearnMethod::Tm => "tm",
+            LearnMethod::Egg => "egg",
+            LearnMethod::Tutor => "tutor",
+        })
+    }
+}
+
+/// A main series generation, learnsets changing from one to the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Generation(u8);
+
+impl Generation {
+    pub const MIN: u8 = 1;
+    pub const MAX: u8 = 8;
+}
+
+impl TryFrom<u8> for Generation {
+    type Error = ValidationError;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&val) {
+            Ok(Self(val))
+        } else {
+            Err(ValidationError::GenerationOutOfRange {
+                min: Self::MIN,
+                max: Self::MAX,
+                got: val,
+            })
+        }
+    }
+}
+
+impl From<Generation> for u8 {
+    fn from(val: Generation) -> Self {
+        val.0
+    }
+}
+
+/// A move a Pokemon can learn in a generation. `level` is only set for moves
+/// learnt by level up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LearnsetEntry {
+    pub move_name: MoveName,
+    pub method: LearnMethod,
+    pub level: Option<Level>,
+    pub generation: Generation,
+}
+
+impl LearnsetEntry {
+    pub fn new(
+        move_name: MoveName,
+        method: LearnMethod,
+        level: Option<Level>,
+        generation: Generation,
+    ) -> Result<Self, ValidationError> {
+        let level = match (method, level) {
+            (LearnMethod::Level, None) => return Err(ValidationError::MissingLevel),
+            (LearnMethod::Level, level) => level,
+            _ => None,
+        };
+
+        Ok(Self {
+            move_name,
+            method,
+            level,
+            generation,
+        })
+    }
+
+    /// The order learnsets are listed in: by generation, method, level and name.
+    pub fn sort_key(&self) -> (Generation, LearnMethod, Option<u8>, MoveName) {
+        (
+            self.generation,
+            self.method,
+            self.level.map(u8::from),
+            self.move_name.clone(),
+        )
+    }
+}
+
+/// A form of a Pokemon, identified by its number and form together.
+#[derive(Clone)]
+pub struct Pokemon {
+    pub number: PokemonNumber,
+    pub form: FormSlug,
+    pub name: PokemonName,
+    pub types: PokemonTypes,
+    pub stats: Option<BaseStats>,
+    pub abilities: PokemonAbilities,
+    /// Incremented by every write of the form, 0 until it is stored.
+    pub revision: u32,
+}
+
+impl Pokemon {
+    /// The revision of a form stored for the first time.
+    pub const FIRST_REVISION: u32 = 1;
+
+    pub fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+        Self {
+            number,
+            form: FormSlug::default(),
+            name,
+            types,
+            stats: None,
+            abilities: PokemonAbilities::default(),
+            revision: 0,
+        }
+    }
+
+    pub fn with_form(self, form: FormSlug) -> Self {
+        Self { form, ..self }
+    }
+
+    pub fn with_stats(self, stats: Option<BaseStats>) -> Self {
+        Self { stats, ..self }
+    }
+
+    pub fn with_abilities(self, abilities: PokemonAbilities) -> Self {
+        Self { abilities, ..self }
+    }
+
+    pub fn with_revision(self, revision: u32) -> Self {
+        Self { revision, ..self }
+    }
+}
+
+#[cfg(test)]
+impl Pokemon {
+    pub fn pikachu() -> Self {
+        Self::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+    }
+
+    pub fn charmander() -> Self {
+        Self::new(
+            PokemonNumber::charmander(),
+            PokemonName::charmader(),
+            PokemonTypes::charmander(),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct KeyName(String);
+
+impl TryFrom<String> for KeyName {
+    type Error = ValidationError;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        if val.trim().is_empty() {
+            return Err(ValidationError::EmptyKeyName);
+        }
+
+        Ok(Self(val))
+    }
+}
+
+impl From<KeyName> for String {
+    fn from(val: KeyName) -> Self {
+        val.0
+    }
+}
+
+/// What an API key is allowed to do, each role granting what the previous
+/// ones do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Reader,
+    Editor,
+    Admin,
+}
+
+impl Role {
+    pub const ALL: [Role; 3] = [Role::Reader, Role::Editor, Role::Admin];
+}
+
+impl TryFrom<String> for Role {
+    type Error = ValidationError;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match Role::ALL
+            .into_iter()
+            .find(|role| String::from(*role) == val)
+        {
+            Some(role) => Ok(role),
+            None => Err(ValidationError::UnknownRole(val)),
+        }
+    }
+}
+
+impl From<Role> for String {
+    fn from(val: Role) -> Self {
+        String::from(match val {
+            Role::Reader => "reader",
+            Role::Editor => "editor",
+            Role::Admin => "admin",
+        })
+    }
+}
+
+/// An API key as stored. The secret itself is never kept, only its SHA-256
+/// hash and its first characters, which tell keys apart in a listing.
+#[derive(Clone)]
+pub struct ApiKey {
+    pub name: KeyName,
+    pub role: Role,
+    pub prefix: String,
+    pub hash: String,
+    /// An RFC 3339 timestamp.
+    pub created_at: String,
+    pub revoked: bool,
+}
+
+impl ApiKey {
+    /// How many characters of the secret are kept as its prefix.
+    pub const PREFIX_LEN: usize = 8;
+
+    pub fn new(name: KeyName, role: Role, secret: &str, created_at: String) -> Self {
+        Self {
+            name,
+            role,
+            prefix: secret.chars().take(Self::PREFIX_LEN).collect(),
+            hash: Self::hash(secret),
+            created_at,
+            revoked: false,
+        }
+    }
+
+    /// The lowercase hexadecimal SHA-256 digest of a secret.
+    pub fn hash(secret: &str) -> String {
+        Sha256::digest(secret.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl ApiKey {
+    pub fn editor() -> Self {
+        Self::new(
+            KeyName(String::from("ci")),
+            Role::Editor,
+            "pdx_editor",
+            String::from("2024-01-01T00:00:00Z"),
+        )
+    }
+}
+
+/// Who makes a change, as recorded in the audit log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Actor {
+    /// The name of the API key, `anonymous` without one, or the OS user running
+    /// the CLI.
+    pub name: String,
+    /// The X-Request-Id of the API request making the change.
+    pub request_id: Option<String>,
+}
+
+impl Actor {
+    pub const ANONYMOUS: &'static str = "anonymous";
+    pub const CLI: &'static str = "cli";
+
+    pub fn new(name: String, request_id: Option<String>) -> Self {
+        Self { name, request_id }
+    }
+
+    /// The user logged in the shell, or `cli` when it does not tell.
+    pub fn cli() -> Self {
+        let user = ["USER", "USERNAME"]
+            .into_iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|user| !user.trim().is_empty());
+        Self::new(user.unwrap_or_else(|| String::from(Self::CLI)), None)
+    }
+}
+
+#[cfg(test)]
+impl Actor {
+    pub fn ci() -> Self {
+        Self::new(String::from("ci"), Some(String::from("req-1")))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AuditAction {
+    Insert,
+    Update,
+    /// Moved to the trash.
+    Delete,
+    /// Taken back out of the trash.
+    Restore,
+    /// Removed from the trash for good.
+    Purge,
+}
+
+impl AuditAction {
+    pub const ALL: [AuditAction; 5] = [
+        AuditAction::Insert,
+        AuditAction::Update,
+        AuditAction::Delete,
+        AuditAction::Restore,
+        AuditAction::Purge,
+    ];
+}
+
+impl TryFrom<String> for AuditAction {
+    type Error = String;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match AuditAction::ALL
+            .into_iter()
+            .find(|action| String::from(*action) == val)
+        {
+            Some(action) => Ok(action),
+            None => Err(val),
+        }
+    }
+}
+
+impl From<AuditAction> for String {
+    fn from(val: AuditAction) -> Self {
+        String::from(match val {
+            AuditAction::Insert => "insert",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Restore => "restore",
+            AuditAction::Purge => "purge",
+        })
+    }
+}
+
+/// A change of a form of a Pokemon, never modified once recorded.
+#[derive(Clone)]
+pub struct AuditEvent {
+    /// Increasing in the order of the changes, 0 until it is recorded.
+    pub id: u64,
+    pub action: AuditAction,
+    pub number: PokemonNumber,
+    pub form: FormSlug,
+    pub actor: String,
+    pub request_id: Option<String>,
+    /// An RFC 3339 timestamp, to the millisecond.
+    pub timestamp: String,
+    /// The form before the change, missing for an insert or a restore.
+    pub before: Option<Pokemon>,
+    /// The form after the change, missing for a delete or a purge.
+    pub after: Option<Pokemon>,
+}
+
+impl AuditEvent {
+    pub fn inserted(actor: &Actor, after: Pokemon) -> Self {
+        let (number, form) = (after.number.clone(), after.form.clone());
+        Self::new(AuditAction::Insert, actor, number, form, None, Some(after))
+    }
+
+    pub fn updated(actor: &Actor, before: Pokemon, after: Pokemon) -> Self {
+        let (number, form) = (after.number.clone(), after.form.clone());
+        Self::new(
+            AuditAction::Update,
+            actor,
+            number,
+            form,
+            Some(before),
+            Some(after),
+        )
+    }
+
+    pub fn deleted(actor: &Actor, before: Pokemon) -> Self {
+        let (number, form) = (before.number.clone(), before.form.clone());
+        Self::new(AuditAction::Delete, actor, number, form, Some(before), None)
+    }
+
+    pub fn restored(actor: &Actor, after: Pokemon) -> Self {
+        let (number, form) = (after.number.clone(), after.form.clone());
+        Self::new(AuditAction::Restore, actor, number, form, None, Some(after))
+    }
+
+    pub fn purged(actor: &Actor, before: Pokemon) -> Self {
+        let (number, form) = (before.number.clone(), before.form.clone());
+        Self::new(AuditAction::Purge, actor, number, form, Some(before), None)
+    }
+
+    pub fn with_id(self, id: u64) -> Self {
+        Self { id, ..self }
+    }
+
+    /// Dates the change, so that the forms changed together share a timestamp.
+    pub fn at(self, timestamp: String) -> Self {
+        Self { timestamp, ..self }
+    }
+
+    fn new(
+        action: AuditAction,
+        actor: &Actor,
+        number: PokemonNumber,
+        form: FormSlug,
+        before: Option<Pokemon>,
+        after: Option<Pokemon>,
+    ) -> Self {
+        Self {
+            id: 0,
+            action,
+            number,
+            form,
+            actor: actor.name.clone(),
+            request_id: actor.request_id.clone(),
+            timestamp: now(),
+            before,
+            after,
+        }
+    }
+}
+
+/// A form in the trash, hidden from every other listing until it is restored
+/// or purged.
+#[derive(Clone)]
+pub struct DeletedPokemon {
+    pub pokemon: Pokemon,
+    /// An RFC 3339 timestamp, to the millisecond, shared by the forms deleted
+    /// together.
+    pub deleted_at: String,
+}
+
+/// The current time as an RFC 3339 timestamp to the millisecond, which sorts
+/// chronologically as text.
+pub fn now() -> String {
+    humantime::format_rfc3339_millis(SystemTime::now()).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_should_accept_every_official_type() {
+        for t in PokemonType::ALL {
+            assert_eq!(PokemonType::try_from(String::from(t)), Ok(t));
+        }
+    }
+
+    #[test]
+    fn it_should_reject_an_unknown_type() {
+        assert_eq!(
+            PokemonType::try_from(String::from("Shadow")),
+            Err(ValidationError::UnknownType(String::from("Shadow")))
+        );
+    }
+
+    #[test]
+    fn it_should_reject_a_type_listed_twice() {
+        assert_eq!(
+            PokemonTypes::try_from(vec![String::from("Fire"), String::from("Fire")]).err(),
+            Some(ValidationError::DuplicateType(String::from("Fire")))
+        );
+    }
+
+    #[test]
+    fn it_should_describe_why_a_number_is_rejected() {
+        match PokemonNumber::try_from(899) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "the Pokemon number must be between 1 and 898, got 899"
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_reject_a_base_stat_out_of_range() {
+        assert_eq!(
+            BaseStats::try_from(StatValues::from([35, 55, 0, 50, 50, 90])),
+            Err(ValidationError::StatOutOfRange {
+                stat: Stat::Defense,
+                min: 1,
+                max: 255,
+                got: 0
+            })
+        );
+        assert!(BaseStats::try_from(StatValues::from([255, 1, 1, 1, 1, 1])).is_ok());
+    }
+
+    #[test]
+    fn it_should_reject_effort_values_above_the_total() {
+        assert_eq!(
+            EffortValues::try_from(StatValues::from([252, 252, 0, 0, 0, 8])),
+            Err(ValidationError::EffortValuesTotal { max: 510, got: 512 })
+        );
+        assert!(EffortValues::try_from(StatValues::from([252, 252, 0, 0, 0, 6])).is_ok());
+    }
+
+    #[test]
+    fn it_should_parse_a_nature_regardless_of_case() {
+        assert_eq!(
+            Nature::try_from(String::from("adamant")),
+            Ok(Nature::Adamant)
+        );
+        assert_eq!(Nature::Adamant.percentage(Stat::Attack), 110);
+        assert_eq!(Nature::Adamant.percentage(Stat::SpecialAttack), 90);
+        assert_eq!(Nature::Hardy.percentage(Stat::Attack), 100);
+    }
+
+    #[test]
+    fn it_should_order_the_abilities_by_slot() {
+        let abilities = PokemonAbilities::try_from(vec![
+            SlotValue {
+                slot: String::from("hidden"),
+                name: String::from("Lightning Rod"),
+            },
+            SlotValue {
+                slot: String::from("primary"),
+                name: String::from("Static"),
+            },
+        ]);
+
+        assert_eq!(abilities, Ok(PokemonAbilities::pikachu()));
+    }
+
+    #[test]
+    fn it_should_reject_two_abilities_in_the_same_slot() {
+        let abilities = PokemonAbilities::try_from(vec![
+            SlotValue {
+                slot: String::from("primary"),
+                name: String::from("Static"),
+            },
+            SlotValue {
+                slot: String::from("primary"),
+                name: String::from("Lightning Rod"),
+            },
+        ]);
+
+        assert_eq!(
+            abilities,
+            Err(ValidationError::DuplicateAbilitySlot(String::from(
+                "primary"
+            )))
+        );
+    }
+
+    #[test]
+    fn it_should_require_the_details_of_a_trigger() {
+        let trigger = |kind: &str, level, item: Option<&str>| {
+            EvolutionTrigger::try_from(TriggerValue {
+                kind: String::from(kind),
+                level,
+                item: item.map(String::from),
+            })
+        };
+
+        assert_eq!(
+            trigger("level_up", Some(16), None),
+            Ok(EvolutionTrigger::LevelUp(Level::try_from(16).unwrap()))
+        );
+        assert_eq!(
+            trigger("level_up", None, None),
+            Err(ValidationError::MissingTriggerDetail("level"))
+        );
+        assert_eq!(
+            trigger("item", None, Some(" ")),
+            Err(ValidationError::MissingTriggerDetail("item"))
+        );
+        assert_eq!(
+            trigger("moon", None, None),
+            Err(ValidationError::UnknownTrigger(String::from("moon")))
+        );
+    }
+
+    #[test]
+    fn it_should_only_keep_the_level_of_a_move_learnt_by_level_up() {
+        let entry = |method, level| {
+            LearnsetEntry::new(
+                MoveName::thunderbolt(),
+                method,
+                level,
+                Generation::try_from(8).unwrap(),
+            )
+            .map(|entry| entry.level)
+        };
+        let level = Level::try_from(26).ok();
+
+        assert_eq!(entry(LearnMethod::Level, level), Ok(level));
+        assert_eq!(entry(LearnMethod::Tm, level), Ok(None));
+        assert_eq!(
+            entry(LearnMethod::Level, None),
+            Err(ValidationError::MissingLevel)
+        );
+    }
+
+    #[test]
+    fn it_should_only_accept_a_form_in_kebab_case() {
+        for form in ["alola", "mega-x", "10-percent"] {
+            assert!(FormSlug::try_from(String::from(form)).is_ok());
+        }
+
+        for form in ["", "Alola", "mega x", "mega--x", "-alola"] {
+            assert_eq!(
+                FormSlug::try_from(String::from(form)),
+                Err(ValidationError::InvalidForm(String::from(form)))
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_multiplier_against_a_single_type() {
+        assert_eq!(
+            PokemonType::Water.effectiveness_against(&PokemonType::Fire),
+            2.0
+        );
+        assert_eq!(
+            PokemonType::Fire.effectiveness_against(&PokemonType::Water),
+            0.5
+        );
+        assert_eq!(
+            PokemonType::Normal.effectiveness_against(&PokemonType::Psychic),
+            1.0
+        );
+    }
+
+    #[test]
+    fn it_should_return_zero_against_an_immune_type() {
+        assert_eq!(
+            PokemonType::Electric.effectiveness_against(&PokemonType::Ground),
+            0.0
+        );
+        assert_eq!(
+            PokemonType::Dragon.effectiveness_against(&PokemonType::Fairy),
+            0.0
+        );
+    }
+
+    #[test]
+    fn it_should_multiply_the_effectiveness_against_a_dual_type() {
+        let charizard =
+            PokemonTypes::try_from(vec![String::from("Fire"), String::from("Flying")]).unwrap();
+        let gyarados =
+            PokemonTypes::try_from(vec![String::from("Water"), String::from("Flying")]).unwrap();
+        let gliscor =
+            PokemonTypes::try_from(vec![String::from("Ground"), String::from("Flying")]).unwrap();
+
+        assert_eq!(charizard.effectiveness_of(&PokemonType::Rock), 4.0);
+        assert_eq!(gyarados.effectiveness_of(&PokemonType::Electric), 4.0);
+        assert_eq!(gliscor.effectiveness_of(&PokemonType::Electric), 0.0);
+        assert_eq!(charizard.effectiveness_of(&PokemonType::Grass), 0.25);
+    }
+}
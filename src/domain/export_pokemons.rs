@@ -0,0 +1,472 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{
+    FieldError, Pokemon, PokemonNumber, SlotValue, StatValues, ValidationError,
+};
+use crate::domain::import_pokemons::{JsonRecord, CSV_COLUMNS};
+use crate::repositories::pokemon::{FetchAllQuery, Repository, RetrieveAllError, Source};
+
+/// How many Pokemons are read from the repository at once.
+pub const PAGE_SIZE: u32 = 100;
+
+pub struct Request {
+    pub format: String,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Json,
+    Jsonl,
+    Csv,
+    Sql,
+}
+
+enum Step {
+    Header,
+    Page(Option<PokemonNumber>),
+    Footer,
+    Done,
+}
+
+/// The export as a sequence of text chunks, built lazily a page of Pokemons
+/// at a time so that it can be streamed. Every form of every Pokemon is
+/// written, ordered by number then with the default form first, which is the
+/// order an import needs.
+pub struct Export {
+    repo: Arc<dyn Repository>,
+    format: Format,
+    step: Step,
+    written: usize,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Export, Error> {
+    let format = match req.format.as_str() {
+        "json" => Format::Json,
+        "jsonl" => Format::Jsonl,
+        "csv" => Format::Csv,
+        "sql" => Format::Sql,
+        format => {
+            return Err(Error::BadRequest(vec![FieldError::new(
+                "format",
+                ValidationError::UnknownExportFormat(String::from(format)),
+            )]))
+        }
+    };
+
+    Ok(Export {
+        repo,
+        format,
+        step: Step::Header,
+        written: 0,
+    })
+}
+
+impl Iterator for Export {
+    type Item = Result<String, Source>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match std::mem::replace(&mut self.step, Step::Done) {
+            Step::Header => match self.header() {
+                Ok(chunk) => {
+                    self.step = Step::Page(None);
+                    Some(Ok(chunk))
+                }
+                Err(RetrieveAllError::Unknown(e)) => Some(Err(e)),
+            },
+            Step::Page(min_number) => match self.page(min_number) {
+                Ok(chunk) => Some(Ok(chunk)),
+                Err(RetrieveAllError::Unknown(e)) => {
+                    self.step = Step::Done;
+                    Some(Err(e))
+                }
+            },
+            Step::Footer => Some(Ok(self.footer())),
+            Step::Done => None,
+        }
+    }
+}
+
+impl Export {
+    /// The SQL dump starts with the abilities catalogue, which the Pokemons
+    /// reference. The other formats leave it to the import to fill it in.
+    fn header(&self) -> Result<String, RetrieveAllError> {
+        Ok(match self.format {
+            Format::Json => String::from("["),
+            Format::Jsonl => String::new(),
+            Format::Csv => format!("{}\n", CSV_COLUMNS.join(",")),
+            Format::Sql => {
+                let mut out = String::from(
+                    "-- Load into a migrated database, e.g. sqlite3 pokedex.db < pokedex.sql\nbegin;\n",
+                );
+                for ability in self.repo.fetch_abilities()? {
+                    out.push_str(&format!(
+                        "insert or ignore into abilities (name, description) values ({}, {});\n",
+                        sql_string(&String::from(ability.name)),
+                        sql_string(&ability.description)
+                    ));
+                }
+                out
+            }
+        })
+    }
+
+    fn footer(&self) -> String {
+        match self.format {
+            Format::Json => String::from("\n]\n"),
+            Format::Jsonl | Format::Csv => String::new(),
+            Format::Sql => String::from("commit;\n"),
+        }
+    }
+
+    /// Writes the Pokemons numbered from `min_number`, then moves on to the
+    /// next page, or to the footer after the last one. A failure ends the export.
+    fn page(&mut self, min_number: Option<PokemonNumber>) -> Result<String, RetrieveAllError> {
+        let query = FetchAllQuery {
+            limit: Some(PAGE_SIZE),
+            min_number,
+            ..FetchAllQuery::default()
+        };
+        let pokemons = self.repo.fetch_all(query)?;
+
+        self.step = match pokemons.last() {
+            Some(last) if pokemons.len() == PAGE_SIZE as usize => {
+                match PokemonNumber::try_from(u16::from(last.number.clone()) + 1) {
+                    Ok(next) => Step::Page(Some(next)),
+                    Err(_) => Step::Footer,
+                }
+            }
+            _ => Step::Footer,
+        };
+
+        let mut chunk = String::new();
+        for pokemon in pokemons {
+            for form in self.repo.fetch_forms(pokemon.number)? {
+                chunk.push_str(&self.entry(form));
+                self.written += 1;
+            }
+        }
+
+        Ok(chunk)
+    }
+
+    fn entry(&self, pokemon: Pokemon) -> String {
+        match self.format {
+            Format::Json => format!(
+                "{}\n  {}",
+                if self.written == 0 { "" } else { "," },
+                serde_json::to_string(&JsonRecord::from(pokemon)).unwrap_or_default()
+            ),
+            Format::Jsonl => format!(
+                "{}\n",
+                serde_json::to_string(&JsonRecord::from(pokemon)).unwrap_or_default()
+            ),
+            Format::Csv => csv_row(pokemon),
+            Format::Sql => sql_statements(pokemon),
+        }
+    }
+}
+
+/// Quotes a CSV field when it contains a separator, a quote or a line break.
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        String::from(value)
+    }
+}
+
+/// Joins the items of a list held in a single CSV field with `;`, escaping a
+/// `;` or a `\` inside an item with a `\`.
+fn csv_list(items: impl IntoIterator<Item = String>) -> String {
+    items
+        .into_iter()
+        .map(|item| item.replace('\\', "\\\\").replace(';', "\\;"))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn csv_row(pokemon: Pokemon) -> String {
+    let stats = match pokemon.stats.map(StatValues::from) {
+        Some(values) => [
+            values.hp,
+            values.attack,
+            values.defense,
+            values.special_attack,
+            values.special_defense,
+            values.speed,
+        ]
+        .map(|value| value.to_string()),
+        None => Default::default(),
+    };
+    let abilities = Vec::<SlotValue>::from(pokemon.abilities)
+        .into_iter()
+        .map(|a| format!("{}:{}", a.slot, a.name));
+
+    format!(
+        "{},{},{},{},{},{}\n",
+        u16::from(pokemon.number),
+        String::from(pokemon.form),
+        csv_field(&String::from(pokemon.name)),
+        csv_field(&csv_list(Vec::<String>::from(pokemon.types))),
+        stats.join(","),
+        csv_field(&csv_list(abilities)),
+    )
+}
+
+fn sql_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// The statements restoring a single form, the same way the SQLite repository
+/// stores it.
+fn sql_statements(pokemon: Pokemon) -> String {
+    let number = u16::from(pokemon.number.clone());
+    let form = sql_string(&String::from(pokemon.form.clone()));
+    let stats = match pokemon.stats.map(StatValues::from) {
+        Some(values) => [
+            values.hp,
+            values.attack,
+            values.defense,
+            values.special_attack,
+            values.special_defense,
+            values.speed,
+        ]
+        .map(|value| value.to_string()),
+        None => [(); 6].map(|_| String::from("null")),
+    }
+    .join(", ");
+    let name = sql_string(&String::from(pokemon.name.clone()));
+
+    let mut out = if pokemon.form.is_default() {
+        format!(
+            "insert into pokemons (number, name, hp, attack, defense, special_attack, special_defense, speed) values ({}, {}, {});\n",
+            number, name, stats
+        )
+    } else {
+        format!(
+            "insert into forms (pokemon_number, form, name, hp, attack, defense, special_attack, special_defense, speed) values ({}, {}, {}, {});\n",
+            number, form, name, stats
+        )
+    };
+
+    for name in Vec::<String>::from(pokemon.types) {
+        out.push_str(&format!(
+            "insert into types (pokemon_number, form, name) values ({}, {}, {});\n",
+            number,
+            form,
+            sql_string(&name)
+        ));
+    }
+
+    for value in Vec::<SlotValue>::from(pokemon.abilities) {
+        out.push_str(&format!(
+            "insert into pokemon_abilities (pokemon_number, form, slot, ability_name) values ({}, {}, {}, {});\n",
+            number,
+            form,
+            sql_string(&value.slot),
+            sql_string(&value.name)
+        ));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{
+        Ability, AbilityName, Actor, BaseStats, FormSlug, PokemonAbilities, PokemonName,
+        PokemonTypes,
+    };
+    use crate::domain::import_pokemons;
+    use crate::repositories::pokemon::{InMemoryRepository, SqliteRepository};
+
+    fn dex() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert_ability(Ability::new(
+            AbilityName::static_(),
+            String::from("May paralyze on contact."),
+        ));
+        let _ = repo.insert_ability(Ability::new(
+            AbilityName::lightning_rod(),
+            String::from("Draws in Electric-type moves."),
+        ));
+        let _ = repo.insert(
+            Pokemon::pikachu()
+                .with_stats(Some(BaseStats::pikachu()))
+                .with_abilities(PokemonAbilities::pikachu()),
+            &Actor::ci(),
+        );
+        let _ = repo.insert(
+            Pokemon::pikachu().with_form(FormSlug::alola()),
+            &Actor::ci(),
+        );
+        let _ = repo.insert(Pokemon::charmander(), &Actor::ci());
+        repo
+    }
+
+    fn export(repo: Arc<dyn Repository>, format: &str) -> String {
+        match execute(
+            repo,
+            Request {
+                format: String::from(format),
+            },
+        ) {
+            Ok(export) => export.collect::<Result<String, _>>().unwrap_or_default(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_write_every_form_ordered_by_number() {
+        assert_eq!(
+            export(dex(), "csv"),
+            "number,form,name,types,hp,attack,defense,special_attack,special_defense,speed,abilities\n\
+             4,default,Charmander,Fire,,,,,,,\n\
+             25,default,Pikachu,Electric,35,55,40,50,50,90,primary:Static;hidden:Lightning Rod\n\
+             25,alola,Pikachu,Electric,,,,,,,\n"
+        );
+    }
+
+    #[test]
+    fn it_should_write_a_json_array_with_one_pokemon_per_line() {
+        let json = export(dex(), "json");
+
+        assert_eq!(json.lines().count(), 5);
+        match serde_json::from_str::<Vec<serde_json::Value>>(&json) {
+            Ok(items) => assert_eq!(items.len(), 3),
+            _ => unreachable!(),
+        }
+        assert_eq!(
+            export(Arc::new(InMemoryRepository::new()), "json"),
+            "[\n]\n"
+        );
+    }
+
+    #[test]
+    fn it_should_round_trip_through_an_import() {
+        for format in ["json", "jsonl", "csv"] {
+            let repos: [Arc<dyn Repository>; 2] = [
+                Arc::new(InMemoryRepository::new()),
+                Arc::new(SqliteRepository::try_new(":memory:").ok().unwrap()),
+            ];
+            for repo in repos {
+                let req = import_pokemons::Request {
+                    format: String::from(format),
+                    on_conflict: String::from("fail"),
+                    content: export(dex(), format),
+                };
+
+                match import_pokemons::execute(repo.clone(), req, &Actor::ci()) {
+                    Ok(res) => {
+                        assert_eq!(res.inserted, 3);
+                        assert!(res.rejected.is_empty());
+                    }
+                    _ => unreachable!(),
+                }
+                assert_eq!(export(repo, format), export(dex(), format));
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_round_trip_a_csv_with_line_breaks_and_separators_in_the_names() {
+        let dex = Arc::new(InMemoryRepository::new());
+        let _ = dex.insert_ability(Ability::new(
+            AbilityName::try_from(String::from("Static; Shock\\")).unwrap(),
+            String::from("May paralyze on contact."),
+        ));
+        let _ = dex.insert(
+            Pokemon::new(
+                PokemonNumber::try_from(122).unwrap(),
+                PokemonName::try_from(String::from("Mr.\r\n\"Mime\", Jr")).unwrap(),
+                PokemonTypes::pikachu(),
+            )
+            .with_abilities(
+                PokemonAbilities::try_from(vec![SlotValue {
+                    slot: String::from("primary"),
+                    name: String::from("Static; Shock\\"),
+                }])
+                .unwrap(),
+            ),
+            &Actor::ci(),
+        );
+        let _ = dex.insert(Pokemon::pikachu(), &Actor::ci());
+        let csv = export(dex.clone(), "csv");
+
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = import_pokemons::Request {
+            format: String::from("csv"),
+            on_conflict: String::from("fail"),
+            content: csv.clone(),
+        };
+
+        match import_pokemons::execute(repo.clone(), req, &Actor::ci()) {
+            Ok(res) => {
+                assert_eq!(res.inserted, 2);
+                assert!(res.rejected.is_empty());
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(export(repo, "csv"), csv);
+    }
+
+    #[test]
+    fn it_should_load_the_sql_dump_with_its_abilities() {
+        let repo = Arc::new(SqliteRepository::try_new(":memory:").ok().unwrap());
+
+        assert!(repo.load(&export(dex(), "sql")).is_ok());
+        assert_eq!(export(repo, "sql"), export(dex(), "sql"));
+    }
+
+    #[test]
+    fn it_should_read_the_repository_a_page_at_a_time() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for number in 1..=(PAGE_SIZE as u16 * 2 + 1) {
+            let _ = repo.insert(
+                Pokemon::new(
+                    PokemonNumber::try_from(number).unwrap(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::pikachu(),
+                ),
+                &Actor::ci(),
+            );
+        }
+
+        match execute(
+            repo,
+            Request {
+                format: String::from("jsonl"),
+            },
+        ) {
+            // The header, three pages and the footer.
+            Ok(export) => assert_eq!(export.count(), 5),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_escape_the_sql_strings() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(
+            Pokemon::new(
+                PokemonNumber::try_from(83).unwrap(),
+                PokemonName::try_from(String::from("Farfetch'd")).unwrap(),
+                PokemonTypes::try_from(vec![String::from("Normal"), String::from("Flying")])
+                    .unwrap(),
+            ),
+            &Actor::ci(),
+        );
+
+        let sql = export(repo, "sql");
+
+        assert!(sql.contains(
+            "insert into pokemons (number, name, hp, attack, defense, special_attack, special_defense, speed) values (83, 'Farfetch''d', null, null, null, null, null, null);\n"
+        ));
+        assert!(sql.ends_with("insert into types (pokemon_number, form, name) values (83, 'default', 'Flying');\ncommit;\n"));
+    }
+}
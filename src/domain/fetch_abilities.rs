@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use crate::domain::create_ability::AbilityResponse;
+use crate::repositories::pokemon::{Repository, RetrieveAllError, Source};
+
+pub enum Error {
+    Unknown(Source),
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<AbilityResponse>, Error> {
+    match repo.fetch_abilities() {
+        Ok(abilities) => Ok(abilities
+            .into_iter()
+            .map(|ability| AbilityResponse {
+                name: String::from(ability.name),
+                description: ability.description,
+            })
+            .collect()),
+        Err(RetrieveAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{AbilityName, FieldError};
+use crate::repositories::pokemon::{Repository, RetrieveAllError, RetrieveError, Source};
+
+pub struct Request {
+    pub name: String,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown(Source),
+}
+
+pub struct AbilityPokemonResponse {
+    pub number: u16,
+    pub form: String,
+    pub name: String,
+    pub slot: String,
+}
+
+/// Lists every form holding the ability, the alternate ones included.
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<Vec<AbilityPokemonResponse>, Error> {
+    let name = match AbilityName::try_from(req.name) {
+        Ok(name) => name,
+        Err(e) => return Err(Error::BadRequest(vec![FieldError::new("name", e)])),
+    };
+
+    let ability = match repo.fetch_ability(name) {
+        Ok(ability) => ability,
+        Err(RetrieveError::NotFound) => return Err(Error::NotFound),
+        Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+    };
+
+    match repo.fetch_ability_holders(ability.name.clone()) {
+        Ok(pokemons) => Ok(pokemons
+            .into_iter()
+            .filter_map(|p| {
+                p.abilities
+                    .contains(&ability.name)
+                    .map(|slot| AbilityPokemonResponse {
+                        number: u16::from(p.number),
+                        form: String::from(p.form),
+                        name: String::from(p.name),
+                        slot: String::from(slot),
+                    })
+            })
+            .collect()),
+        Err(RetrieveAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{
+        Ability, AbilityName, Actor, FormSlug, Pokemon, PokemonAbilities,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn seed() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        for name in [AbilityName::static_(), AbilityName::lightning_rod()] {
+            let _ = repo.insert_ability(Ability::new(name, String::new()));
+        }
+        let _ = repo.insert(
+            Pokemon::pikachu().with_abilities(PokemonAbilities::pikachu()),
+            &Actor::ci(),
+        );
+        let _ = repo.insert(Pokemon::charmander(), &Actor::ci());
+        let _ = repo.insert(
+            Pokemon::pikachu()
+                .with_form(FormSlug::alola())
+                .with_abilities(PokemonAbilities::pikachu()),
+            &Actor::ci(),
+        );
+        repo
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_having_the_ability_with_their_slot() {
+        let req = Request {
+            name: String::from("Lightning Rod"),
+        };
+
+        match execute(seed(), req) {
+            Ok(res) => {
+                assert_eq!(
+                    res.iter()
+                        .map(|p| (p.number, p.form.as_str(), p.slot.as_str()))
+                        .collect::<Vec<_>>(),
+                    vec![(25, "default", "hidden"), (25, "alola", "hidden")]
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_ability_does_not_exist() {
+        let req = Request {
+            name: String::from("Levitate"),
+        };
+
+        match execute(seed(), req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,28 +1,190 @@
 use std::sync::Arc;
 
-use crate::repositories::pokemon::{Repository, RetrieveAllError};
+use crate::domain::entities::{
+    FieldError, PokemonNumber, PokemonType, SlotValue, StatValues, ValidationError,
+};
+use crate::repositories::pokemon::{
+    FetchAllQuery, Repository, RetrieveAllError, SortBy, SortOrder, Source,
+};
+
+pub const MAX_LIMIT: u32 = 1000;
+
+#[derive(Default)]
+pub struct Request {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub cursor: Option<String>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub pokemon_type: Option<String>,
+    pub min_number: Option<u16>,
+    pub max_number: Option<u16>,
+}
 
 pub enum Error {
-    Unknown,
+    BadRequest(Vec<FieldError>),
+    Unknown(Source),
 }
 
 pub struct RetrieveAllResponse {
     pub number: u16,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<StatValues>,
+    pub abilities: Vec<SlotValue>,
+}
+
+pub struct RetrieveAllPage {
+    pub pokemons: Vec<RetrieveAllResponse>,
+    pub next_cursor: Option<String>,
 }
 
-pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<RetrieveAllResponse>, Error> {
-    match repo.fetch_all() {
-        Ok(pokemons) => Ok(pokemons
-            .into_iter()
-            .map(|p| RetrieveAllResponse {
-                number: u16::from(p.number),
-                name: String::from(p.name),
-                types: Vec::<String>::from(p.types),
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<RetrieveAllPage, Error> {
+    let query = match FetchAllQuery::try_from(req) {
+        Ok(query) => query,
+        Err(e) => return Err(Error::BadRequest(vec![e])),
+    };
+
+    // Ask for one extra row to find out whether there is a next page.
+    let limit = query.limit;
+    let offset = query.offset;
+    let query = FetchAllQuery {
+        limit: limit.map(|limit| limit + 1),
+        ..query
+    };
+
+    match repo.fetch_all(query) {
+        Ok(mut pokemons) => {
+            let next_cursor = match limit {
+                Some(limit) if pokemons.len() > limit as usize => {
+                    pokemons.truncate(limit as usize);
+                    Some(encode_cursor(offset + limit))
+                }
+                _ => None,
+            };
+
+            Ok(RetrieveAllPage {
+                pokemons: pokemons
+                    .into_iter()
+                    .map(|p| RetrieveAllResponse {
+                        number: u16::from(p.number),
+                        name: String::from(p.name),
+                        types: Vec::<String>::from(p.types),
+                        stats: p.stats.map(StatValues::from),
+                        abilities: Vec::<SlotValue>::from(p.abilities),
+                    })
+                    .collect(),
+                next_cursor,
             })
-            .collect()),
-        Err(RetrieveAllError::Unknown) => Err(Error::Unknown),
+        }
+        Err(RetrieveAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+impl TryFrom<Request> for FetchAllQuery {
+    type Error = FieldError;
+
+    fn try_from(req: Request) -> Result<Self, Self::Error> {
+        let limit = match req.limit {
+            Some(limit) if limit == 0 || limit > MAX_LIMIT => {
+                return Err(FieldError::new(
+                    "limit",
+                    ValidationError::LimitOutOfRange {
+                        min: 1,
+                        max: MAX_LIMIT,
+                        got: limit,
+                    },
+                ))
+            }
+            limit => limit,
+        };
+
+        let offset = match (req.offset, req.cursor) {
+            (Some(_), Some(_)) => {
+                return Err(FieldError::new("cursor", ValidationError::CursorWithOffset))
+            }
+            (Some(offset), None) => offset,
+            (None, Some(cursor)) => match decode_cursor(&cursor) {
+                Ok(offset) => offset,
+                _ => return Err(FieldError::new("cursor", ValidationError::InvalidCursor)),
+            },
+            (None, None) => 0,
+        };
+
+        let sort_by = match req.sort.as_deref() {
+            None | Some("number") => SortBy::Number,
+            Some("name") => SortBy::Name,
+            Some(sort) => {
+                return Err(FieldError::new(
+                    "sort",
+                    ValidationError::UnknownSortField(String::from(sort)),
+                ))
+            }
+        };
+
+        let order = match req.order.as_deref() {
+            None | Some("asc") => SortOrder::Asc,
+            Some("desc") => SortOrder::Desc,
+            Some(order) => {
+                return Err(FieldError::new(
+                    "order",
+                    ValidationError::UnknownSortOrder(String::from(order)),
+                ))
+            }
+        };
+
+        let pokemon_type = req
+            .pokemon_type
+            .map(PokemonType::try_from)
+            .transpose()
+            .map_err(|e| FieldError::new("type", e))?;
+        let min_number = req
+            .min_number
+            .map(PokemonNumber::try_from)
+            .transpose()
+            .map_err(|e| FieldError::new("min_number", e))?;
+        let max_number = req
+            .max_number
+            .map(PokemonNumber::try_from)
+            .transpose()
+            .map_err(|e| FieldError::new("max_number", e))?;
+
+        Ok(Self {
+            limit,
+            offset,
+            sort_by,
+            order,
+            pokemon_type,
+            min_number,
+            max_number,
+        })
+    }
+}
+
+/// Cursors are opaque to clients; they only carry the offset of the next page.
+fn encode_cursor(offset: u32) -> String {
+    format!("offset:{}", offset)
+        .bytes()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn decode_cursor(cursor: &str) -> Result<u32, ()> {
+    if !cursor.len().is_multiple_of(2) {
+        return Err(());
+    }
+
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(cursor.get(i..i + 2).ok_or(())?, 16).map_err(|_| ()))
+        .collect::<Result<Vec<u8>, ()>>()?;
+
+    match String::from_utf8(bytes) {
+        Ok(decoded) => match decoded.strip_prefix("offset:") {
+            Some(offset) => offset.parse::<u32>().map_err(|_| ()),
+            None => Err(()),
+        },
+        _ => Err(()),
     }
 }
 
@@ -31,7 +193,7 @@ mod test {
 
     use super::*;
     use crate::{
-        domain::entities::{PokemonName, PokemonNumber, PokemonTypes},
+        domain::entities::{Actor, Pokemon, PokemonName, PokemonNumber, PokemonTypes},
         repositories::pokemon::InMemoryRepository,
     };
 
@@ -39,10 +201,10 @@ mod test {
     fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
 
-        let res = execute(repo);
+        let res = execute(repo, Request::default());
 
         match res {
-            Err(Error::Unknown) => {}
+            Err(Error::Unknown(_)) => {}
             _ => unreachable!(),
         }
     }
@@ -51,25 +213,16 @@ mod test {
     fn it_should_return_all_the_pokemons_ordered_by_increased_number_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
 
-        let _ = repo
-            .insert(
-                PokemonNumber::pikachu(),
-                PokemonName::pikachu(),
-                PokemonTypes::pikachu(),
-            )
-            .ok();
-        let _ = repo
-            .insert(
-                PokemonNumber::charmander(),
-                PokemonName::charmader(),
-                PokemonTypes::charmander(),
-            )
-            .ok();
-
-        let res = execute(repo);
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci()).ok();
+        let _ = repo.insert(Pokemon::charmander(), &Actor::ci()).ok();
+
+        let res = execute(repo, Request::default());
 
         match res {
-            Ok(res) => {
+            Ok(RetrieveAllPage {
+                pokemons: res,
+                next_cursor: None,
+            }) => {
                 assert_eq!(res[0].number, u16::from(PokemonNumber::charmander()));
                 assert_eq!(res[0].name, String::from(PokemonName::charmader()));
                 assert_eq!(
@@ -80,7 +233,120 @@ mod test {
                 assert_eq!(res[1].name, String::from(PokemonName::pikachu()));
                 assert_eq!(res[1].types, Vec::<String>::from(PokemonTypes::pikachu()));
             }
-            Err(_) => unreachable!(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn seed() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(Pokemon::charmander(), &Actor::ci());
+        repo
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_query_is_invalid() {
+        let requests = vec![
+            Request {
+                limit: Some(0),
+                ..Request::default()
+            },
+            Request {
+                sort: Some(String::from("weight")),
+                ..Request::default()
+            },
+            Request {
+                pokemon_type: Some(String::from("Shadow")),
+                ..Request::default()
+            },
+            Request {
+                cursor: Some(String::from("not-a-cursor")),
+                ..Request::default()
+            },
+        ];
+
+        for req in requests {
+            match execute(seed(), req) {
+                Err(Error::BadRequest(_)) => {}
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_filter_the_pokemons_by_type() {
+        let req = Request {
+            pokemon_type: Some(String::from("Fire")),
+            ..Request::default()
+        };
+
+        match execute(seed(), req) {
+            Ok(page) => {
+                assert_eq!(page.pokemons.len(), 1);
+                assert_eq!(
+                    page.pokemons[0].name,
+                    String::from(PokemonName::charmader())
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_sort_the_pokemons_by_name_in_descending_order() {
+        let req = Request {
+            sort: Some(String::from("name")),
+            order: Some(String::from("desc")),
+            ..Request::default()
+        };
+
+        match execute(seed(), req) {
+            Ok(page) => {
+                assert_eq!(page.pokemons[0].name, String::from(PokemonName::pikachu()));
+                assert_eq!(
+                    page.pokemons[1].name,
+                    String::from(PokemonName::charmader())
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_paginate_the_pokemons_with_a_cursor() {
+        let repo = seed();
+        let req = Request {
+            limit: Some(1),
+            ..Request::default()
+        };
+
+        let cursor = match execute(repo.clone(), req) {
+            Ok(RetrieveAllPage {
+                pokemons,
+                next_cursor: Some(cursor),
+            }) => {
+                assert_eq!(pokemons.len(), 1);
+                assert_eq!(pokemons[0].number, u16::from(PokemonNumber::charmander()));
+                cursor
+            }
+            _ => unreachable!(),
+        };
+
+        let req = Request {
+            limit: Some(1),
+            cursor: Some(cursor),
+            ..Request::default()
+        };
+
+        match execute(repo, req) {
+            Ok(RetrieveAllPage {
+                pokemons,
+                next_cursor: None,
+            }) => {
+                assert_eq!(pokemons.len(), 1);
+                assert_eq!(pokemons[0].number, u16::from(PokemonNumber::pikachu()));
+            }
+            _ => unreachable!(),
         }
     }
 }
@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use crate::domain::create_api_key::ApiKeyResponse;
+use crate::repositories::pokemon::{Repository, RetrieveAllError, Source};
+
+pub enum Error {
+    Unknown(Source),
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<ApiKeyResponse>, Error> {
+    match repo.fetch_keys() {
+        Ok(keys) => Ok(keys.into_iter().map(ApiKeyResponse::from).collect()),
+        Err(RetrieveAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use crate::domain::entities::{FieldError, ValidationError};
+use crate::domain::fetch_history::AuditEventResponse;
+use crate::repositories::pokemon::{Repository, RetrieveAllError, Source};
+
+pub const MAX_LIMIT: u32 = 1000;
+
+pub struct Request {
+    /// An RFC 3339 timestamp, the seconds being optional, and the offset too
+    /// for a time in UTC.
+    pub since: Option<String>,
+    pub limit: u32,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    Unknown(Source),
+}
+
+/// Returns the changes made to every Pokemon since a moment, the oldest first.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<AuditEventResponse>, Error> {
+    // Stored timestamps are compared as text, so they must be written alike.
+    let since = req.since.as_deref().map(|since| {
+        parse_timestamp(since)
+            .map(|time| humantime::format_rfc3339_millis(time).to_string())
+            .ok_or_else(|| {
+                FieldError::new(
+                    "since",
+                    ValidationError::InvalidTimestamp(String::from(since)),
+                )
+            })
+    });
+    let errors = [
+        since
+            .as_ref()
+            .and_then(|since| since.as_ref().err().cloned()),
+        (req.limit == 0 || req.limit > MAX_LIMIT).then(|| {
+            FieldError::new(
+                "limit",
+                ValidationError::LimitOutOfRange {
+                    min: 1,
+                    max: MAX_LIMIT,
+                    got: req.limit,
+                },
+            )
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(Error::BadRequest(errors));
+    }
+
+    match repo.fetch_audit(since.and_then(Result::ok), req.limit) {
+        Ok(events) => Ok(events.into_iter().map(AuditEventResponse::from).collect()),
+        Err(RetrieveAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+/// Reads a timestamp the way humantime does, which is only in UTC, then applies
+/// its offset. A `+` left unencoded in a query string is read as a space, and
+/// is taken for a `+`.
+fn parse_timestamp(since: &str) -> Option<SystemTime> {
+    let since = since.trim();
+    // Past the date, a sign only starts the offset.
+    let (time, offset) = match since.rfind(['+', '-', ' ']) {
+        Some(at) if at > "YYYY-MM-DD".len() => (&since[..at], Some(&since[at..])),
+        _ => (since, None),
+    };
+    let time = humantime::parse_rfc3339_weak(time).ok()?;
+    let Some(offset) = offset else {
+        return Some(time);
+    };
+
+    let (hours, minutes) = offset[1..].split_once(':')?;
+    let digits = |s: &str| s.len() == 2 && s.bytes().all(|b| b.is_ascii_digit());
+    if !digits(hours) || !digits(minutes) {
+        return None;
+    }
+    let (hours, minutes) = (hours.parse::<u64>().ok()?, minutes.parse::<u64>().ok()?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+
+    let shift = Duration::from_secs(hours * 3600 + minutes * 60);
+    if offset.starts_with('-') {
+        time.checked_add(shift)
+    } else {
+        time.checked_sub(shift)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{Actor, Pokemon};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_the_changes_since_a_moment() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(Pokemon::charmander(), &Actor::ci());
+
+        match execute(
+            repo.clone(),
+            Request {
+                since: Some(String::from("2000-01-01T00:00:00Z")),
+                limit: 1,
+            },
+        ) {
+            Ok(events) => {
+                assert_eq!(events.len(), 1);
+                assert_eq!(events[0].number, 25);
+            }
+            _ => unreachable!(),
+        }
+
+        match execute(
+            repo,
+            Request {
+                since: Some(String::from("2999-01-01T00:00:00Z")),
+                limit: MAX_LIMIT,
+            },
+        ) {
+            Ok(events) => assert!(events.is_empty()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_read_a_timestamp_with_an_offset() {
+        let utc = parse_timestamp("2026-10-18T01:03:49Z");
+
+        assert!(utc.is_some());
+        assert_eq!(parse_timestamp("2026-10-18T03:03:49+02:00"), utc);
+        assert_eq!(parse_timestamp("2026-10-18T03:03:49 02:00"), utc);
+        assert_eq!(parse_timestamp("2026-10-17T23:33:49-01:30"), utc);
+        assert_eq!(parse_timestamp("2026-10-18 01:03:49"), utc);
+        assert_eq!(parse_timestamp("2026-10-18T03:03:49+24:00"), None);
+        assert_eq!(parse_timestamp("2026-10-18T03:03:49+2"), None);
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            since: Some(String::from("yesterday")),
+            limit: 0,
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(
+                    errors.iter().map(|e| e.field).collect::<Vec<_>>(),
+                    vec!["since", "limit"]
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{Evolution, FieldError, FormSlug, PokemonNumber, TriggerValue};
+use crate::repositories::pokemon::{Repository, RetrieveAllError, RetrieveError, Source};
+
+pub struct Request {
+    pub number: u16,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown(Source),
+}
+
+/// A Pokemon of a chain along with everything it evolves into. `trigger` is
+/// what makes its pre-evolution evolve into it, `None` at the root.
+pub struct EvolutionNode {
+    pub number: u16,
+    pub name: String,
+    pub trigger: Option<TriggerValue>,
+    pub evolves_to: Vec<EvolutionNode>,
+}
+
+/// Returns the whole chain the Pokemon belongs to, from its root.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<EvolutionNode, Error> {
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => number,
+        Err(e) => return Err(Error::BadRequest(vec![FieldError::new("number", e)])),
+    };
+
+    match repo.fetch_one(number.clone(), FormSlug::default()) {
+        Ok(_) => {}
+        Err(RetrieveError::NotFound) => return Err(Error::NotFound),
+        Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+
+    let evolutions = match repo.fetch_evolutions() {
+        Ok(evolutions) => evolutions,
+        Err(RetrieveAllError::Unknown(e)) => return Err(Error::Unknown(e)),
+    };
+
+    let mut root = number;
+    for _ in 0..evolutions.len() {
+        match evolutions.iter().find(|e| e.to == root) {
+            Some(evolution) => root = evolution.from.clone(),
+            None => break,
+        }
+    }
+
+    node(repo.as_ref(), &evolutions, root, None, evolutions.len())
+}
+
+/// `depth` bounds the recursion, a valid chain never being deeper than its
+/// number of edges.
+fn node(
+    repo: &dyn Repository,
+    evolutions: &[Evolution],
+    number: PokemonNumber,
+    trigger: Option<TriggerValue>,
+    depth: usize,
+) -> Result<EvolutionNode, Error> {
+    let pokemon = match repo.fetch_one(number.clone(), FormSlug::default()) {
+        Ok(pokemon) => pokemon,
+        Err(RetrieveError::NotFound) => return Err(Error::NotFound),
+        Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+    };
+
+    let mut evolves_to = vec![];
+    if depth > 0 {
+        for evolution in evolutions.iter().filter(|e| e.from == number) {
+            evolves_to.push(node(
+                repo,
+                evolutions,
+                evolution.to.clone(),
+                Some(TriggerValue::from(evolution.trigger.clone())),
+                depth - 1,
+            )?);
+        }
+    }
+
+    Ok(EvolutionNode {
+        number: u16::from(pokemon.number),
+        name: String::from(pokemon.name),
+        trigger,
+        evolves_to,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{
+        Actor, EvolutionTrigger, Level, Pokemon, PokemonName, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn number(n: u16) -> PokemonNumber {
+        PokemonNumber::try_from(n).unwrap()
+    }
+
+    fn seed(pokemons: &[(u16, &str)], evolutions: Vec<Evolution>) -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        for (n, name) in pokemons {
+            let _ = repo.insert(
+                Pokemon::new(
+                    number(*n),
+                    PokemonName::try_from(String::from(*name)).unwrap(),
+                    PokemonTypes::pikachu(),
+                ),
+                &Actor::ci(),
+            );
+        }
+        for evolution in evolutions {
+            let _ = repo.insert_evolution(evolution);
+        }
+        repo
+    }
+
+    #[test]
+    fn it_should_return_the_whole_chain_from_any_of_its_members() {
+        let repo = seed(
+            &[(172, "Pichu"), (25, "Pikachu"), (26, "Raichu")],
+            vec![
+                Evolution::new(number(172), number(25), EvolutionTrigger::Friendship),
+                Evolution::new(
+                    number(25),
+                    number(26),
+                    EvolutionTrigger::Item(String::from("Thunder Stone")),
+                ),
+            ],
+        );
+
+        match execute(repo, Request { number: 26 }) {
+            Ok(root) => {
+                assert_eq!(root.number, 172);
+                assert_eq!(root.trigger, None);
+                assert_eq!(root.evolves_to.len(), 1);
+                assert_eq!(root.evolves_to[0].name, "Pikachu");
+                assert_eq!(root.evolves_to[0].evolves_to[0].number, 26);
+                assert_eq!(
+                    root.evolves_to[0].evolves_to[0].trigger,
+                    Some(TriggerValue {
+                        kind: String::from("item"),
+                        level: None,
+                        item: Some(String::from("Thunder Stone")),
+                    })
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_branch() {
+        let repo = seed(
+            &[(133, "Eevee"), (134, "Vaporeon"), (196, "Espeon")],
+            vec![
+                Evolution::new(
+                    number(133),
+                    number(134),
+                    EvolutionTrigger::Item(String::from("Water Stone")),
+                ),
+                Evolution::new(number(133), number(196), EvolutionTrigger::Friendship),
+            ],
+        );
+
+        match execute(repo, Request { number: 133 }) {
+            Ok(root) => assert_eq!(
+                root.evolves_to
+                    .iter()
+                    .map(|node| node.number)
+                    .collect::<Vec<_>>(),
+                vec![134, 196]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_single_node_for_a_pokemon_that_does_not_evolve() {
+        let repo = seed(
+            &[(25, "Pikachu"), (1, "Bulbasaur"), (2, "Ivysaur")],
+            vec![Evolution::new(
+                number(1),
+                number(2),
+                EvolutionTrigger::LevelUp(Level::try_from(16).unwrap()),
+            )],
+        );
+
+        match execute(repo, Request { number: 25 }) {
+            Ok(root) => {
+                assert_eq!(root.number, 25);
+                assert!(root.evolves_to.is_empty());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request { number: 25 }) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+}
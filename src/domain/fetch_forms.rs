@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{FieldError, PokemonNumber};
+use crate::domain::fetch_pokemon::RetrieveResponse;
+use crate::repositories::pokemon::{Repository, RetrieveAllError, Source};
+
+pub struct Request {
+    pub number: u16,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown(Source),
+}
+
+/// Returns every form of a Pokemon, the default one first.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<RetrieveResponse>, Error> {
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => number,
+        Err(e) => return Err(Error::BadRequest(vec![FieldError::new("number", e)])),
+    };
+
+    match repo.fetch_forms(number) {
+        Ok(forms) if forms.is_empty() => Err(Error::NotFound),
+        Ok(forms) => Ok(forms.into_iter().map(RetrieveResponse::from).collect()),
+        Err(RetrieveAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
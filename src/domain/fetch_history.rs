@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{AuditEvent, FieldError, PokemonNumber};
+use crate::domain::fetch_pokemon::RetrieveResponse;
+use crate::repositories::pokemon::{Repository, RetrieveAllError, Source};
+
+pub struct Request {
+    pub number: u16,
+}
+
+pub struct AuditEventResponse {
+    pub id: u64,
+    pub action: String,
+    pub number: u16,
+    pub form: String,
+    pub actor: String,
+    pub request_id: Option<String>,
+    pub timestamp: String,
+    pub before: Option<RetrieveResponse>,
+    pub after: Option<RetrieveResponse>,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown(Source),
+}
+
+impl From<AuditEvent> for AuditEventResponse {
+    fn from(event: AuditEvent) -> Self {
+        Self {
+            id: event.id,
+            action: String::from(event.action),
+            number: u16::from(event.number),
+            form: String::from(event.form),
+            actor: event.actor,
+            request_id: event.request_id,
+            timestamp: event.timestamp,
+            before: event.before.map(RetrieveResponse::from),
+            after: event.after.map(RetrieveResponse::from),
+        }
+    }
+}
+
+/// Returns every change of every form of a Pokemon, the oldest first, even
+/// once it is deleted.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<AuditEventResponse>, Error> {
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => number,
+        Err(e) => return Err(Error::BadRequest(vec![FieldError::new("number", e)])),
+    };
+
+    match repo.fetch_history(number) {
+        Ok(events) if events.is_empty() => Err(Error::NotFound),
+        Ok(events) => Ok(events.into_iter().map(AuditEventResponse::from).collect()),
+        Err(RetrieveAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{Actor, FormSlug, Pokemon, PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_keep_the_history_of_a_deleted_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let actor = Actor::ci();
+        let _ = repo.insert(Pokemon::pikachu(), &actor);
+        let _ = repo.update(
+            Pokemon::new(
+                PokemonNumber::pikachu(),
+                PokemonName::charmader(),
+                PokemonTypes::pikachu(),
+            ),
+            None,
+            &actor,
+        );
+        let _ = repo.delete_pokemon(PokemonNumber::pikachu(), FormSlug::default(), None, &actor);
+        let req = Request {
+            number: u16::from(PokemonNumber::pikachu()),
+        };
+
+        match execute(repo, req) {
+            Ok(events) => {
+                assert_eq!(
+                    events.iter().map(|e| e.action.as_str()).collect::<Vec<_>>(),
+                    vec!["insert", "update", "delete"]
+                );
+                assert_eq!(
+                    events.iter().map(|e| e.id).collect::<Vec<_>>(),
+                    vec![1, 2, 3]
+                );
+                assert!(events[0].before.is_none());
+                assert_eq!(events[1].before.as_ref().map(|p| p.revision), Some(1));
+                assert_eq!(
+                    events[1].after.as_ref().map(|p| p.name.as_str()),
+                    Some("Charmander")
+                );
+                assert!(events[2].after.is_none());
+                assert_eq!(events[2].actor, "ci");
+                assert_eq!(events[2].request_id.as_deref(), Some("req-1"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_without_any_change() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: u16::from(PokemonNumber::pikachu()),
+        };
+
+        match execute(repo, req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+}
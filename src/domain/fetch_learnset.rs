@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::create_learnset_entry::LearnsetResponse;
+use crate::domain::create_move::MoveResponse;
+use crate::domain::entities::{FieldError, FormSlug, Generation, LearnMethod, PokemonNumber};
+use crate::repositories::pokemon::{
+    LearnsetQuery, Repository, RetrieveAllError, RetrieveError, Source,
+};
+
+#[derive(Default)]
+pub struct Request {
+    pub number: u16,
+    pub method: Option<String>,
+    pub generation: Option<u8>,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown(Source),
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<LearnsetResponse>, Error> {
+    let (number, query) = match (
+        PokemonNumber::try_from(req.number),
+        req.method.map(LearnMethod::try_from).transpose(),
+        req.generation.map(Generation::try_from).transpose(),
+    ) {
+        (Ok(number), Ok(method), Ok(generation)) => (number, LearnsetQuery { method, generation }),
+        (number, method, generation) => {
+            return Err(Error::BadRequest(
+                [
+                    number.err().map(|e| FieldError::new("number", e)),
+                    method.err().map(|e| FieldError::new("method", e)),
+                    generation.err().map(|e| FieldError::new("generation", e)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ))
+        }
+    };
+
+    match repo.fetch_one(number.clone(), FormSlug::default()) {
+        Ok(_) => {}
+        Err(RetrieveError::NotFound) => return Err(Error::NotFound),
+        Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+
+    let entries = match repo.fetch_learnset(number, query) {
+        Ok(entries) => entries,
+        Err(RetrieveAllError::Unknown(e)) => return Err(Error::Unknown(e)),
+    };
+
+    // A learnset names most of the catalogue, so load it once rather than
+    // every move on its own.
+    let moves = match repo.fetch_moves() {
+        Ok(moves) => moves
+            .into_iter()
+            .map(|m| (m.name.clone(), m))
+            .collect::<HashMap<_, _>>(),
+        Err(RetrieveAllError::Unknown(e)) => return Err(Error::Unknown(e)),
+    };
+
+    entries
+        .into_iter()
+        .map(|entry| match moves.get(&entry.move_name) {
+            Some(details) => Ok(LearnsetResponse {
+                method: String::from(entry.method),
+                level: entry.level.map(u8::from),
+                generation: u8::from(entry.generation),
+                details: MoveResponse::from(details.clone()),
+            }),
+            None => Err(Error::Unknown(Source::from(format!(
+                "the move '{}' is missing from the catalogue",
+                String::from(entry.move_name)
+            )))),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{Actor, LearnsetEntry, Level, Move, MoveName, Pokemon};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn pikachu_learnset() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert_move(Move::thunderbolt());
+        let _ = repo.insert_move(Move::quick_attack());
+
+        let generation = Generation::try_from(8).unwrap();
+        for entry in [
+            LearnsetEntry::new(MoveName::thunderbolt(), LearnMethod::Tm, None, generation),
+            LearnsetEntry::new(
+                MoveName::thunderbolt(),
+                LearnMethod::Level,
+                Level::try_from(36).ok(),
+                generation,
+            ),
+            LearnsetEntry::new(
+                MoveName::quick_attack(),
+                LearnMethod::Level,
+                Level::try_from(1).ok(),
+                generation,
+            ),
+        ] {
+            let _ = repo.insert_learnset_entry(PokemonNumber::pikachu(), entry.unwrap());
+        }
+
+        repo
+    }
+
+    #[test]
+    fn it_should_list_the_moves_by_method_then_level() {
+        let repo = pikachu_learnset();
+        let req = Request {
+            number: 25,
+            ..Request::default()
+        };
+
+        match execute(repo, req) {
+            Ok(res) => assert_eq!(
+                res.iter()
+                    .map(|r| (r.method.as_str(), r.details.name.as_str()))
+                    .collect::<Vec<_>>(),
+                vec![
+                    ("level", "Quick Attack"),
+                    ("level", "Thunderbolt"),
+                    ("tm", "Thunderbolt"),
+                ]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_only_list_the_moves_learnt_by_the_given_method() {
+        let repo = pikachu_learnset();
+        let req = Request {
+            number: 25,
+            method: Some(String::from("tm")),
+            ..Request::default()
+        };
+
+        match execute(repo, req) {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].level, None);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 25,
+            ..Request::default()
+        };
+
+        match execute(repo, req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+}
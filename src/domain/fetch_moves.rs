@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use crate::domain::create_move::MoveResponse;
+use crate::repositories::pokemon::{Repository, RetrieveAllError, Source};
+
+pub enum Error {
+    Unknown(Source),
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<MoveResponse>, Error> {
+    match repo.fetch_moves() {
+        Ok(moves) => Ok(moves.into_iter().map(MoveResponse::from).collect()),
+        Err(RetrieveAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
@@ -1,37 +1,64 @@
 use std::sync::Arc;
 
-use crate::repositories::pokemon::{Repository, RetrieveError};
+use crate::repositories::pokemon::{Repository, RetrieveError, Source};
 
-use super::entities::PokemonNumber;
+use super::entities::{FieldError, FormSlug, Pokemon, PokemonNumber, SlotValue, StatValues};
 
 pub struct Request {
     pub number: u16,
+    /// `None` fetches the default form.
+    pub form: Option<String>,
 }
 
 pub struct RetrieveResponse {
     pub number: u16,
+    pub form: String,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<StatValues>,
+    pub abilities: Vec<SlotValue>,
+    pub revision: u32,
 }
 
 pub enum Error {
-    Unknown,
-    BadRequest,
+    Unknown(Source),
+    BadRequest(Vec<FieldError>),
     NotFound,
 }
 
+impl From<Pokemon> for RetrieveResponse {
+    fn from(p: Pokemon) -> Self {
+        Self {
+            number: u16::from(p.number),
+            form: String::from(p.form),
+            name: String::from(p.name),
+            types: Vec::<String>::from(p.types),
+            stats: p.stats.map(StatValues::from),
+            abilities: Vec::<SlotValue>::from(p.abilities),
+            revision: p.revision,
+        }
+    }
+}
+
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<RetrieveResponse, Error> {
-    match PokemonNumber::try_from(req.number) {
-        Ok(number) => match repo.fetch_one(number) {
-            Ok(p) => Ok(RetrieveResponse {
-                number: u16::from(p.number),
-                name: String::from(p.name),
-                types: Vec::<String>::from(p.types),
-            }),
+    match (
+        PokemonNumber::try_from(req.number),
+        req.form.map(FormSlug::try_from).transpose(),
+    ) {
+        (Ok(number), Ok(form)) => match repo.fetch_one(number, form.unwrap_or_default()) {
+            Ok(p) => Ok(RetrieveResponse::from(p)),
             Err(RetrieveError::NotFound) => Err(Error::NotFound),
-            Err(RetrieveError::Unknown) => Err(Error::Unknown),
+            Err(RetrieveError::Unknown(e)) => Err(Error::Unknown(e)),
         },
-        _ => Err(Error::BadRequest),
+        (number, form) => Err(Error::BadRequest(
+            [
+                number.err().map(|e| FieldError::new("number", e)),
+                form.err().map(|e| FieldError::new("form", e)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        )),
     }
 }
 
@@ -40,7 +67,7 @@ mod test {
 
     use super::*;
     use crate::{
-        domain::entities::{PokemonName, PokemonNumber, PokemonTypes},
+        domain::entities::{Actor, PokemonName, PokemonTypes},
         repositories::pokemon::InMemoryRepository,
     };
 
@@ -48,6 +75,7 @@ mod test {
         pub fn new(number: PokemonNumber) -> Self {
             Self {
                 number: u16::from(number),
+                form: None,
             }
         }
     }
@@ -61,7 +89,7 @@ mod test {
         let res = execute(repo, req);
 
         match res {
-            Err(Error::Unknown) => {}
+            Err(Error::Unknown(_)) => {}
             _ => unreachable!(),
         }
     }
@@ -74,7 +102,7 @@ mod test {
         let res = execute(repo, req);
 
         match res {
-            Err(Error::BadRequest) => {}
+            Err(Error::BadRequest(_)) => {}
             _ => unreachable!(),
         }
     }
@@ -95,11 +123,7 @@ mod test {
     #[test]
     fn it_should_return_the_pokemon_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
-        let _ = repo.insert(
-            PokemonNumber::pikachu(),
-            PokemonName::pikachu(),
-            PokemonTypes::pikachu(),
-        );
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
         let req = Request::new(PokemonNumber::pikachu());
 
         let res = execute(repo, req);
@@ -113,4 +137,25 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn it_should_return_the_requested_form() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(
+            Pokemon::pikachu()
+                .with_form(FormSlug::alola())
+                .with_stats(None),
+            &Actor::ci(),
+        );
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(PokemonNumber::pikachu())
+        };
+
+        match execute(repo, req) {
+            Ok(p) => assert_eq!(p.form, "alola"),
+            _ => unreachable!(),
+        }
+    }
 }
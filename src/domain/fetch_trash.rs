@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::domain::fetch_pokemon::RetrieveResponse;
+use crate::repositories::pokemon::{Repository, RetrieveAllError, Source};
+
+use super::entities::DeletedPokemon;
+
+pub struct TrashResponse {
+    pub pokemon: RetrieveResponse,
+    pub deleted_at: String,
+}
+
+pub enum Error {
+    Unknown(Source),
+}
+
+impl From<DeletedPokemon> for TrashResponse {
+    fn from(deleted: DeletedPokemon) -> Self {
+        Self {
+            pokemon: RetrieveResponse::from(deleted.pokemon),
+            deleted_at: deleted.deleted_at,
+        }
+    }
+}
+
+/// Returns the forms in the trash by number, the default one first.
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<TrashResponse>, Error> {
+    match repo.fetch_trash() {
+        Ok(trash) => Ok(trash.into_iter().map(TrashResponse::from).collect()),
+        Err(RetrieveAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{Actor, FormSlug, Pokemon, PokemonNumber};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo) {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_deleted_forms_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(
+            Pokemon::pikachu().with_form(FormSlug::alola()),
+            &Actor::ci(),
+        );
+        let _ = repo.insert(Pokemon::charmander(), &Actor::ci());
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+
+        match execute(repo) {
+            Ok(trash) => {
+                assert_eq!(
+                    trash
+                        .iter()
+                        .map(|d| (d.pokemon.number, d.pokemon.form.as_str()))
+                        .collect::<Vec<_>>(),
+                    vec![(25, "default"), (25, "alola")]
+                );
+                assert_eq!(trash[0].deleted_at, trash[1].deleted_at);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
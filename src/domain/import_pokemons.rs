@@ -0,0 +1,654 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+
+use crate::domain::create_pokemon;
+use crate::domain::entities::{Actor, FieldError, Pokemon, SlotValue, StatValues, ValidationError};
+use crate::repositories::pokemon::{ImportError, ImportOutcome, OnConflict, Repository, Source};
+
+/// The columns of a CSV import, in their canonical order. Only `number`,
+/// `name` and `types` are required; the stats columns go all together.
+pub const CSV_COLUMNS: [&str; 11] = [
+    "number",
+    "form",
+    "name",
+    "types",
+    "hp",
+    "attack",
+    "defense",
+    "special_attack",
+    "special_defense",
+    "speed",
+    "abilities",
+];
+
+pub struct Request {
+    pub format: String,
+    pub on_conflict: String,
+    pub content: String,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    /// The Pokemon of this line already exists, so nothing was imported.
+    Conflict {
+        line: usize,
+    },
+    Unknown(Source),
+}
+
+/// A line that was left out of the import. For a JSON array, `line` is the
+/// one the item starts on.
+#[derive(Debug, PartialEq)]
+pub struct Rejection {
+    pub line: usize,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportResponse {
+    pub inserted: usize,
+    pub replaced: usize,
+    pub skipped: usize,
+    pub rejected: Vec<Rejection>,
+}
+
+enum Format {
+    Json,
+    Jsonl,
+    Csv,
+}
+
+/// A parsed line, either ready to be validated or already rejected.
+type Record = (usize, Result<create_pokemon::Request, String>);
+
+/// Imports the Pokemons on behalf of `actor`, who is recorded in the audit log.
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+    actor: &Actor,
+) -> Result<ImportResponse, Error> {
+    let format = match req.format.as_str() {
+        "json" => Ok(Format::Json),
+        "jsonl" => Ok(Format::Jsonl),
+        "csv" => Ok(Format::Csv),
+        format => Err(ValidationError::UnknownImportFormat(String::from(format))),
+    };
+    let on_conflict = match req.on_conflict.as_str() {
+        "fail" => Ok(OnConflict::Fail),
+        "skip" => Ok(OnConflict::Skip),
+        "replace" => Ok(OnConflict::Replace),
+        policy => Err(ValidationError::UnknownConflictPolicy(String::from(policy))),
+    };
+
+    let (records, on_conflict) = match (format, on_conflict) {
+        (Ok(Format::Json), Ok(on_conflict)) => match json_records(&req.content) {
+            Ok(records) => (records, on_conflict),
+            Err(e) => return Err(Error::BadRequest(vec![FieldError::new("content", e)])),
+        },
+        (Ok(Format::Jsonl), Ok(on_conflict)) => (jsonl_records(&req.content), on_conflict),
+        (Ok(Format::Csv), Ok(on_conflict)) => match csv_records(&req.content) {
+            Ok(records) => (records, on_conflict),
+            Err(e) => return Err(Error::BadRequest(vec![FieldError::new("content", e)])),
+        },
+        (format, on_conflict) => {
+            return Err(Error::BadRequest(
+                [
+                    format.err().map(|e| FieldError::new("format", e)),
+                    on_conflict.err().map(|e| FieldError::new("on_conflict", e)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ))
+        }
+    };
+
+    let mut res = ImportResponse::default();
+    let mut pokemons = vec![];
+    for (line, record) in records {
+        match record.and_then(|req| Pokemon::try_from(req).map_err(|errors| describe(&errors))) {
+            Ok(pokemon) => pokemons.push((line, pokemon)),
+            Err(reason) => res.rejected.push(Rejection { line, reason }),
+        }
+    }
+
+    // Default forms go first so that the alternate forms of the same file find them.
+    pokemons.sort_by_key(|(_, pokemon)| !pokemon.form.is_default());
+    let (lines, pokemons): (Vec<usize>, Vec<Pokemon>) = pokemons.into_iter().unzip();
+
+    let outcomes = match repo.import(pokemons, on_conflict, actor) {
+        Ok(outcomes) => outcomes,
+        Err(ImportError::Conflict(index)) => return Err(Error::Conflict { line: lines[index] }),
+        Err(ImportError::Unknown(e)) => return Err(Error::Unknown(e)),
+    };
+
+    for (line, outcome) in lines.into_iter().zip(outcomes) {
+        match outcome {
+            ImportOutcome::Inserted(_) => res.inserted += 1,
+            ImportOutcome::Replaced(_) => res.replaced += 1,
+            ImportOutcome::Skipped => res.skipped += 1,
+            ImportOutcome::MissingDefaultForm => res.rejected.push(Rejection {
+                line,
+                reason: String::from("form: the default form of this Pokemon does not exist"),
+            }),
+            ImportOutcome::Deleted => res.rejected.push(Rejection {
+                line,
+                reason: String::from("this Pokemon is in the trash, restore or purge it first"),
+            }),
+        }
+    }
+    res.rejected.sort_by_key(|rejection| rejection.line);
+
+    Ok(res)
+}
+
+fn describe(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.error))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// A Pokemon as written in JSON files, shaped like the body of `POST /`.
+#[derive(Serialize, Deserialize)]
+pub(super) struct JsonRecord {
+    number: u16,
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<JsonStats>,
+    #[serde(default)]
+    abilities: Vec<JsonSlot>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonStats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    special_attack: u16,
+    special_defense: u16,
+    speed: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonSlot {
+    slot: String,
+    name: String,
+}
+
+impl From<Pokemon> for JsonRecord {
+    fn from(p: Pokemon) -> Self {
+        Self {
+            number: u16::from(p.number),
+            form: Some(String::from(p.form)),
+            name: String::from(p.name),
+            types: Vec::<String>::from(p.types),
+            stats: p.stats.map(|stats| {
+                let values = StatValues::from(stats);
+                JsonStats {
+                    hp: values.hp,
+                    attack: values.attack,
+                    defense: values.defense,
+                    special_attack: values.special_attack,
+                    special_defense: values.special_defense,
+                    speed: values.speed,
+                }
+            }),
+            abilities: Vec::<SlotValue>::from(p.abilities)
+                .into_iter()
+                .map(|a| JsonSlot {
+                    slot: a.slot,
+                    name: a.name,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<JsonRecord> for create_pokemon::Request {
+    fn from(record: JsonRecord) -> Self {
+        Self {
+            number: record.number,
+            form: record.form,
+            name: record.name,
+            types: record.types,
+            stats: record.stats.map(|stats| {
+                StatValues::from([
+                    stats.hp,
+                    stats.attack,
+                    stats.defense,
+                    stats.special_attack,
+                    stats.special_defense,
+                    stats.speed,
+                ])
+            }),
+            abilities: record
+                .abilities
+                .into_iter()
+                .map(|a| SlotValue {
+                    slot: a.slot,
+                    name: a.name,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The content is a single array, each item being checked on its own so
+/// that one bad item does not reject the others.
+fn json_records(content: &str) -> Result<Vec<Record>, ValidationError> {
+    let items = match serde_json::from_str::<Vec<&RawValue>>(content) {
+        Ok(items) => items,
+        Err(e) => return Err(ValidationError::MalformedContent(e.to_string())),
+    };
+
+    Ok(items
+        .into_iter()
+        .map(|item| {
+            // An item is borrowed from the content, which tells where it starts.
+            let start = item.get().as_ptr() as usize - content.as_ptr() as usize;
+            let line = content[..start].matches('\n').count() + 1;
+            let record = serde_json::from_str::<JsonRecord>(item.get())
+                .map(create_pokemon::Request::from)
+                .map_err(|e| format!("invalid JSON: {}", e));
+
+            (line, record)
+        })
+        .collect())
+}
+
+/// Every non blank line holds a JSON object shaped like the body of `POST /`.
+fn jsonl_records(content: &str) -> Vec<Record> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let record = serde_json::from_str::<JsonRecord>(line)
+                .map(create_pokemon::Request::from)
+                .map_err(|e| format!("invalid JSON: {}", e));
+
+            (index + 1, record)
+        })
+        .collect()
+}
+
+/// The first line is a header naming the columns, in any order. Types are
+/// separated by `;`, and so are abilities, written as `slot:name`, a `\`
+/// escaping a `;` or a `\` inside an item.
+fn csv_records(content: &str) -> Result<Vec<Record>, ValidationError> {
+    let mut lines = content.split('\n').enumerate().peekable();
+    let header = match split_csv_record(&mut lines) {
+        Some((_, Ok(header))) => header,
+        _ => vec![],
+    };
+    let column = |name: &str| header.iter().position(|c| c.trim() == name);
+
+    let (number, name, types) = match (column("number"), column("name"), column("types")) {
+        (Some(number), Some(name), Some(types)) => (number, name, types),
+        (None, _, _) => return Err(ValidationError::MissingColumn("number")),
+        (_, None, _) => return Err(ValidationError::MissingColumn("name")),
+        (_, _, None) => return Err(ValidationError::MissingColumn("types")),
+    };
+    let stats = match [4, 5, 6, 7, 8, 9].map(|i| column(CSV_COLUMNS[i])) {
+        columns if columns.iter().all(Option::is_none) => None,
+        columns => match columns.iter().position(Option::is_none) {
+            Some(missing) => return Err(ValidationError::MissingColumn(CSV_COLUMNS[4 + missing])),
+            None => Some(columns.map(Option::unwrap_or_default)),
+        },
+    };
+    let (form, abilities) = (column("form"), column("abilities"));
+
+    let mut records = vec![];
+    loop {
+        while lines.next_if(|(_, line)| line.trim().is_empty()).is_some() {}
+        let Some((index, fields)) = split_csv_record(&mut lines) else {
+            break;
+        };
+        let record = fields.and_then(|fields| {
+            if fields.len() != header.len() {
+                return Err(format!(
+                    "expected {} fields, got {}",
+                    header.len(),
+                    fields.len()
+                ));
+            }
+
+            Ok(create_pokemon::Request {
+                number: fields[number]
+                    .trim()
+                    .parse::<u16>()
+                    .map_err(|_| format!("number: '{}' is not a number", fields[number]))?,
+                form: form
+                    .map(|i| fields[i].trim())
+                    .filter(|form| !form.is_empty())
+                    .map(String::from),
+                name: fields[name].clone(),
+                types: split_list(&fields[types]),
+                stats: match stats {
+                    Some(columns) => parse_stats(columns.map(|i| fields[i].trim()))?,
+                    None => None,
+                },
+                abilities: match abilities {
+                    Some(i) => parse_abilities(&fields[i])?,
+                    None => vec![],
+                },
+            })
+        });
+
+        records.push((index + 1, record));
+    }
+
+    Ok(records)
+}
+
+/// Splits a list on `;`, a `\` escaping the character following it.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = vec![];
+    let mut item = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => item.extend(chars.next()),
+            ';' => items.push(std::mem::take(&mut item)),
+            c => item.push(c),
+        }
+    }
+    items.push(item);
+
+    items
+        .iter()
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// The stats are either all empty or all set.
+fn parse_stats(values: [&str; 6]) -> Result<Option<StatValues>, String> {
+    if values.iter().all(|value| value.is_empty()) {
+        return Ok(None);
+    }
+
+    match values
+        .iter()
+        .map(|value| value.parse::<u16>())
+        .collect::<Result<Vec<_>, _>>()
+        .map(<[u16; 6]>::try_from)
+    {
+        Ok(Ok(values)) => Ok(Some(StatValues::from(values))),
+        _ => Err(String::from(
+            "stats: expected six positive integers, or none at all",
+        )),
+    }
+}
+
+fn parse_abilities(value: &str) -> Result<Vec<SlotValue>, String> {
+    split_list(value)
+        .into_iter()
+        .map(|item| match item.split_once(':') {
+            Some((slot, name)) => Ok(SlotValue {
+                slot: String::from(slot.trim()),
+                name: String::from(name.trim()),
+            }),
+            None => Err(format!(
+                "abilities: '{}' is not an ability, expected slot:name",
+                item
+            )),
+        })
+        .collect()
+}
+
+/// Splits the next CSV record, with the index of the line it starts on.
+/// Fields are optionally quoted with `"`, quotes being doubled inside them,
+/// and a quoted field goes on over the next lines until it is closed.
+fn split_csv_record<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Option<(usize, Result<Vec<String>, String>)> {
+    let (index, mut line) = lines.next()?;
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+
+    loop {
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, quoted) {
+                ('"', true) if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                ('"', true) => quoted = false,
+                ('"', false) if field.is_empty() => quoted = true,
+                (',', false) => fields.push(std::mem::take(&mut field)),
+                // The line ends with CRLF.
+                ('\r', false) if chars.peek().is_none() => {}
+                (c, _) => field.push(c),
+            }
+        }
+
+        if !quoted {
+            break;
+        }
+        match lines.next() {
+            Some((_, next)) => {
+                field.push('\n');
+                line = next;
+            }
+            None => return Some((index, Err(String::from("unterminated quoted field")))),
+        }
+    }
+
+    fields.push(field);
+    Some((index, Ok(fields)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{Ability, AbilityName, FormSlug, PokemonNumber};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        fn new(format: &str, on_conflict: &str, content: &str) -> Self {
+            Self {
+                format: String::from(format),
+                on_conflict: String::from(on_conflict),
+                content: String::from(content),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_import_every_valid_line_and_report_the_others() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let content = r#"{"number":25,"name":"Pikachu","types":["Electric"]}
+
+{"number":26,"name":"","types":["Electric"]}
+{"number":27,"name":"Sandshrew","types":["Ground"
+{"number":27,"form":"alola","name":"Sandshrew","types":["Ice","Steel"]}
+{"number":27,"name":"Sandshrew","types":["Ground"]}
+{"number":28,"form":"alola","name":"Sandslash","types":["Ice","Steel"]}
+"#;
+
+        match execute(
+            repo.clone(),
+            Request::new("jsonl", "fail", content),
+            &Actor::ci(),
+        ) {
+            Ok(res) => {
+                assert_eq!(res.inserted, 3);
+                assert_eq!(
+                    res.rejected.iter().map(|r| r.line).collect::<Vec<_>>(),
+                    vec![3, 4, 7]
+                );
+                assert_eq!(
+                    res.rejected[0].reason,
+                    "name: the Pokemon name must not be empty"
+                );
+                assert_eq!(
+                    res.rejected[2].reason,
+                    "form: the default form of this Pokemon does not exist"
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        match repo.fetch_one(PokemonNumber::try_from(27).unwrap(), FormSlug::alola()) {
+            Ok(pokemon) => assert_eq!(Vec::<String>::from(pokemon.types).len(), 2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_report_the_line_an_item_of_a_json_array_starts_on() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let content = r#"[
+  {"number": 25, "name": "Pikachu", "types": ["Electric"]},
+  {
+    "number": 26,
+    "name": "",
+    "types": ["Electric"]
+  }, {"number": 27, "name": "Sandshrew", "types": "Ground"}
+]"#;
+
+        match execute(repo, Request::new("json", "fail", content), &Actor::ci()) {
+            Ok(res) => {
+                assert_eq!(res.inserted, 1);
+                assert_eq!(
+                    res.rejected.iter().map(|r| r.line).collect::<Vec<_>>(),
+                    vec![3, 7]
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_read_a_csv_with_quoted_fields_and_optional_columns() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert_ability(Ability::new(AbilityName::static_(), String::new()));
+        let content = "name,number,types,abilities\n\
+                       \"Pikachu, \"\"the mouse\"\"\",25,Electric,primary:Static\n\
+                       Charmander,4,Fire,primary:Blaze\n\
+                       Bulbasaur,one,Grass;Poison,\n";
+
+        match execute(
+            repo.clone(),
+            Request::new("csv", "fail", content),
+            &Actor::ci(),
+        ) {
+            Ok(res) => {
+                assert_eq!(res.inserted, 2);
+                assert_eq!(
+                    res.rejected,
+                    vec![Rejection {
+                        line: 4,
+                        reason: String::from("number: 'one' is not a number"),
+                    }]
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        // Blaze was missing from the catalogue.
+        match repo.fetch_abilities() {
+            Ok(abilities) => assert_eq!(abilities.len(), 2),
+            _ => unreachable!(),
+        }
+
+        match repo.fetch_one(PokemonNumber::pikachu(), FormSlug::default()) {
+            Ok(pokemon) => assert_eq!(String::from(pokemon.name), "Pikachu, \"the mouse\""),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_apply_the_conflict_policy() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let content = "number,name,types\n4,Charmander,Fire\n25,Raichu,Electric\n";
+
+        match execute(
+            repo.clone(),
+            Request::new("csv", "fail", content),
+            &Actor::ci(),
+        ) {
+            Err(Error::Conflict { line }) => assert_eq!(line, 3),
+            _ => unreachable!(),
+        }
+        assert!(repo
+            .fetch_one(PokemonNumber::charmander(), FormSlug::default())
+            .is_err());
+
+        match execute(
+            repo.clone(),
+            Request::new("csv", "skip", content),
+            &Actor::ci(),
+        ) {
+            Ok(res) => assert_eq!((res.inserted, res.skipped), (1, 1)),
+            _ => unreachable!(),
+        }
+        match execute(
+            repo.clone(),
+            Request::new("csv", "replace", content),
+            &Actor::ci(),
+        ) {
+            Ok(res) => assert_eq!((res.inserted, res.replaced), (0, 2)),
+            _ => unreachable!(),
+        }
+
+        match repo.fetch_one(PokemonNumber::pikachu(), FormSlug::default()) {
+            Ok(pokemon) => assert_eq!(String::from(pokemon.name), "Raichu"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_for_an_unknown_format_or_policy() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new("xml", "merge", ""), &Actor::ci()) {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![
+                    FieldError::new(
+                        "format",
+                        ValidationError::UnknownImportFormat(String::from("xml"))
+                    ),
+                    FieldError::new(
+                        "on_conflict",
+                        ValidationError::UnknownConflictPolicy(String::from("merge"))
+                    ),
+                ]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_require_the_number_name_and_types_columns() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(
+            repo,
+            Request::new("csv", "fail", "number,name\n25,Pikachu\n"),
+            &Actor::ci(),
+        ) {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![FieldError::new(
+                    "content",
+                    ValidationError::MissingColumn("types")
+                )]
+            ),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,5 +1,29 @@
+pub mod authorize;
+pub mod calculate_stats;
+pub mod create_ability;
+pub mod create_api_key;
+pub mod create_evolution;
+pub mod create_learnset_entry;
+pub mod create_move;
 pub mod create_pokemon;
 pub mod delete_pokemon;
 pub mod entities;
+pub mod export_pokemons;
+pub mod fetch_abilities;
+pub mod fetch_ability_pokemons;
 pub mod fetch_all_pokemons;
+pub mod fetch_api_keys;
+pub mod fetch_audit;
+pub mod fetch_evolution_chain;
+pub mod fetch_forms;
+pub mod fetch_history;
+pub mod fetch_learnset;
+pub mod fetch_moves;
 pub mod fetch_pokemon;
+pub mod fetch_trash;
+pub mod import_pokemons;
+pub mod purge_pokemons;
+pub mod restore_pokemon;
+pub mod revoke_api_key;
+pub mod search_pokemons;
+pub mod update_pokemon;
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::domain::fetch_pokemon::RetrieveResponse;
+use crate::repositories::pokemon::{DeleteError, Repository, Source};
+
+use super::entities::{Actor, FieldError, ValidationError};
+
+/// How long deleted forms are kept in the trash when no window is given.
+pub const DEFAULT_RETENTION: &str = "30d";
+
+pub struct Request {
+    /// A duration such as `30d` or `12h`, the forms deleted longer ago than
+    /// that being purged.
+    pub older_than: String,
+}
+
+pub enum Error {
+    Unknown(Source),
+    BadRequest(Vec<FieldError>),
+}
+
+/// Removes for good the forms that have been in the trash for longer than the
+/// retention window, on behalf of `actor`, and returns them.
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+    actor: &Actor,
+) -> Result<Vec<RetrieveResponse>, Error> {
+    let cutoff = humantime::parse_duration(req.older_than.trim())
+        .ok()
+        .and_then(|window| SystemTime::now().checked_sub(window));
+    let cutoff = match cutoff {
+        Some(cutoff) => humantime::format_rfc3339_millis(cutoff).to_string(),
+        None => {
+            return Err(Error::BadRequest(vec![FieldError::new(
+                "older_than",
+                ValidationError::InvalidDuration(req.older_than),
+            )]))
+        }
+    };
+
+    match repo.purge(cutoff, actor) {
+        Ok(purged) => Ok(purged.into_iter().map(RetrieveResponse::from).collect()),
+        Err(DeleteError::Unknown(e)) => Err(Error::Unknown(e)),
+        Err(e @ (DeleteError::NotFound | DeleteError::Stale(_))) => Err(Error::Unknown(e.into())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{FormSlug, Pokemon, PokemonNumber};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        pub fn new(older_than: &str) -> Self {
+            Self {
+                older_than: String::from(older_than),
+            }
+        }
+    }
+
+    fn trashed_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+        repo
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_window_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new("a month"), &Actor::ci()) {
+            Err(Error::BadRequest(errors)) => assert_eq!(errors[0].field, "older_than"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, Request::new(DEFAULT_RETENTION), &Actor::ci()) {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_keep_the_forms_deleted_within_the_window() {
+        let repo = trashed_pikachu();
+
+        match (
+            execute(repo.clone(), Request::new(DEFAULT_RETENTION), &Actor::ci()),
+            repo.fetch_trash(),
+        ) {
+            (Ok(purged), Ok(trash)) => {
+                assert!(purged.is_empty());
+                assert_eq!(trash.len(), 1);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_purge_the_forms_deleted_before_the_window() {
+        let repo = trashed_pikachu();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        match (
+            execute(repo.clone(), Request::new("1ms"), &Actor::ci()),
+            repo.fetch_trash(),
+        ) {
+            (Ok(purged), Ok(trash)) => {
+                assert_eq!(purged.len(), 1);
+                assert_eq!(purged[0].number, 25);
+                assert!(trash.is_empty());
+            }
+            _ => unreachable!(),
+        }
+    }
+}
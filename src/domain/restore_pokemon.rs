@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::domain::fetch_pokemon::RetrieveResponse;
+use crate::repositories::pokemon::{Repository, RestoreError, Source};
+
+use super::entities::{Actor, FieldError, FormSlug, PokemonNumber};
+
+pub struct Request {
+    pub number: u16,
+    /// `None` restores the default form, and with it the forms deleted along
+    /// with it.
+    pub form: Option<String>,
+}
+
+pub enum Error {
+    Unknown(Source),
+    BadRequest(Vec<FieldError>),
+    /// The form is not in the trash.
+    NotFound,
+    /// The default form is in the trash too, and must be restored first.
+    Conflict,
+}
+
+/// Takes the form back out of the trash on behalf of `actor`, who is recorded
+/// in the audit log.
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+    actor: &Actor,
+) -> Result<RetrieveResponse, Error> {
+    match (
+        PokemonNumber::try_from(req.number),
+        req.form.map(FormSlug::try_from).transpose(),
+    ) {
+        (Ok(number), Ok(form)) => {
+            let form = form.unwrap_or_default();
+            match repo.restore(number, form.clone(), actor) {
+                Ok(restored) => match restored.into_iter().find(|p| p.form == form) {
+                    Some(pokemon) => Ok(RetrieveResponse::from(pokemon)),
+                    None => Err(Error::NotFound),
+                },
+                Err(RestoreError::NotFound) => Err(Error::NotFound),
+                Err(RestoreError::DefaultFormDeleted) => Err(Error::Conflict),
+                Err(RestoreError::Unknown(e)) => Err(Error::Unknown(e)),
+            }
+        }
+        (number, form) => Err(Error::BadRequest(
+            [
+                number.err().map(|e| FieldError::new("number", e)),
+                form.err().map(|e| FieldError::new("form", e)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::Pokemon;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        pub fn new(number: PokemonNumber) -> Self {
+            Self {
+                number: u16::from(number),
+                form: None,
+            }
+        }
+    }
+
+    fn trashed_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(
+            Pokemon::pikachu().with_form(FormSlug::alola()),
+            &Actor::ci(),
+        );
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+        repo
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu());
+
+        match execute(repo, req, &Actor::ci()) {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::bad());
+
+        match execute(repo, req, &Actor::ci()) {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_form_is_not_in_the_trash() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let req = Request::new(PokemonNumber::pikachu());
+
+        match execute(repo, req, &Actor::ci()) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_default_form_is_in_the_trash() {
+        let repo = trashed_pikachu();
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(PokemonNumber::pikachu())
+        };
+
+        match execute(repo, req, &Actor::ci()) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_restore_every_form_deleted_with_the_default_one() {
+        let repo = trashed_pikachu();
+        let req = Request::new(PokemonNumber::pikachu());
+
+        match (
+            execute(repo.clone(), req, &Actor::ci()),
+            repo.fetch_forms(PokemonNumber::pikachu()),
+            repo.fetch_trash(),
+        ) {
+            (Ok(res), Ok(forms), Ok(trash)) => {
+                assert_eq!(res.form, String::from("default"));
+                assert_eq!(res.revision, 1);
+                assert_eq!(forms.len(), 2);
+                assert!(trash.is_empty());
+            }
+            _ => unreachable!(),
+        }
+    }
+}
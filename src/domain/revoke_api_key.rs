@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{FieldError, KeyName};
+use crate::repositories::pokemon::{DeleteError, Repository, Source};
+
+pub struct Request {
+    pub name: String,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown(Source),
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    let name = match KeyName::try_from(req.name) {
+        Ok(name) => name,
+        Err(e) => return Err(Error::BadRequest(vec![FieldError::new("name", e)])),
+    };
+
+    match repo.revoke_key(name) {
+        Ok(()) => Ok(()),
+        Err(DeleteError::NotFound) => Err(Error::NotFound),
+        Err(DeleteError::Unknown(e)) => Err(Error::Unknown(e)),
+        Err(e @ DeleteError::Stale(_)) => Err(Error::Unknown(e.into())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::ApiKey;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_keep_the_revoked_key_listed() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert_key(ApiKey::editor());
+        let req = Request {
+            name: String::from("ci"),
+        };
+
+        match (execute(repo.clone(), req), repo.fetch_keys()) {
+            (Ok(()), Ok(keys)) => assert!(keys[0].revoked),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_no_key_has_the_name() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            name: String::from("ci"),
+        };
+
+        match execute(repo, req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+}
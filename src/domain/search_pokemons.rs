@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{FieldError, SlotValue, StatValues, ValidationError};
+use crate::domain::fetch_all_pokemons::RetrieveAllResponse;
+use crate::repositories::pokemon::{Repository, RetrieveAllError, Source};
+
+pub const MAX_LIMIT: u32 = 50;
+
+pub struct Request {
+    pub query: String,
+    pub limit: u32,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    Unknown(Source),
+}
+
+/// Returns the Pokemons best matching the query, the best match first.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<RetrieveAllResponse>, Error> {
+    let query = req.query.trim();
+    let errors = [
+        query
+            .is_empty()
+            .then(|| FieldError::new("q", ValidationError::EmptySearch)),
+        (req.limit == 0 || req.limit > MAX_LIMIT).then(|| {
+            FieldError::new(
+                "limit",
+                ValidationError::LimitOutOfRange {
+                    min: 1,
+                    max: MAX_LIMIT,
+                    got: req.limit,
+                },
+            )
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(Error::BadRequest(errors));
+    }
+
+    match repo.search(String::from(query), req.limit) {
+        Ok(pokemons) => Ok(pokemons
+            .into_iter()
+            .map(|p| RetrieveAllResponse {
+                number: u16::from(p.number),
+                name: String::from(p.name),
+                types: Vec::<String>::from(p.types),
+                stats: p.stats.map(StatValues::from),
+                abilities: Vec::<SlotValue>::from(p.abilities),
+            })
+            .collect()),
+        Err(RetrieveAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entities::{Actor, Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        for (number, name) in [(4, "Charmander"), (25, "Pikachu"), (26, "Raichu")] {
+            let _ = repo.insert(
+                Pokemon::new(
+                    PokemonNumber::try_from(number).unwrap(),
+                    PokemonName::try_from(String::from(name)).unwrap(),
+                    PokemonTypes::pikachu(),
+                ),
+                &Actor::ci(),
+            );
+        }
+        repo
+    }
+
+    fn names(res: Result<Vec<RetrieveAllResponse>, Error>) -> Vec<String> {
+        match res {
+            Ok(pokemons) => pokemons.into_iter().map(|p| p.name).collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_find_a_pokemon_despite_a_typo() {
+        let req = Request {
+            query: String::from("pikchu"),
+            limit: 10,
+        };
+
+        assert_eq!(names(execute(repo(), req)), vec!["Pikachu"]);
+    }
+
+    #[test]
+    fn it_should_rank_a_prefix_before_a_substring() {
+        let req = Request {
+            query: String::from("r"),
+            limit: 10,
+        };
+
+        assert_eq!(names(execute(repo(), req)), vec!["Raichu", "Charmander"]);
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_query_is_blank() {
+        let req = Request {
+            query: String::from("  "),
+            limit: 0,
+        };
+
+        match execute(repo(), req) {
+            Err(Error::BadRequest(errors)) => assert_eq!(errors.len(), 2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            query: String::from("pikachu"),
+            limit: 10,
+        };
+
+        match execute(repo, req) {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
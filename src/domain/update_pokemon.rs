@@ -0,0 +1,330 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{
+    Actor, BaseStats, FieldError, FormSlug, Pokemon, PokemonAbilities, PokemonName, PokemonNumber,
+    PokemonTypes, SlotValue, StatValues, ValidationError,
+};
+use crate::repositories::pokemon::{Repository, RetrieveError, Source, UpdateError};
+
+pub struct Request {
+    pub number: u16,
+    /// `None` updates the default form.
+    pub form: Option<String>,
+    pub name: Option<String>,
+    pub types: Option<Vec<String>>,
+    /// `None` keeps the current stats, `Some(None)` clears them.
+    pub stats: Option<Option<StatValues>>,
+    pub abilities: Option<Vec<SlotValue>>,
+    /// The revisions the client accepts, `None` updating the form whatever
+    /// its revision.
+    pub revisions: Option<Vec<u32>>,
+}
+
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    /// The form is at another revision, given here.
+    PreconditionFailed(u32),
+    Unknown(Source),
+}
+
+#[derive(Debug)]
+pub struct UpdateResponse {
+    pub number: u16,
+    pub form: String,
+    pub name: String,
+    pub types: Vec<String>,
+    pub stats: Option<StatValues>,
+    pub abilities: Vec<SlotValue>,
+    pub revision: u32,
+}
+
+/// Updates the form on behalf of `actor`, who is recorded in the audit log.
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+    actor: &Actor,
+) -> Result<UpdateResponse, Error> {
+    let (number, form) = match (
+        PokemonNumber::try_from(req.number),
+        req.form.map(FormSlug::try_from).transpose(),
+    ) {
+        (Ok(number), Ok(form)) => (number, form.unwrap_or_default()),
+        (number, form) => {
+            return Err(Error::BadRequest(
+                [
+                    number.err().map(|e| FieldError::new("number", e)),
+                    form.err().map(|e| FieldError::new("form", e)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ))
+        }
+    };
+
+    let (name, types, stats, abilities, revisions) =
+        match (req.name, req.types, req.stats, req.abilities) {
+            (Some(name), Some(types), Some(stats), Some(abilities)) => {
+                (name, types, stats, abilities, req.revisions)
+            }
+            (name, types, stats, abilities) => match repo.fetch_one(number.clone(), form.clone()) {
+                // No need to merge with a stale form.
+                Ok(current)
+                    if req
+                        .revisions
+                        .as_ref()
+                        .is_some_and(|revisions| !revisions.contains(&current.revision)) =>
+                {
+                    return Err(Error::PreconditionFailed(current.revision))
+                }
+                // The merged fields are only current at the revision they were read
+                // at, so a form changed in the meantime is not overwritten.
+                Ok(current) => (
+                    name.unwrap_or_else(|| String::from(current.name)),
+                    types.unwrap_or_else(|| Vec::<String>::from(current.types)),
+                    stats.unwrap_or_else(|| current.stats.map(StatValues::from)),
+                    abilities.unwrap_or_else(|| Vec::<SlotValue>::from(current.abilities)),
+                    req.revisions.or_else(|| Some(vec![current.revision])),
+                ),
+                Err(RetrieveError::NotFound) => return Err(Error::NotFound),
+                Err(RetrieveError::Unknown(e)) => return Err(Error::Unknown(e)),
+            },
+        };
+
+    match (
+        PokemonName::try_from(name),
+        PokemonTypes::try_from(types),
+        stats.map(BaseStats::try_from).transpose(),
+        PokemonAbilities::try_from(abilities),
+    ) {
+        (Ok(name), Ok(types), Ok(stats), Ok(abilities)) => match repo.update(
+            Pokemon::new(number, name, types)
+                .with_form(form)
+                .with_stats(stats)
+                .with_abilities(abilities),
+            revisions,
+            actor,
+        ) {
+            Ok(pokemon) => Ok(UpdateResponse {
+                number: u16::from(pokemon.number),
+                form: String::from(pokemon.form),
+                name: String::from(pokemon.name),
+                types: Vec::<String>::from(pokemon.types),
+                stats: pokemon.stats.map(StatValues::from),
+                abilities: Vec::<SlotValue>::from(pokemon.abilities),
+                revision: pokemon.revision,
+            }),
+            Err(UpdateError::NotFound) => Err(Error::NotFound),
+            Err(UpdateError::Stale(revision)) => Err(Error::PreconditionFailed(revision)),
+            Err(UpdateError::UnknownAbility(name)) => {
+                Err(Error::BadRequest(vec![FieldError::new(
+                    "abilities",
+                    ValidationError::UnknownAbility(String::from(name)),
+                )]))
+            }
+            Err(UpdateError::Unknown(e)) => Err(Error::Unknown(e)),
+        },
+        (name, types, stats, abilities) => Err(Error::BadRequest(
+            [
+                name.err().map(|e| FieldError::new("name", e)),
+                types.err().map(|e| FieldError::new("types", e)),
+                stats.err().map(|e| FieldError::new("stats", e)),
+                abilities.err().map(|e| FieldError::new("abilities", e)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+            Self {
+                number: u16::from(number),
+                form: None,
+                name: Some(String::from(name)),
+                types: Some(Vec::<String>::from(types)),
+                stats: Some(None),
+                abilities: Some(vec![]),
+                revisions: None,
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+
+        let res = execute(repo, req, &Actor::ci());
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::empty(),
+            PokemonTypes::pikachu(),
+        );
+
+        let res = execute(repo, req, &Actor::ci());
+
+        match res {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+
+        let res = execute(repo, req, &Actor::ci());
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_precondition_failed_error_when_the_revision_is_stale() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let req = Request {
+            name: None,
+            revisions: Some(vec![2]),
+            ..Request::new(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            )
+        };
+
+        match execute(repo, req, &Actor::ci()) {
+            Err(Error::PreconditionFailed(1)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_replace_the_name_and_types_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(
+            Pokemon::new(
+                PokemonNumber::pikachu(),
+                PokemonName::charmader(),
+                PokemonTypes::charmander(),
+            ),
+            &Actor::ci(),
+        );
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+
+        let res = execute(repo.clone(), req, &Actor::ci());
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res.name, String::from(PokemonName::pikachu()));
+                assert_eq!(res.types, Vec::<String>::from(PokemonTypes::pikachu()));
+            }
+            _ => unreachable!(),
+        }
+
+        match repo.fetch_one(PokemonNumber::pikachu(), FormSlug::default()) {
+            Ok(p) => assert_eq!(String::from(p.name), String::from(PokemonName::pikachu())),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_keep_the_missing_fields_on_a_partial_update() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let _ = repo.insert(
+            Pokemon::new(
+                PokemonNumber::pikachu(),
+                PokemonName::charmader(),
+                PokemonTypes::pikachu(),
+            )
+            .with_stats(Some(BaseStats::pikachu())),
+            &Actor::ci(),
+        );
+        let req = Request {
+            number: u16::from(PokemonNumber::pikachu()),
+            form: None,
+            name: Some(String::from(PokemonName::pikachu())),
+            types: None,
+            stats: None,
+            abilities: None,
+            revisions: None,
+        };
+
+        let res = execute(repo, req, &Actor::ci());
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.name, String::from(PokemonName::pikachu()));
+                assert_eq!(res.types, Vec::<String>::from(PokemonTypes::pikachu()));
+                assert_eq!(res.stats, Some(StatValues::from(BaseStats::pikachu())));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_not_merge_with_a_form_changed_in_the_meantime() {
+        let repo = Arc::new(InMemoryRepository::new().with_stale_reads());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.update(
+            Pokemon::pikachu().with_stats(Some(BaseStats::pikachu())),
+            None,
+            &Actor::ci(),
+        );
+        let req = Request {
+            number: u16::from(PokemonNumber::pikachu()),
+            form: None,
+            name: Some(String::from(PokemonName::charmader())),
+            types: None,
+            stats: None,
+            abilities: None,
+            revisions: None,
+        };
+
+        match execute(repo.clone(), req, &Actor::ci()) {
+            Err(Error::PreconditionFailed(2)) => {}
+            _ => unreachable!(),
+        }
+
+        match repo.fetch_one(PokemonNumber::pikachu(), FormSlug::default()) {
+            Ok(p) => assert_eq!(String::from(p.name), String::from(PokemonName::pikachu())),
+            _ => unreachable!(),
+        }
+    }
+}
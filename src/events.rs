@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::domain::entities::Pokemon;
+
+/// How many events are kept for the clients resuming the stream.
+pub const CAPACITY: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventKind {
+    /// Inserted, or taken back out of the trash.
+    Created,
+    Updated,
+    /// Moved to the trash.
+    Deleted,
+}
+
+impl From<EventKind> for String {
+    fn from(val: EventKind) -> Self {
+        String::from(match val {
+            EventKind::Created => "created",
+            EventKind::Updated => "updated",
+            EventKind::Deleted => "deleted",
+        })
+    }
+}
+
+/// A committed change of a form of a Pokemon, with the form as it is after
+/// the change, or as it was before it was deleted.
+#[derive(Clone)]
+pub struct Event {
+    pub id: u64,
+    pub kind: EventKind,
+    pub pokemon: Pokemon,
+}
+
+struct Buffer {
+    events: VecDeque<Event>,
+    last_id: u64,
+}
+
+/// The changes committed to the Pokedex, numbered from 1 in the order they
+/// are published. Only the latest ones are kept in memory, so a client
+/// resuming from an older event misses the ones in between.
+pub struct Events {
+    buffer: Mutex<Buffer>,
+    published: Condvar,
+    capacity: usize,
+}
+
+impl Default for Events {
+    fn default() -> Self {
+        Self::with_capacity(CAPACITY)
+    }
+}
+
+impl Events {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buffer: Mutex::new(Buffer {
+                events: VecDeque::with_capacity(capacity),
+                last_id: 0,
+            }),
+            published: Condvar::new(),
+            capacity,
+        }
+    }
+
+    /// Numbers the event, drops the oldest one when the buffer is full and
+    /// wakes up every client waiting for it.
+    pub fn publish(&self, kind: EventKind, pokemon: Pokemon) {
+        let mut buffer = lock(&self.buffer);
+        buffer.last_id += 1;
+        let event = Event {
+            id: buffer.last_id,
+            kind,
+            pokemon,
+        };
+
+        if buffer.events.len() == self.capacity {
+            buffer.events.pop_front();
+        }
+        buffer.events.push_back(event);
+        self.published.notify_all();
+    }
+
+    /// Tells where a client starts reading: after the event it last received,
+    /// or from now on without one. An id this process never published comes
+    /// from before a restart, every kept event being new to that client.
+    pub fn resume(&self, last_event_id: Option<u64>) -> u64 {
+        let last_id = lock(&self.buffer).last_id;
+        match last_event_id {
+            Some(id) if id <= last_id => id,
+            Some(_) => 0,
+            None => last_id,
+        }
+    }
+
+    /// Returns the kept events published after `after`, waiting up to
+    /// `timeout` for the next one when there is none yet.
+    pub fn wait(&self, after: u64, timeout: Duration) -> Vec<Event> {
+        let deadline = Instant::now() + timeout;
+        let mut buffer = lock(&self.buffer);
+
+        while buffer.last_id <= after {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return vec![];
+            }
+            buffer = self
+                .published
+                .wait_timeout(buffer, remaining)
+                .map(|(buffer, _)| buffer)
+                .unwrap_or_else(|e| e.into_inner().0);
+        }
+
+        buffer
+            .events
+            .iter()
+            .filter(|event| event.id > after)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Publishing cannot leave the buffer inconsistent, so a poisoned lock is
+/// still used rather than stopping every stream.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_should_only_keep_the_latest_events() {
+        let events = Events::with_capacity(2);
+        events.publish(EventKind::Created, Pokemon::pikachu());
+        events.publish(EventKind::Updated, Pokemon::pikachu());
+        events.publish(EventKind::Deleted, Pokemon::pikachu());
+
+        let kept = events.wait(0, Duration::ZERO);
+        assert_eq!(kept.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(kept[1].kind, EventKind::Deleted);
+    }
+
+    #[test]
+    fn it_should_resume_after_the_last_event_received() {
+        let events = Events::default();
+        events.publish(EventKind::Created, Pokemon::pikachu());
+        events.publish(EventKind::Created, Pokemon::charmander());
+
+        assert_eq!(events.resume(None), 2);
+        assert_eq!(events.resume(Some(1)), 1);
+        assert_eq!(events.resume(Some(7)), 0);
+        assert!(events.wait(2, Duration::from_millis(1)).is_empty());
+    }
+
+    #[test]
+    fn it_should_wake_up_a_waiting_client_on_publish() {
+        let events = std::sync::Arc::new(Events::default());
+        let publisher = events.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            publisher.publish(EventKind::Created, Pokemon::pikachu());
+        });
+
+        let received = events.wait(0, Duration::from_secs(5));
+        let _ = handle.join();
+        assert_eq!(received.len(), 1);
+    }
+}
@@ -0,0 +1,177 @@
+//! Structured logs written to stderr, either as human readable lines or as
+//! one JSON object per line carrying the fields of the enclosing spans.
+
+use std::fmt;
+use std::io::IsTerminal;
+use std::str::FromStr;
+
+use tracing::Subscriber;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::EnvFilter;
+
+pub const DEFAULT_LEVEL: &str = "info";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl TryFrom<&str> for LogFormat {
+    type Error = ();
+
+    fn try_from(val: &str) -> Result<Self, Self::Error> {
+        match val {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LoggingError {
+    InvalidLevel(String),
+}
+
+impl fmt::Display for LoggingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoggingError::InvalidLevel(level) => write!(f, "invalid log level '{}'", level),
+        }
+    }
+}
+
+impl std::error::Error for LoggingError {}
+
+/// Installs the logger of the whole process. `level` is either a level such as
+/// `debug` or a list of per-module directives such as `warn,pokedex::api=debug`.
+pub fn init(level: &str, format: LogFormat) -> Result<(), LoggingError> {
+    let subscriber = subscriber(level, format, std::io::stderr)?;
+    let _ = tracing::subscriber::set_global_default(subscriber);
+    Ok(())
+}
+
+fn subscriber<W>(
+    level: &str,
+    format: LogFormat,
+    writer: W,
+) -> Result<Box<dyn Subscriber + Send + Sync>, LoggingError>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let invalid = || LoggingError::InvalidLevel(String::from(level));
+    // A single word would otherwise be taken for the name of a module.
+    if !level.contains([',', '=']) {
+        LevelFilter::from_str(level).map_err(|_| invalid())?;
+    }
+    let filter = EnvFilter::try_new(level).map_err(|_| invalid())?;
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(std::io::stderr().is_terminal());
+
+    Ok(match format {
+        LogFormat::Text => Box::new(builder.finish()),
+        LogFormat::Json => Box::new(
+            builder
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .finish(),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    /// Collects the logs in memory.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    impl Buffer {
+        fn lines(&self) -> Vec<serde_json::Value> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn it_should_write_the_fields_of_every_enclosing_span_as_json() {
+        let buffer = Buffer::default();
+        let subscriber = match subscriber("debug", LogFormat::Json, buffer.clone()) {
+            Ok(subscriber) => subscriber,
+            _ => unreachable!(),
+        };
+
+        tracing::subscriber::with_default(subscriber, || {
+            let request = tracing::info_span!("request", request_id = "abc", method = "GET");
+            let _request = request.enter();
+            let _repository = tracing::debug_span!("repository", operation = "fetch_one").entered();
+            tracing::debug!("done");
+        });
+
+        let lines = buffer.lines();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["fields"]["message"], "done");
+        assert_eq!(lines[0]["span"]["operation"], "fetch_one");
+        assert_eq!(lines[0]["spans"][0]["request_id"], "abc");
+        assert_eq!(lines[0]["spans"][1]["name"], "repository");
+    }
+
+    #[test]
+    fn it_should_filter_out_the_events_below_the_level() {
+        let buffer = Buffer::default();
+        let subscriber = match subscriber("warn", LogFormat::Json, buffer.clone()) {
+            Ok(subscriber) => subscriber,
+            _ => unreachable!(),
+        };
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("ignored");
+            tracing::warn!("kept");
+        });
+
+        assert_eq!(buffer.lines().len(), 1);
+    }
+
+    #[test]
+    fn it_should_reject_an_invalid_level() {
+        assert!(matches!(
+            subscriber("loud", LogFormat::Text, io::sink),
+            Err(LoggingError::InvalidLevel(_))
+        ));
+        assert!(matches!(
+            subscriber("warn,pokedex=loud", LogFormat::Text, io::sink),
+            Err(LoggingError::InvalidLevel(_))
+        ));
+    }
+}
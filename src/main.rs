@@ -1,11 +1,22 @@
 use std::sync::Arc;
 
 use clap::Arg;
+use config::{FileConfig, Overrides, Settings};
+use events::Events;
+use metrics::Metrics;
+use repositories::instrumented::InstrumentedRepository;
+use repositories::migrations::SchemaStatus;
 use repositories::pokemon::{InMemoryRepository, Repository, SqliteRepository};
+use repositories::publishing::PublishingRepository;
+use tracing::warn;
 
 mod api;
 mod cli;
+mod config;
 mod domain;
+mod events;
+mod logging;
+mod metrics;
 mod repositories;
 
 #[macro_use]
@@ -19,30 +30,246 @@ fn main() {
         .version(crate_version!())
         .name(crate_name!())
         .author(crate_authors!())
+        .after_help(
+            "Settings are resolved from the command line first, then the POKEDEX_* \
+             environment variables, then the config file, then the defaults.",
+        )
         .arg(
             Arg::new("cli")
                 .long("cli")
-                .action(clap::ArgAction::SetFalse)
-                .help("Runs in CLI mode"),
+                .action(clap::ArgAction::SetTrue)
+                .help("Runs in interactive CLI mode"),
+        )
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .value_name("PATH")
+                .env("POKEDEX_CONFIG")
+                .global(true)
+                .help("Reads the settings from a TOML config file"),
+        )
+        .arg(
+            Arg::new("sqlite")
+                .long("sqlite")
+                .value_name("PATH")
+                .env("POKEDEX_SQLITE")
+                .global(true)
+                .help("Uses the sqlite database at PATH instead of an in-memory repository"),
+        )
+        .arg(
+            Arg::new("log-level")
+                .long("log-level")
+                .value_name("LEVEL")
+                .env("POKEDEX_LOG_LEVEL")
+                .global(true)
+                .help(format!(
+                    "Logs the events from LEVEL up, or per module as in warn,pokedex::api=debug [default: {}]",
+                    logging::DEFAULT_LEVEL
+                )),
+        )
+        .arg(
+            Arg::new("log-format")
+                .long("log-format")
+                .env("POKEDEX_LOG_FORMAT")
+                .global(true)
+                .value_parser(["text", "json"])
+                .help("Writes the logs as text lines or as JSON objects [default: text]"),
+        )
+        .arg(
+            Arg::new("host")
+                .long("host")
+                .env("POKEDEX_HOST")
+                .help(format!(
+                    "Address the server binds to [default: {}]",
+                    config::DEFAULT_HOST
+                )),
+        )
+        .arg(
+            Arg::new("port")
+                .long("port")
+                .env("POKEDEX_PORT")
+                .value_parser(value_parser!(u16))
+                .help(format!(
+                    "Port the server listens on [default: {}]",
+                    config::DEFAULT_PORT
+                )),
         )
-        .arg(Arg::new("sqlite").long("sqlite").value_name("PATH"))
+        .arg(
+            Arg::new("threads")
+                .long("threads")
+                .env("POKEDEX_THREADS")
+                .value_parser(value_parser!(usize))
+                .help("Number of worker threads of the server [default: 8 per CPU]"),
+        )
+        .arg(
+            Arg::new("no-auth")
+                .long("no-auth")
+                .env("POKEDEX_NO_AUTH")
+                .action(clap::ArgAction::SetTrue)
+                .help("Lets anyone change the Pokedex without an API key [default without --sqlite]"),
+        )
+        .arg(
+            Arg::new("migrate")
+                .long("migrate")
+                .action(clap::ArgAction::SetTrue)
+                .help("Applies pending schema migrations to the sqlite database and exits"),
+        )
+        .arg(
+            Arg::new("check-schema")
+                .long("check-schema")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("migrate")
+                .help("Checks whether the sqlite database schema is up to date and exits"),
+        )
+        .subcommands(cli::commands::subcommands())
         .get_matches();
 
-    let repo = build_repo(matches.get_one::<String>("sqlite"));
+    let file = match matches.get_one::<String>("config") {
+        Some(path) => FileConfig::load(path),
+        None => Ok(FileConfig::default()),
+    };
+
+    let overrides = Overrides {
+        host: matches.get_one::<String>("host").cloned(),
+        port: matches.get_one::<u16>("port").copied(),
+        threads: matches.get_one::<usize>("threads").copied(),
+        sqlite: matches.get_one::<String>("sqlite").cloned(),
+        log_level: matches.get_one::<String>("log-level").cloned(),
+        log_format: matches.get_one::<String>("log-format").cloned(),
+        auth: matches.get_flag("no-auth").then_some(false),
+    };
 
-    match matches.contains_id("cli") {
-        true => cli::run(repo.clone()),
-        false => api::serve("localhost:8000", repo),
+    let settings = match file.and_then(|file| Settings::resolve(overrides, file)) {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("Error while reading the settings: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(e) = logging::init(&settings.log_level, settings.log_format) {
+        eprintln!("Error while reading the settings: {}", e);
+        std::process::exit(2);
     }
+
+    if matches.get_flag("migrate") || matches.get_flag("check-schema") {
+        let path = match &settings.sqlite {
+            Some(path) => path,
+            None => {
+                eprintln!("--migrate and --check-schema need a sqlite database (--sqlite)");
+                std::process::exit(2);
+            }
+        };
+
+        return match matches.get_flag("migrate") {
+            true => migrate(path),
+            false => check_schema(path),
+        };
+    }
+
+    if matches.subcommand_name() == Some("keys") && settings.sqlite.is_none() {
+        eprintln!("keys needs a sqlite database (--sqlite)");
+        std::process::exit(2);
+    }
+
+    let metrics = Arc::new(Metrics::default());
+    let events = Arc::new(Events::default());
+    let repo: Arc<dyn Repository> = Arc::new(PublishingRepository::new(
+        Arc::new(InstrumentedRepository::new(
+            build_repo(settings.sqlite.as_ref()),
+            metrics.clone(),
+        )),
+        events.clone(),
+    ));
+
+    if let Some((name, sub_matches)) = matches.subcommand() {
+        std::process::exit(cli::commands::run(repo, name, sub_matches));
+    }
+
+    if matches.get_flag("cli") {
+        return cli::run(repo.clone());
+    }
+
+    match (settings.auth, &settings.sqlite) {
+        (false, _) => warn!("Authentication is disabled, anyone can change the Pokedex"),
+        (true, None) => warn!(
+            "API keys are only stored in a sqlite database, the in-memory Pokedex is read-only \
+             unless authentication is disabled"
+        ),
+        (true, Some(_)) => {}
+    }
+    api::serve(
+        &settings.address(),
+        settings.threads,
+        repo,
+        metrics,
+        events,
+        settings.auth,
+    )
 }
 
 fn build_repo(sqlite_value: Option<&String>) -> Arc<dyn Repository> {
     if let Some(path) = sqlite_value {
         match SqliteRepository::try_new(path) {
             Ok(repo) => return Arc::new(repo),
-            _ => panic!("Error while creating sqlite repo"),
+            Err(e) => {
+                eprintln!("Error while opening the sqlite database: {}", e);
+                std::process::exit(1);
+            }
         }
     }
 
     Arc::new(InMemoryRepository::new())
 }
+
+fn migrate(path: &str) {
+    match SqliteRepository::try_new(path) {
+        Ok(repo) => match repo.schema_status() {
+            Ok(SchemaStatus::UpToDate(version)) => {
+                println!("The database schema is at version {}", version)
+            }
+            _ => {
+                eprintln!("Error while reading the database schema version");
+                std::process::exit(1);
+            }
+        },
+        Err(e) => {
+            eprintln!("Error while migrating the sqlite database: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn check_schema(path: &str) {
+    let repo = match SqliteRepository::open(path) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error while opening the sqlite database: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match repo.schema_status() {
+        Ok(SchemaStatus::UpToDate(version)) => {
+            println!("The database schema is up to date (version {})", version)
+        }
+        Ok(SchemaStatus::Outdated { current, latest }) => {
+            println!(
+                "The database schema is outdated (version {}, latest {}), run with --migrate",
+                current, latest
+            );
+            std::process::exit(1);
+        }
+        Ok(SchemaStatus::Unsupported { current, latest }) => {
+            println!(
+                "The database schema (version {}) is newer than this binary supports (version {})",
+                current, latest
+            );
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Error while reading the database schema version: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
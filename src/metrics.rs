@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// The upper bounds, in seconds, of the buckets of every latency histogram.
+pub const BUCKETS: [f64; 12] = [
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+
+const HTTP_REQUESTS: &str = "pokedex_http_requests_total";
+const HTTP_REQUEST_DURATION: &str = "pokedex_http_request_duration_seconds";
+const REPOSITORY_OPERATIONS: &str = "pokedex_repository_operations_total";
+const REPOSITORY_OPERATION_DURATION: &str = "pokedex_repository_operation_duration_seconds";
+
+/// The name, type and help text of every metric, in exposition order.
+const FAMILIES: [(&str, &str, &str); 4] = [
+    (
+        HTTP_REQUESTS,
+        "counter",
+        "The HTTP requests answered, by route and status code.",
+    ),
+    (
+        HTTP_REQUEST_DURATION,
+        "histogram",
+        "The time taken to answer an HTTP request, by route.",
+    ),
+    (
+        REPOSITORY_OPERATIONS,
+        "counter",
+        "The repository operations, by operation and result.",
+    ),
+    (
+        REPOSITORY_OPERATION_DURATION,
+        "histogram",
+        "The time taken by a repository operation, by operation.",
+    ),
+];
+
+type Labels = Vec<(&'static str, String)>;
+
+#[derive(Default)]
+struct Histogram {
+    buckets: [u64; BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        for (bound, count) in BUCKETS.iter().zip(self.buckets.iter_mut()) {
+            if seconds <= *bound {
+                *count += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+/// The counters and histograms of the server, kept in memory and exposed in
+/// the Prometheus text format at `GET /metrics`.
+#[derive(Default)]
+pub struct Metrics {
+    counters: Mutex<BTreeMap<(&'static str, Labels), u64>>,
+    histograms: Mutex<BTreeMap<(&'static str, Labels), Histogram>>,
+}
+
+impl Metrics {
+    /// Records an answered request, `route` being the path template it matched
+    /// so that the number of series stays bounded.
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        let labels = vec![
+            ("method", String::from(method)),
+            ("route", String::from(route)),
+        ];
+
+        let mut with_status = labels.clone();
+        with_status.push(("status", status.to_string()));
+        self.increment(HTTP_REQUESTS, with_status);
+        self.observe(HTTP_REQUEST_DURATION, labels, duration);
+    }
+
+    /// Records a repository operation, `result` telling a success from an
+    /// expected answer such as a conflict and from a failure.
+    pub fn observe_operation(&self, operation: &str, result: &str, duration: Duration) {
+        let labels = vec![("operation", String::from(operation))];
+
+        let mut with_result = labels.clone();
+        with_result.push(("result", String::from(result)));
+        self.increment(REPOSITORY_OPERATIONS, with_result);
+        self.observe(REPOSITORY_OPERATION_DURATION, labels, duration);
+    }
+
+    /// Writes every metric in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let counters = lock(&self.counters);
+        let histograms = lock(&self.histograms);
+        let mut out = String::new();
+
+        for (family, kind, help) in FAMILIES {
+            let _ = writeln!(out, "# HELP {} {}", family, help);
+            let _ = writeln!(out, "# TYPE {} {}", family, kind);
+
+            for ((_, labels), value) in counters.iter().filter(|((name, _), _)| *name == family) {
+                let _ = writeln!(out, "{}{} {}", family, format_labels(labels), value);
+            }
+
+            for ((_, labels), histogram) in
+                histograms.iter().filter(|((name, _), _)| *name == family)
+            {
+                for (bound, count) in BUCKETS.iter().zip(histogram.buckets) {
+                    let mut labels = labels.clone();
+                    labels.push(("le", bound.to_string()));
+                    let _ = writeln!(out, "{}_bucket{} {}", family, format_labels(&labels), count);
+                }
+                let mut labels_inf = labels.clone();
+                labels_inf.push(("le", String::from("+Inf")));
+                let _ = writeln!(
+                    out,
+                    "{}_bucket{} {}",
+                    family,
+                    format_labels(&labels_inf),
+                    histogram.count
+                );
+                let _ = writeln!(
+                    out,
+                    "{}_sum{} {}",
+                    family,
+                    format_labels(labels),
+                    histogram.sum
+                );
+                let _ = writeln!(
+                    out,
+                    "{}_count{} {}",
+                    family,
+                    format_labels(labels),
+                    histogram.count
+                );
+            }
+        }
+
+        out
+    }
+
+    fn increment(&self, name: &'static str, labels: Labels) {
+        *lock(&self.counters).entry((name, labels)).or_default() += 1;
+    }
+
+    fn observe(&self, name: &'static str, labels: Labels, duration: Duration) {
+        lock(&self.histograms)
+            .entry((name, labels))
+            .or_default()
+            .observe(duration);
+    }
+}
+
+/// A panic while recording cannot leave the maps inconsistent, so a poisoned
+/// lock is still used rather than failing every later request.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn format_labels(labels: &Labels) -> String {
+    let labels = labels
+        .iter()
+        .map(|(name, value)| {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{}=\"{}\"", name, value)
+        })
+        .collect::<Vec<_>>();
+
+    format!("{{{}}}", labels.join(","))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_should_count_the_requests_by_route_and_status() {
+        let metrics = Metrics::default();
+        metrics.observe_request("GET", "/{number}", 200, Duration::from_millis(2));
+        metrics.observe_request("GET", "/{number}", 200, Duration::from_millis(3));
+        metrics.observe_request("GET", "/{number}", 404, Duration::from_millis(1));
+
+        let out = metrics.render();
+
+        assert!(out.contains(
+            "pokedex_http_requests_total{method=\"GET\",route=\"/{number}\",status=\"200\"} 2\n"
+        ));
+        assert!(out.contains(
+            "pokedex_http_requests_total{method=\"GET\",route=\"/{number}\",status=\"404\"} 1\n"
+        ));
+        assert!(out.contains(
+            "pokedex_http_request_duration_seconds_count{method=\"GET\",route=\"/{number}\"} 3\n"
+        ));
+    }
+
+    #[test]
+    fn it_should_write_cumulative_histogram_buckets() {
+        let metrics = Metrics::default();
+        metrics.observe_operation("fetch_one", "ok", Duration::from_millis(2));
+        metrics.observe_operation("fetch_one", "ok", Duration::from_millis(20));
+
+        let out = metrics.render();
+
+        for (bound, count) in [("0.001", 0), ("0.0025", 1), ("0.025", 2), ("+Inf", 2)] {
+            assert!(out.contains(&format!(
+                "pokedex_repository_operation_duration_seconds_bucket{{operation=\"fetch_one\",le=\"{}\"}} {}\n",
+                bound, count
+            )));
+        }
+        assert!(out.contains(
+            "pokedex_repository_operation_duration_seconds_sum{operation=\"fetch_one\"} 0.022\n"
+        ));
+    }
+
+    #[test]
+    fn it_should_describe_every_metric_even_before_any_observation() {
+        let out = Metrics::default().render();
+
+        assert_eq!(out.lines().count(), FAMILIES.len() * 2);
+        assert!(out.starts_with(
+            "# HELP pokedex_http_requests_total The HTTP requests answered, by route and status code.\n\
+             # TYPE pokedex_http_requests_total counter\n"
+        ));
+    }
+
+    #[test]
+    fn it_should_escape_the_label_values() {
+        assert_eq!(
+            format_labels(&vec![("route", String::from("a\"b\\c\nd"))]),
+            "{route=\"a\\\"b\\\\c\\nd\"}"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! The snapshots of the audit log, stored as JSON so that a form can be
+//! rebuilt as it was whatever the schema has become since.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{
+    BaseStats, FormSlug, Pokemon, PokemonAbilities, PokemonName, PokemonNumber, PokemonTypes,
+    SlotValue, StatValues,
+};
+use crate::repositories::pokemon::Source;
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    number: u16,
+    form: String,
+    name: String,
+    types: Vec<String>,
+    /// In HP/Atk/Def/SpA/SpD/Spe order.
+    stats: Option<[u16; 6]>,
+    abilities: Vec<(String, String)>,
+    revision: u32,
+}
+
+pub fn encode(pokemon: &Pokemon) -> String {
+    let pokemon = pokemon.clone();
+    let snapshot = Snapshot {
+        number: u16::from(pokemon.number),
+        form: String::from(pokemon.form),
+        name: String::from(pokemon.name),
+        types: Vec::<String>::from(pokemon.types),
+        stats: pokemon
+            .stats
+            .map(|stats| <[u16; 6]>::from(StatValues::from(stats))),
+        abilities: Vec::<SlotValue>::from(pokemon.abilities)
+            .into_iter()
+            .map(|ability| (ability.slot, ability.name))
+            .collect(),
+        revision: pokemon.revision,
+    };
+
+    serde_json::to_string(&snapshot).unwrap_or_default()
+}
+
+/// Reads the revision of a snapshot without rebuilding the form.
+pub fn revision(json: &str) -> Result<u32, Source> {
+    #[derive(Deserialize)]
+    struct Revision {
+        revision: u32,
+    }
+
+    Ok(serde_json::from_str::<Revision>(json)?.revision)
+}
+
+/// Rebuilds a form from its snapshot, which fails if it was written by hand
+/// and breaks the entity rules.
+pub fn decode(json: &str) -> Result<Pokemon, Source> {
+    let snapshot = serde_json::from_str::<Snapshot>(json)?;
+    let stats = match snapshot.stats {
+        Some(values) => Some(BaseStats::try_from(StatValues::from(values))?),
+        None => None,
+    };
+    let abilities = snapshot
+        .abilities
+        .into_iter()
+        .map(|(slot, name)| SlotValue { slot, name })
+        .collect::<Vec<_>>();
+
+    Ok(Pokemon::new(
+        PokemonNumber::try_from(snapshot.number)?,
+        PokemonName::try_from(snapshot.name)?,
+        PokemonTypes::try_from(snapshot.types)?,
+    )
+    .with_form(FormSlug::try_from(snapshot.form)?)
+    .with_stats(stats)
+    .with_abilities(PokemonAbilities::try_from(abilities)?)
+    .with_revision(snapshot.revision))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_should_rebuild_a_form_from_its_snapshot() {
+        let pikachu = Pokemon::pikachu()
+            .with_form(FormSlug::alola())
+            .with_stats(Some(BaseStats::pikachu()))
+            .with_abilities(PokemonAbilities::pikachu())
+            .with_revision(3);
+
+        match decode(&encode(&pikachu)) {
+            Ok(pokemon) => {
+                assert_eq!(pokemon.form, FormSlug::alola());
+                assert_eq!(pokemon.stats, Some(BaseStats::pikachu()));
+                assert_eq!(pokemon.abilities, PokemonAbilities::pikachu());
+                assert_eq!(pokemon.revision, 3);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use tracing::{debug, debug_span};
+
+use crate::domain::entities::{
+    Ability, AbilityName, Actor, ApiKey, AuditEvent, DeletedPokemon, Evolution, FormSlug, KeyName,
+    LearnsetEntry, Move, MoveName, Pokemon, PokemonNumber,
+};
+use crate::metrics::Metrics;
+use crate::repositories::pokemon::{
+    DeleteError, FetchAllQuery, ImportError, ImportOutcome, InsertError, LearnsetQuery, OnConflict,
+    Repository, RestoreError, RetrieveAllError, RetrieveError, UpdateError,
+};
+
+/// Times every operation of the repository it wraps, whichever it is, and runs
+/// it in a span nested in the one of the request being served.
+pub struct InstrumentedRepository {
+    inner: Arc<dyn Repository>,
+    metrics: Arc<Metrics>,
+}
+
+impl InstrumentedRepository {
+    pub fn new(inner: Arc<dyn Repository>, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    fn observe<T, E: Outcome>(
+        &self,
+        operation: &'static str,
+        f: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E> {
+        let _span = debug_span!("repository", operation).entered();
+        let start = Instant::now();
+        let res = f();
+        let duration = start.elapsed();
+        let result = match &res {
+            Ok(_) => "ok",
+            Err(e) if e.is_failure() => "error",
+            Err(_) => "rejected",
+        };
+
+        debug!(
+            result,
+            duration_ms = duration.as_secs_f64() * 1000.0,
+            "repository call"
+        );
+        self.metrics.observe_operation(operation, result, duration);
+        res
+    }
+}
+
+/// Tells an unexpected failure from an expected answer, such as a missing
+/// Pokemon or a conflict, which the repository did serve.
+trait Outcome {
+    fn is_failure(&self) -> bool;
+}
+
+impl Outcome for InsertError {
+    fn is_failure(&self) -> bool {
+        matches!(self, InsertError::Unknown(_))
+    }
+}
+
+impl Outcome for ImportError {
+    fn is_failure(&self) -> bool {
+        matches!(self, ImportError::Unknown(_))
+    }
+}
+
+impl Outcome for RetrieveAllError {
+    fn is_failure(&self) -> bool {
+        matches!(self, RetrieveAllError::Unknown(_))
+    }
+}
+
+impl Outcome for RetrieveError {
+    fn is_failure(&self) -> bool {
+        matches!(self, RetrieveError::Unknown(_))
+    }
+}
+
+impl Outcome for DeleteError {
+    fn is_failure(&self) -> bool {
+        matches!(self, DeleteError::Unknown(_))
+    }
+}
+
+impl Outcome for UpdateError {
+    fn is_failure(&self) -> bool {
+        matches!(self, UpdateError::Unknown(_))
+    }
+}
+
+impl Outcome for RestoreError {
+    fn is_failure(&self) -> bool {
+        matches!(self, RestoreError::Unknown(_))
+    }
+}
+
+impl Repository for InstrumentedRepository {
+    fn insert(&self, pokemon: Pokemon, actor: &Actor) -> Result<Pokemon, InsertError> {
+        self.observe("insert", || self.inner.insert(pokemon, actor))
+    }
+
+    fn fetch_all(&self, query: FetchAllQuery) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        self.observe("fetch_all", || self.inner.fetch_all(query))
+    }
+
+    fn fetch_one(&self, number: PokemonNumber, form: FormSlug) -> Result<Pokemon, RetrieveError> {
+        self.observe("fetch_one", || self.inner.fetch_one(number, form))
+    }
+
+    fn search(&self, query: String, limit: u32) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        self.observe("search", || self.inner.search(query, limit))
+    }
+
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        self.observe("fetch_forms", || self.inner.fetch_forms(number))
+    }
+
+    fn fetch_ability_holders(&self, name: AbilityName) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        self.observe("fetch_ability_holders", || {
+            self.inner.fetch_ability_holders(name)
+        })
+    }
+
+    fn delete_pokemon(
+        &self,
+        number: PokemonNumber,
+        form: FormSlug,
+        expected: Option<Vec<u32>>,
+        actor: &Actor,
+    ) -> Result<Vec<Pokemon>, DeleteError> {
+        self.observe("delete_pokemon", || {
+            self.inner.delete_pokemon(number, form, expected, actor)
+        })
+    }
+
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, RetrieveAllError> {
+        self.observe("fetch_trash", || self.inner.fetch_trash())
+    }
+
+    fn restore(
+        &self,
+        number: PokemonNumber,
+        form: FormSlug,
+        actor: &Actor,
+    ) -> Result<Vec<Pokemon>, RestoreError> {
+        self.observe("restore", || self.inner.restore(number, form, actor))
+    }
+
+    fn purge(&self, before: String, actor: &Actor) -> Result<Vec<Pokemon>, DeleteError> {
+        self.observe("purge", || self.inner.purge(before, actor))
+    }
+
+    fn update(
+        &self,
+        pokemon: Pokemon,
+        expected: Option<Vec<u32>>,
+        actor: &Actor,
+    ) -> Result<Pokemon, UpdateError> {
+        self.observe("update", || self.inner.update(pokemon, expected, actor))
+    }
+
+    fn import(
+        &self,
+        pokemons: Vec<Pokemon>,
+        on_conflict: OnConflict,
+        actor: &Actor,
+    ) -> Result<Vec<ImportOutcome>, ImportError> {
+        self.observe("import", || self.inner.import(pokemons, on_conflict, actor))
+    }
+
+    fn fetch_history(&self, number: PokemonNumber) -> Result<Vec<AuditEvent>, RetrieveAllError> {
+        self.observe("fetch_history", || self.inner.fetch_history(number))
+    }
+
+    fn fetch_audit(
+        &self,
+        since: Option<String>,
+        limit: u32,
+    ) -> Result<Vec<AuditEvent>, RetrieveAllError> {
+        self.observe("fetch_audit", || self.inner.fetch_audit(since, limit))
+    }
+
+    fn insert_ability(&self, ability: Ability) -> Result<Ability, InsertError> {
+        self.observe("insert_ability", || self.inner.insert_ability(ability))
+    }
+
+    fn fetch_abilities(&self) -> Result<Vec<Ability>, RetrieveAllError> {
+        self.observe("fetch_abilities", || self.inner.fetch_abilities())
+    }
+
+    fn fetch_ability(&self, name: AbilityName) -> Result<Ability, RetrieveError> {
+        self.observe("fetch_ability", || self.inner.fetch_ability(name))
+    }
+
+    fn insert_evolution(&self, evolution: Evolution) -> Result<Evolution, InsertError> {
+        self.observe("insert_evolution", || {
+            self.inner.insert_evolution(evolution)
+        })
+    }
+
+    fn fetch_evolutions(&self) -> Result<Vec<Evolution>, RetrieveAllError> {
+        self.observe("fetch_evolutions", || self.inner.fetch_evolutions())
+    }
+
+    fn insert_move(&self, pokemon_move: Move) -> Result<Move, InsertError> {
+        self.observe("insert_move", || self.inner.insert_move(pokemon_move))
+    }
+
+    fn fetch_moves(&self) -> Result<Vec<Move>, RetrieveAllError> {
+        self.observe("fetch_moves", || self.inner.fetch_moves())
+    }
+
+    fn fetch_move(&self, name: MoveName) -> Result<Move, RetrieveError> {
+        self.observe("fetch_move", || self.inner.fetch_move(name))
+    }
+
+    fn insert_learnset_entry(
+        &self,
+        number: PokemonNumber,
+        entry: LearnsetEntry,
+    ) -> Result<LearnsetEntry, InsertError> {
+        self.observe("insert_learnset_entry", || {
+            self.inner.insert_learnset_entry(number, entry)
+        })
+    }
+
+    fn fetch_learnset(
+        &self,
+        number: PokemonNumber,
+        query: LearnsetQuery,
+    ) -> Result<Vec<LearnsetEntry>, RetrieveAllError> {
+        self.observe("fetch_learnset", || {
+            self.inner.fetch_learnset(number, query)
+        })
+    }
+
+    fn insert_key(&self, key: ApiKey) -> Result<ApiKey, InsertError> {
+        self.observe("insert_key", || self.inner.insert_key(key))
+    }
+
+    fn fetch_keys(&self) -> Result<Vec<ApiKey>, RetrieveAllError> {
+        self.observe("fetch_keys", || self.inner.fetch_keys())
+    }
+
+    fn fetch_key(&self, hash: String) -> Result<ApiKey, RetrieveError> {
+        self.observe("fetch_key", || self.inner.fetch_key(hash))
+    }
+
+    fn revoke_key(&self, name: KeyName) -> Result<(), DeleteError> {
+        self.observe("revoke_key", || self.inner.revoke_key(name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_record_the_result_of_every_operation() {
+        let metrics = Arc::new(Metrics::default());
+        let repo =
+            InstrumentedRepository::new(Arc::new(InMemoryRepository::new()), metrics.clone());
+
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.fetch_one(PokemonNumber::pikachu(), FormSlug::default());
+
+        let out = metrics.render();
+        assert!(out.contains(
+            "pokedex_repository_operations_total{operation=\"insert\",result=\"ok\"} 1\n"
+        ));
+        assert!(out.contains(
+            "pokedex_repository_operations_total{operation=\"insert\",result=\"rejected\"} 1\n"
+        ));
+        assert!(out.contains(
+            "pokedex_repository_operation_duration_seconds_count{operation=\"fetch_one\"} 1\n"
+        ));
+    }
+
+    #[test]
+    fn it_should_record_a_failure_as_an_error() {
+        let metrics = Arc::new(Metrics::default());
+        let repo = InstrumentedRepository::new(
+            Arc::new(InMemoryRepository::new().with_error()),
+            metrics.clone(),
+        );
+
+        match repo.fetch_all(FetchAllQuery::default()) {
+            Err(RetrieveAllError::Unknown(_)) => {}
+            _ => unreachable!(),
+        }
+        assert!(metrics.render().contains(
+            "pokedex_repository_operations_total{operation=\"fetch_all\",result=\"error\"} 1\n"
+        ));
+    }
+}
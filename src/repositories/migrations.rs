@@ -0,0 +1,178 @@
+use std::error::Error;
+use std::fmt;
+
+use rusqlite::Connection;
+
+/// Embedded schema migrations, applied in order. The position of a migration in
+/// this list is its version, as tracked by `PRAGMA user_version`.
+const MIGRATIONS: &[&str] = &[
+    include_str!("migrations/0001_create_pokemons.sql"),
+    include_str!("migrations/0002_add_base_stats.sql"),
+    include_str!("migrations/0003_create_abilities.sql"),
+    include_str!("migrations/0004_create_evolutions.sql"),
+    include_str!("migrations/0005_create_learnsets.sql"),
+    include_str!("migrations/0006_create_forms.sql"),
+    include_str!("migrations/0007_create_api_keys.sql"),
+    include_str!("migrations/0008_create_pokemons_search.sql"),
+    include_str!("migrations/0009_add_revisions.sql"),
+    include_str!("migrations/0010_create_audit_events.sql"),
+    include_str!("migrations/0011_add_trash.sql"),
+];
+
+pub const LATEST_VERSION: u32 = MIGRATIONS.len() as u32;
+
+#[derive(Debug, PartialEq)]
+pub enum SchemaStatus {
+    UpToDate(u32),
+    Outdated { current: u32, latest: u32 },
+    Unsupported { current: u32, latest: u32 },
+}
+
+#[derive(Debug)]
+pub enum MigrationError {
+    Unsupported { current: u32, latest: u32 },
+    Poisoned(String),
+    Sqlite(rusqlite::Error),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Unsupported { current, latest } => write!(
+                f,
+                "the database schema (version {}) is newer than this binary supports (version {})",
+                current, latest
+            ),
+            MigrationError::Poisoned(message) => write!(f, "{}", message),
+            MigrationError::Sqlite(_) => write!(f, "the database schema could not be migrated"),
+        }
+    }
+}
+
+impl Error for MigrationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MigrationError::Sqlite(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for MigrationError {
+    fn from(e: rusqlite::Error) -> Self {
+        MigrationError::Sqlite(e)
+    }
+}
+
+pub fn current_version(connection: &Connection) -> Result<u32, rusqlite::Error> {
+    connection.query_row("pragma user_version", [], |row| row.get::<usize, u32>(0))
+}
+
+pub fn status(connection: &Connection) -> Result<SchemaStatus, rusqlite::Error> {
+    let current = current_version(connection)?;
+
+    Ok(match current {
+        current if current == LATEST_VERSION => SchemaStatus::UpToDate(current),
+        current if current < LATEST_VERSION => SchemaStatus::Outdated {
+            current,
+            latest: LATEST_VERSION,
+        },
+        current => SchemaStatus::Unsupported {
+            current,
+            latest: LATEST_VERSION,
+        },
+    })
+}
+
+/// Applies every pending migration, each one in its own transaction, and
+/// returns the resulting schema version.
+pub fn migrate(connection: &mut Connection) -> Result<u32, MigrationError> {
+    let current = current_version(connection)?;
+
+    if current > LATEST_VERSION {
+        return Err(MigrationError::Unsupported {
+            current,
+            latest: LATEST_VERSION,
+        });
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let version = index as u32 + 1;
+
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(migration)?;
+        transaction.execute_batch(&format!("pragma user_version = {}", version))?;
+        transaction.commit()?;
+    }
+
+    Ok(LATEST_VERSION)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_should_report_an_empty_database_as_outdated() {
+        let connection = Connection::open_in_memory().unwrap();
+
+        assert_eq!(
+            status(&connection),
+            Ok(SchemaStatus::Outdated {
+                current: 0,
+                latest: LATEST_VERSION
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_apply_every_migration_and_be_idempotent() {
+        let mut connection = Connection::open_in_memory().unwrap();
+
+        assert_eq!(migrate(&mut connection).ok(), Some(LATEST_VERSION));
+        assert_eq!(migrate(&mut connection).ok(), Some(LATEST_VERSION));
+        assert_eq!(
+            status(&connection),
+            Ok(SchemaStatus::UpToDate(LATEST_VERSION))
+        );
+    }
+
+    #[test]
+    fn it_should_adopt_a_database_created_by_hand() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "create table pokemons (number integer primary key, name text);
+                 insert into pokemons (number, name) values (25, 'Pikachu');",
+            )
+            .unwrap();
+
+        assert_eq!(migrate(&mut connection).ok(), Some(LATEST_VERSION));
+        assert_eq!(
+            connection.query_row("select name from pokemons where number = 25", [], |row| {
+                row.get::<usize, String>(0)
+            }),
+            Ok(String::from("Pikachu"))
+        );
+    }
+
+    #[test]
+    fn it_should_refuse_a_database_newer_than_the_binary() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(&format!("pragma user_version = {}", LATEST_VERSION + 1))
+            .unwrap();
+
+        assert!(matches!(
+            migrate(&mut connection),
+            Err(MigrationError::Unsupported { .. })
+        ));
+        assert_eq!(
+            status(&connection),
+            Ok(SchemaStatus::Unsupported {
+                current: LATEST_VERSION + 1,
+                latest: LATEST_VERSION
+            })
+        );
+    }
+}
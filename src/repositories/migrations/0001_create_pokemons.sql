@@ -0,0 +1,11 @@
+create table if not exists pokemons (
+number integer primary key,
+name text
+);
+
+create table if not exists types (
+pokemon_number integer,
+name text,
+foreign key (pokemon_number) references pokemons (number) on delete cascade,
+primary key (pokemon_number, name)
+);
@@ -0,0 +1,6 @@
+alter table pokemons add column hp integer;
+alter table pokemons add column attack integer;
+alter table pokemons add column defense integer;
+alter table pokemons add column special_attack integer;
+alter table pokemons add column special_defense integer;
+alter table pokemons add column speed integer;
@@ -0,0 +1,15 @@
+create table if not exists abilities (
+name text primary key,
+description text not null
+);
+
+create table if not exists pokemon_abilities (
+pokemon_number integer,
+slot text check (slot in ('primary', 'secondary', 'hidden')),
+ability_name text,
+foreign key (pokemon_number) references pokemons (number) on delete cascade,
+foreign key (ability_name) references abilities (name),
+primary key (pokemon_number, slot)
+);
+
+create index if not exists pokemon_abilities_by_ability on pokemon_abilities (ability_name);
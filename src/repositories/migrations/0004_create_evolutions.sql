@@ -0,0 +1,12 @@
+create table if not exists evolutions (
+from_number integer not null,
+to_number integer not null,
+trigger text not null check (trigger in ('level_up', 'item', 'trade', 'friendship')),
+level integer,
+item text,
+foreign key (from_number) references pokemons (number) on delete cascade,
+foreign key (to_number) references pokemons (number) on delete cascade,
+primary key (from_number, to_number)
+);
+
+create unique index if not exists evolutions_single_parent on evolutions (to_number);
@@ -0,0 +1,19 @@
+create table if not exists moves (
+name text primary key,
+type text not null,
+category text not null check (category in ('physical', 'special', 'status')),
+power integer,
+accuracy integer,
+pp integer not null
+);
+
+create table if not exists learnsets (
+pokemon_number integer,
+move_name text,
+method text check (method in ('level', 'tm', 'egg', 'tutor')),
+level integer,
+generation integer,
+foreign key (pokemon_number) references pokemons (number) on delete cascade,
+foreign key (move_name) references moves (name),
+primary key (pokemon_number, generation, method, move_name)
+);
@@ -0,0 +1,47 @@
+create table if not exists forms (
+pokemon_number integer not null,
+form text not null check (form <> 'default'),
+name text not null,
+hp integer,
+attack integer,
+defense integer,
+special_attack integer,
+special_defense integer,
+speed integer,
+foreign key (pokemon_number) references pokemons (number) on delete cascade,
+primary key (pokemon_number, form)
+);
+
+-- Types and abilities now belong to a form, the default one being stored in
+-- pokemons and every other one in forms.
+alter table types rename to types_without_forms;
+
+create table types (
+pokemon_number integer,
+form text not null default 'default',
+name text,
+foreign key (pokemon_number) references pokemons (number) on delete cascade,
+primary key (pokemon_number, form, name)
+);
+
+insert into types (pokemon_number, name) select pokemon_number, name from types_without_forms;
+drop table types_without_forms;
+
+alter table pokemon_abilities rename to pokemon_abilities_without_forms;
+drop index if exists pokemon_abilities_by_ability;
+
+create table pokemon_abilities (
+pokemon_number integer,
+form text not null default 'default',
+slot text check (slot in ('primary', 'secondary', 'hidden')),
+ability_name text,
+foreign key (pokemon_number) references pokemons (number) on delete cascade,
+foreign key (ability_name) references abilities (name),
+primary key (pokemon_number, form, slot)
+);
+
+insert into pokemon_abilities (pokemon_number, slot, ability_name)
+select pokemon_number, slot, ability_name from pokemon_abilities_without_forms;
+drop table pokemon_abilities_without_forms;
+
+create index if not exists pokemon_abilities_by_ability on pokemon_abilities (ability_name);
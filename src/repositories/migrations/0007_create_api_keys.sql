@@ -0,0 +1,8 @@
+create table if not exists api_keys (
+name text primary key,
+role text not null check (role in ('reader', 'editor', 'admin')),
+prefix text not null,
+hash text not null unique,
+created_at text not null,
+revoked integer not null default 0
+);
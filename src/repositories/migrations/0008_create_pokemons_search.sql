@@ -0,0 +1,23 @@
+-- A trigram index of the names of the default forms, kept in sync with
+-- pokemons by the triggers below.
+create virtual table if not exists pokemons_search using fts5 (
+name,
+content = 'pokemons',
+content_rowid = 'number',
+tokenize = 'trigram'
+);
+
+insert into pokemons_search (pokemons_search) values ('rebuild');
+
+create trigger if not exists pokemons_search_insert after insert on pokemons begin
+insert into pokemons_search (rowid, name) values (new.number, new.name);
+end;
+
+create trigger if not exists pokemons_search_delete after delete on pokemons begin
+insert into pokemons_search (pokemons_search, rowid, name) values ('delete', old.number, old.name);
+end;
+
+create trigger if not exists pokemons_search_update after update of name on pokemons begin
+insert into pokemons_search (pokemons_search, rowid, name) values ('delete', old.number, old.name);
+insert into pokemons_search (rowid, name) values (new.number, new.name);
+end;
@@ -0,0 +1,4 @@
+-- Every write of a form increments its revision, which the API exposes as an
+-- entity tag for conditional requests.
+alter table pokemons add column revision integer not null default 1;
+alter table forms add column revision integer not null default 1;
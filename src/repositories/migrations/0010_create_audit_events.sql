@@ -0,0 +1,24 @@
+-- Every change of a form, kept after the form is deleted, hence no foreign
+-- key. The snapshots are JSON and the triggers below make the log append-only.
+create table if not exists audit_events (
+id integer primary key autoincrement,
+action text not null check (action in ('insert', 'update', 'delete')),
+pokemon_number integer not null,
+form text not null,
+actor text not null,
+request_id text,
+timestamp text not null,
+before text,
+after text
+);
+
+create index if not exists audit_events_by_pokemon on audit_events (pokemon_number, id);
+create index if not exists audit_events_by_timestamp on audit_events (timestamp);
+
+create trigger if not exists audit_events_no_update before update on audit_events begin
+select raise(abort, 'audit events are immutable');
+end;
+
+create trigger if not exists audit_events_no_delete before delete on audit_events begin
+select raise(abort, 'audit events are immutable');
+end;
@@ -0,0 +1,39 @@
+-- Deleted forms are kept in the trash, flagged with the time they were deleted,
+-- until they are restored or purged.
+alter table pokemons add column deleted_at text;
+alter table forms add column deleted_at text;
+
+create index if not exists pokemons_by_deleted_at on pokemons (deleted_at) where deleted_at is not null;
+create index if not exists forms_by_deleted_at on forms (deleted_at) where deleted_at is not null;
+
+-- Restores and purges are recorded too, which the check on the action must
+-- allow, so the log is copied to a new table.
+drop trigger if exists audit_events_no_update;
+drop trigger if exists audit_events_no_delete;
+alter table audit_events rename to audit_events_before_trash;
+
+create table audit_events (
+id integer primary key autoincrement,
+action text not null check (action in ('insert', 'update', 'delete', 'restore', 'purge')),
+pokemon_number integer not null,
+form text not null,
+actor text not null,
+request_id text,
+timestamp text not null,
+before text,
+after text
+);
+
+insert into audit_events select * from audit_events_before_trash;
+drop table audit_events_before_trash;
+
+create index if not exists audit_events_by_pokemon on audit_events (pokemon_number, id);
+create index if not exists audit_events_by_timestamp on audit_events (timestamp);
+
+create trigger if not exists audit_events_no_update before update on audit_events begin
+select raise(abort, 'audit events are immutable');
+end;
+
+create trigger if not exists audit_events_no_delete before delete on audit_events begin
+select raise(abort, 'audit events are immutable');
+end;
@@ -1 +1,6 @@
+pub mod audit;
+pub mod instrumented;
+pub mod migrations;
 pub mod pokemon;
+pub mod publishing;
+pub mod search;
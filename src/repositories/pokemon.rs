@@ -1,53 +1,482 @@
-use std::sync::{Mutex, MutexGuard};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
-use rusqlite::{params, params_from_iter, Connection, OpenFlags};
+use rusqlite::{
+    params, params_from_iter, types::Value, Connection, OpenFlags, OptionalExtension, Row,
+};
 
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use crate::domain::entities::{
+    now, Ability, AbilityName, Actor, ApiKey, AuditAction, AuditEvent, BaseStats, DeletedPokemon,
+    Evolution, EvolutionTrigger, FormSlug, Generation, KeyName, LearnMethod, LearnsetEntry, Level,
+    Move, MoveAccuracy, MoveCategory, MoveName, MovePower, Pokemon, PokemonAbilities, PokemonName,
+    PokemonNumber, PokemonType, PokemonTypes, PowerPoints, Role, SlotValue, StatValues,
+    TriggerValue, ValidationError,
+};
+use crate::repositories::migrations::{self, MigrationError, SchemaStatus};
+use crate::repositories::{audit, search};
 
+/// The underlying cause of an unexpected repository failure.
+pub type Source = Box<dyn Error + Send + Sync>;
+
+#[derive(Debug)]
 pub enum InsertError {
     Conflict,
-    Unknown,
+    UnknownAbility(AbilityName),
+    /// The form is in the trash, and must be restored or purged first.
+    Deleted,
+    /// Breaks a rule that holds across the stored entities, such as every
+    /// evolution chain being a tree.
+    Invalid(ValidationError),
+    Unknown(Source),
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    /// The Pokemon at this index of the batch already exists.
+    Conflict(usize),
+    Unknown(Source),
 }
 
+#[derive(Debug)]
 pub enum RetrieveAllError {
-    Unknown,
+    Unknown(Source),
 }
 
+#[derive(Debug)]
 pub enum RetrieveError {
-    Unknown,
+    Unknown(Source),
     NotFound,
 }
 
+#[derive(Debug)]
 pub enum DeleteError {
-    Unknown,
+    Unknown(Source),
+    NotFound,
+    /// The stored revision is not the expected one.
+    Stale(u32),
+}
+
+#[derive(Debug)]
+pub enum RestoreError {
+    Unknown(Source),
+    /// The form is not in the trash.
+    NotFound,
+    /// The default form is in the trash, and must be restored first.
+    DefaultFormDeleted,
+}
+
+#[derive(Debug)]
+pub enum UpdateError {
+    Unknown(Source),
+    UnknownAbility(AbilityName),
     NotFound,
+    /// The stored revision is not the expected one.
+    Stale(u32),
+}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertError::Unknown(_) => write!(f, "the Pokemon could not be inserted"),
+            InsertError::Conflict => write!(f, "the Pokemon already exists"),
+            InsertError::Deleted => write!(f, "the Pokemon is in the trash"),
+            InsertError::Invalid(e) => write!(f, "{}", e),
+            InsertError::UnknownAbility(name) => {
+                write!(
+                    f,
+                    "there is no ability named '{}'",
+                    String::from(name.clone())
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Unknown(_) => write!(f, "the Pokemons could not be imported"),
+            ImportError::Conflict(_) => write!(f, "the Pokemon already exists"),
+        }
+    }
+}
+
+impl fmt::Display for RetrieveAllError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetrieveAllError::Unknown(_) => write!(f, "the Pokemons could not be retrieved"),
+        }
+    }
+}
+
+impl fmt::Display for RetrieveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetrieveError::Unknown(_) => write!(f, "the Pokemon could not be retrieved"),
+            RetrieveError::NotFound => write!(f, "the Pokemon does not exist"),
+        }
+    }
+}
+
+impl fmt::Display for DeleteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeleteError::Unknown(_) => write!(f, "the Pokemon could not be deleted"),
+            DeleteError::NotFound => write!(f, "the Pokemon does not exist"),
+            DeleteError::Stale(revision) => {
+                write!(f, "the Pokemon has changed, it is at revision {}", revision)
+            }
+        }
+    }
+}
+
+impl fmt::Display for RestoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestoreError::Unknown(_) => write!(f, "the Pokemon could not be restored"),
+            RestoreError::NotFound => write!(f, "the Pokemon is not in the trash"),
+            RestoreError::DefaultFormDeleted => {
+                write!(f, "the default form of the Pokemon is in the trash")
+            }
+        }
+    }
+}
+
+impl fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateError::Unknown(_) => write!(f, "the Pokemon could not be updated"),
+            UpdateError::UnknownAbility(name) => {
+                write!(
+                    f,
+                    "there is no ability named '{}'",
+                    String::from(name.clone())
+                )
+            }
+            UpdateError::NotFound => write!(f, "the Pokemon does not exist"),
+            UpdateError::Stale(revision) => {
+                write!(f, "the Pokemon has changed, it is at revision {}", revision)
+            }
+        }
+    }
+}
+
+impl Error for InsertError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InsertError::Unknown(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImportError::Unknown(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Error for RetrieveAllError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RetrieveAllError::Unknown(e) => Some(e.as_ref()),
+        }
+    }
+}
+
+impl Error for RetrieveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RetrieveError::Unknown(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Error for DeleteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DeleteError::Unknown(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
 }
 
+impl Error for RestoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RestoreError::Unknown(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Error for UpdateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UpdateError::Unknown(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// A poisoned lock only carries the guard, so keep its message as the cause.
+fn poisoned<T>(e: PoisonError<T>) -> Source {
+    Source::from(e.to_string())
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum SortBy {
+    #[default]
+    Number,
+    Name,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// What `import` does with a Pokemon that is already stored.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum OnConflict {
+    #[default]
+    Fail,
+    Skip,
+    Replace,
+}
+
+/// What happened to a single Pokemon of an imported batch.
+#[derive(Debug, PartialEq)]
+pub enum ImportOutcome {
+    /// The form was inserted at this revision, which is the first one unless
+    /// the form was purged before.
+    Inserted(u32),
+    /// The stored form was replaced, and is now at this revision.
+    Replaced(u32),
+    Skipped,
+    MissingDefaultForm,
+    /// The form is in the trash, which an import never purges.
+    Deleted,
+}
+
+/// Which page of the Pokedex `fetch_all` returns, and in which order. Only the
+/// default forms are listed.
+#[derive(Clone, Default)]
+pub struct FetchAllQuery {
+    pub limit: Option<u32>,
+    pub offset: u32,
+    pub sort_by: SortBy,
+    pub order: SortOrder,
+    pub pokemon_type: Option<PokemonType>,
+    pub min_number: Option<PokemonNumber>,
+    pub max_number: Option<PokemonNumber>,
+}
+
+impl FetchAllQuery {
+    fn matches(&self, pokemon: &Pokemon) -> bool {
+        pokemon.form.is_default()
+            && self
+                .pokemon_type
+                .as_ref()
+                .is_none_or(|t| pokemon.types.contains(t))
+            && self
+                .min_number
+                .as_ref()
+                .is_none_or(|min| &pokemon.number >= min)
+            && self
+                .max_number
+                .as_ref()
+                .is_none_or(|max| &pokemon.number <= max)
+    }
+}
+
+/// Which part of a learnset `fetch_learnset` returns.
+#[derive(Clone, Default)]
+pub struct LearnsetQuery {
+    pub method: Option<LearnMethod>,
+    pub generation: Option<Generation>,
+}
+
+impl LearnsetQuery {
+    fn matches(&self, entry: &LearnsetEntry) -> bool {
+        self.method.is_none_or(|method| entry.method == method)
+            && self
+                .generation
+                .is_none_or(|generation| entry.generation == generation)
+    }
+}
+
+/// Every change of a Pokemon is recorded in the audit log on behalf of an
+/// actor, along with the change itself.
 pub trait Repository: Send + Sync {
-    fn insert(
+    /// Inserts a form of a Pokemon. Any form but the default one requires the
+    /// default form to be stored first. A form in the trash is never replaced:
+    /// it has to be restored or purged first.
+    fn insert(&self, pokemon: Pokemon, actor: &Actor) -> Result<Pokemon, InsertError>;
+
+    fn fetch_all(&self, query: FetchAllQuery) -> Result<Vec<Pokemon>, RetrieveAllError>;
+
+    fn fetch_one(&self, number: PokemonNumber, form: FormSlug) -> Result<Pokemon, RetrieveError>;
+
+    /// Returns the default forms whose name best matches `query`, tolerating
+    /// typos, the best match first.
+    fn search(&self, query: String, limit: u32) -> Result<Vec<Pokemon>, RetrieveAllError>;
+
+    /// Returns every form of a Pokemon, the default one first, then by slug.
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, RetrieveAllError>;
+
+    /// Returns every form holding an ability, the alternate ones included, by
+    /// number, the default one first, then by slug.
+    fn fetch_ability_holders(&self, name: AbilityName) -> Result<Vec<Pokemon>, RetrieveAllError>;
+
+    /// Moves a form to the trash and returns the forms deleted, the default one
+    /// first. Deleting the default form deletes the Pokemon along with all its
+    /// forms. With `expected` revisions, a form at any other revision is left as
+    /// is.
+    fn delete_pokemon(
+        &self,
+        number: PokemonNumber,
+        form: FormSlug,
+        expected: Option<Vec<u32>>,
+        actor: &Actor,
+    ) -> Result<Vec<Pokemon>, DeleteError>;
+
+    /// Returns the forms in the trash by number, the default one first, then
+    /// by slug.
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, RetrieveAllError>;
+
+    /// Takes a form out of the trash, with the revision it was deleted at, and
+    /// returns the forms restored, the default one first. Restoring the default
+    /// form restores the forms deleted along with it.
+    fn restore(
+        &self,
+        number: PokemonNumber,
+        form: FormSlug,
+        actor: &Actor,
+    ) -> Result<Vec<Pokemon>, RestoreError>;
+
+    /// Removes for good the forms deleted before `before`, an RFC 3339
+    /// timestamp to the millisecond, and returns them.
+    fn purge(&self, before: String, actor: &Actor) -> Result<Vec<Pokemon>, DeleteError>;
+
+    /// Replaces a form and increments its revision. With `expected` revisions,
+    /// a form at any other revision is left as is.
+    fn update(
+        &self,
+        pokemon: Pokemon,
+        expected: Option<Vec<u32>>,
+        actor: &Actor,
+    ) -> Result<Pokemon, UpdateError>;
+
+    /// Writes a batch of Pokemons at once, returning an outcome per Pokemon in
+    /// the same order. Nothing is written when it fails, which it does on the
+    /// first conflict with `OnConflict::Fail`. The abilities of the Pokemons
+    /// written are added to the catalogue when missing, without a description,
+    /// so that an export from another Pokedex can be imported as is.
+    fn import(
+        &self,
+        pokemons: Vec<Pokemon>,
+        on_conflict: OnConflict,
+        actor: &Actor,
+    ) -> Result<Vec<ImportOutcome>, ImportError>;
+
+    /// Returns the changes of every form of a Pokemon, the oldest first, those
+    /// made before it was deleted included.
+    fn fetch_history(&self, number: PokemonNumber) -> Result<Vec<AuditEvent>, RetrieveAllError>;
+
+    /// Returns up to `limit` changes made at or after `since`, an RFC 3339
+    /// timestamp to the millisecond, the oldest first.
+    fn fetch_audit(
+        &self,
+        since: Option<String>,
+        limit: u32,
+    ) -> Result<Vec<AuditEvent>, RetrieveAllError>;
+
+    fn insert_ability(&self, ability: Ability) -> Result<Ability, InsertError>;
+
+    /// Returns the whole abilities catalogue, ordered by name.
+    fn fetch_abilities(&self) -> Result<Vec<Ability>, RetrieveAllError>;
+
+    fn fetch_ability(&self, name: AbilityName) -> Result<Ability, RetrieveError>;
+
+    /// Fails with a conflict when `to` already evolves from a Pokemon, and as
+    /// invalid when that Pokemon is another one or when `to` is an ancestor of
+    /// `from`, the chains being checked in the same write as the insert.
+    fn insert_evolution(&self, evolution: Evolution) -> Result<Evolution, InsertError>;
+
+    /// Returns every edge of every evolution chain.
+    fn fetch_evolutions(&self) -> Result<Vec<Evolution>, RetrieveAllError>;
+
+    fn insert_move(&self, pokemon_move: Move) -> Result<Move, InsertError>;
+
+    /// Returns the whole moves catalogue, ordered by name.
+    fn fetch_moves(&self) -> Result<Vec<Move>, RetrieveAllError>;
+
+    fn fetch_move(&self, name: MoveName) -> Result<Move, RetrieveError>;
+
+    /// Fails with a conflict when the Pokemon already learns the move the same
+    /// way in the same generation.
+    fn insert_learnset_entry(
+        &self,
+        number: PokemonNumber,
+        entry: LearnsetEntry,
+    ) -> Result<LearnsetEntry, InsertError>;
+
+    /// Returns the moves learnt by a Pokemon, ordered by generation, method,
+    /// level and name.
+    fn fetch_learnset(
         &self,
         number: PokemonNumber,
-        name: PokemonName,
-        types: PokemonTypes,
-    ) -> Result<Pokemon, InsertError>;
+        query: LearnsetQuery,
+    ) -> Result<Vec<LearnsetEntry>, RetrieveAllError>;
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, RetrieveAllError>;
+    /// Fails with a conflict when a key already has this name, even a revoked one.
+    fn insert_key(&self, key: ApiKey) -> Result<ApiKey, InsertError>;
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, RetrieveError>;
+    /// Returns every API key, the revoked ones included, ordered by name.
+    fn fetch_keys(&self) -> Result<Vec<ApiKey>, RetrieveAllError>;
 
-    fn delete_pokemon(&self, number: PokemonNumber) -> Result<(), DeleteError>;
+    /// Finds a key, revoked or not, by the hash of its secret.
+    fn fetch_key(&self, hash: String) -> Result<ApiKey, RetrieveError>;
+
+    /// Revoking a key twice is not an error.
+    fn revoke_key(&self, name: KeyName) -> Result<(), DeleteError>;
 }
 
 pub struct InMemoryRepository {
     data: Mutex<Vec<Pokemon>>,
+    abilities: Mutex<Vec<Ability>>,
+    evolutions: Mutex<Vec<Evolution>>,
+    moves: Mutex<Vec<Move>>,
+    learnsets: Mutex<Vec<(PokemonNumber, LearnsetEntry)>>,
+    keys: Mutex<Vec<ApiKey>>,
+    trash: Mutex<Vec<DeletedPokemon>>,
+    audit: Mutex<Vec<AuditEvent>>,
     error: bool,
+    stale_reads: bool,
 }
 
 impl InMemoryRepository {
     pub fn new() -> Self {
         Self {
             data: Mutex::new(vec![]),
+            abilities: Mutex::new(vec![]),
+            evolutions: Mutex::new(vec![]),
+            moves: Mutex::new(vec![]),
+            learnsets: Mutex::new(vec![]),
+            keys: Mutex::new(vec![]),
+            trash: Mutex::new(vec![]),
+            audit: Mutex::new(vec![]),
             error: false,
+            stale_reads: false,
         }
     }
 
@@ -58,291 +487,3365 @@ impl InMemoryRepository {
             ..self
         }
     }
+
+    /// Reads a form as it was a revision earlier, as when another change lands
+    /// right after it is read.
+    #[cfg(test)]
+    pub fn with_stale_reads(self) -> Self {
+        Self {
+            stale_reads: true,
+            ..self
+        }
+    }
+
+    /// Returns the first ability of `pokemon` missing from the catalogue.
+    fn unknown_ability(&self, pokemon: &Pokemon) -> Result<Option<AbilityName>, Source> {
+        let lock = match self.abilities.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(poisoned(e)),
+        };
+
+        Ok(pokemon
+            .abilities
+            .iter()
+            .find(|(_, name)| !lock.iter().any(|ability| &ability.name == name))
+            .map(|(_, name)| name.clone()))
+    }
+
+    /// Forgets the evolutions and learnset of a Pokemon purged for good.
+    fn forget_relations(&self, number: &PokemonNumber) -> Result<(), Source> {
+        match self.evolutions.lock() {
+            Ok(mut evolutions) => evolutions.retain(|e| &e.from != number && &e.to != number),
+            Err(e) => return Err(poisoned(e)),
+        }
+
+        match self.learnsets.lock() {
+            Ok(mut learnsets) => learnsets.retain(|(n, _)| n != number),
+            Err(e) => return Err(poisoned(e)),
+        }
+
+        Ok(())
+    }
+}
+
+fn unavailable() -> Source {
+    Source::from("the in-memory repository is unavailable")
+}
+
+/// The revision a form is inserted at: the first one, or the one after the
+/// last recorded in `log` when the form was purged before, so that an entity
+/// tag never matches a former incarnation of the form.
+fn next_revision(log: &[AuditEvent], pokemon: &Pokemon) -> u32 {
+    log.iter()
+        .rev()
+        .find(|e| e.number == pokemon.number && e.form == pokemon.form)
+        .and_then(|e| e.after.as_ref().or(e.before.as_ref()))
+        .map_or(Pokemon::FIRST_REVISION, |last| last.revision + 1)
+}
+
+/// Adds the abilities of `pokemon` missing from the catalogue, without a
+/// description, keeping it ordered by name.
+fn add_missing_abilities(catalogue: &mut Vec<Ability>, pokemon: &Pokemon) {
+    for (_, name) in pokemon.abilities.iter() {
+        if !catalogue.iter().any(|ability| &ability.name == name) {
+            catalogue.push(Ability::new(name.clone(), String::new()));
+        }
+    }
+    catalogue.sort_by(|a, b| a.name.cmp(&b.name));
+}
+
+/// Keeps every chain a tree: a Pokemon has at most one pre-evolution and
+/// cannot evolve into one of its ancestors.
+fn check_evolution(evolutions: &[Evolution], evolution: &Evolution) -> Result<(), InsertError> {
+    let from = u16::from(evolution.from.clone());
+    let to = u16::from(evolution.to.clone());
+
+    match evolutions.iter().find(|e| e.to == evolution.to) {
+        Some(existing) if existing.from == evolution.from => return Err(InsertError::Conflict),
+        Some(existing) => {
+            return Err(InsertError::Invalid(ValidationError::HasPreEvolution {
+                number: to,
+                from: u16::from(existing.from.clone()),
+            }))
+        }
+        None => {}
+    }
+
+    // Walk up from `from`: reaching `to` means `to` is an ancestor. The walk is
+    // bounded in case the stored graph was corrupted by hand.
+    let mut current = Some(evolution.from.clone());
+    for _ in 0..=evolutions.len() {
+        match current {
+            Some(number) if number == evolution.to => {
+                return Err(InsertError::Invalid(ValidationError::EvolutionCycle {
+                    from,
+                    to,
+                }))
+            }
+            Some(number) => {
+                current = evolutions
+                    .iter()
+                    .find(|e| e.to == number)
+                    .map(|e| e.from.clone())
+            }
+            None => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Tells whether the same form of a Pokemon is in the trash.
+fn is_trashed(trash: &[DeletedPokemon], pokemon: &Pokemon) -> bool {
+    trash
+        .iter()
+        .any(|d| d.pokemon.number == pokemon.number && d.pokemon.form == pokemon.form)
+}
+
+/// Appends an event to the audit log, numbering it after the last one.
+fn append(log: &mut Vec<AuditEvent>, event: AuditEvent) {
+    let id = log.len() as u64 + 1;
+    log.push(event.with_id(id));
 }
 
 impl Repository for InMemoryRepository {
-    fn insert(
-        &self,
-        number: PokemonNumber,
-        name: PokemonName,
-        types: PokemonTypes,
-    ) -> Result<Pokemon, InsertError> {
+    fn insert(&self, pokemon: Pokemon, actor: &Actor) -> Result<Pokemon, InsertError> {
         if self.error {
-            return Err(InsertError::Unknown);
+            return Err(InsertError::Unknown(unavailable()));
+        }
+
+        match self.unknown_ability(&pokemon) {
+            Ok(None) => {}
+            Ok(Some(name)) => return Err(InsertError::UnknownAbility(name)),
+            Err(e) => return Err(InsertError::Unknown(e)),
         }
 
         let mut lock = match self.data.lock() {
             Ok(lock) => lock,
-            Err(_) => return Err(InsertError::Unknown),
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
         };
 
-        if lock.iter().any(|pokemon| pokemon.number == number) {
+        if lock
+            .iter()
+            .any(|p| p.number == pokemon.number && p.form == pokemon.form)
+        {
             return Err(InsertError::Conflict);
         }
 
-        let number_clone = number.clone();
-        let pokemon = Pokemon::new(number_clone, name, types);
+        match self.trash.lock() {
+            Ok(trash) if is_trashed(&trash, &pokemon) => return Err(InsertError::Deleted),
+            Ok(_) => {}
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        }
+
+        let mut log = match self.audit.lock() {
+            Ok(log) => log,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        let revision = next_revision(&log, &pokemon);
+        let pokemon = pokemon.with_revision(revision);
         lock.push(pokemon.clone());
+        append(&mut log, AuditEvent::inserted(actor, pokemon.clone()));
         Ok(pokemon)
     }
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, RetrieveAllError> {
+    fn fetch_all(&self, query: FetchAllQuery) -> Result<Vec<Pokemon>, RetrieveAllError> {
         if self.error {
-            return Err(RetrieveAllError::Unknown);
+            return Err(RetrieveAllError::Unknown(unavailable()));
         }
 
         let lock = match self.data.lock() {
             Ok(lock) => lock,
-            Err(_) => return Err(RetrieveAllError::Unknown),
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
         };
 
-        let mut pokemons = lock.to_vec();
-        pokemons.sort_by(|a, b| a.number.cmp(&b.number));
-        Ok(pokemons)
+        let mut pokemons = lock
+            .iter()
+            .filter(|p| query.matches(p))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        pokemons.sort_by(|a, b| {
+            let ordering = match query.sort_by {
+                SortBy::Number => a.number.cmp(&b.number),
+                SortBy::Name => a.name.cmp(&b.name).then(a.number.cmp(&b.number)),
+            };
+            match query.order {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            }
+        });
+
+        Ok(pokemons
+            .into_iter()
+            .skip(query.offset as usize)
+            .take(query.limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect())
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, RetrieveError> {
+    fn fetch_one(&self, number: PokemonNumber, form: FormSlug) -> Result<Pokemon, RetrieveError> {
         if self.error {
-            return Err(RetrieveError::Unknown);
+            return Err(RetrieveError::Unknown(unavailable()));
         }
 
         let lock = match self.data.lock() {
             Ok(lock) => lock,
-            Err(_) => return Err(RetrieveError::Unknown),
+            Err(e) => return Err(RetrieveError::Unknown(poisoned(e))),
         };
 
-        match lock.iter().find(|p| p.number == number).cloned() {
+        match lock
+            .iter()
+            .find(|p| p.number == number && p.form == form)
+            .cloned()
+        {
+            Some(pokemon) if self.stale_reads => {
+                let revision = pokemon.revision.saturating_sub(1);
+                Ok(pokemon.with_revision(revision))
+            }
             Some(pokemon) => Ok(pokemon),
             None => Err(RetrieveError::NotFound),
         }
     }
 
-    fn delete_pokemon(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+    fn search(&self, query: String, limit: u32) -> Result<Vec<Pokemon>, RetrieveAllError> {
         if self.error {
-            return Err(DeleteError::Unknown);
+            return Err(RetrieveAllError::Unknown(unavailable()));
         }
 
-        let mut lock = match self.data.lock() {
+        let lock = match self.data.lock() {
             Ok(lock) => lock,
-            Err(_) => return Err(DeleteError::Unknown),
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
         };
 
-        match lock.iter().position(|p| p.number == number) {
-            Some(index) => {
-                lock.remove(index);
-                Ok(())
-            }
-            None => Err(DeleteError::NotFound),
-        }
+        let candidates = lock
+            .iter()
+            .filter(|p| p.form.is_default())
+            .map(|p| {
+                (
+                    u16::from(p.number.clone()),
+                    String::from(p.name.clone()),
+                    p.clone(),
+                )
+            })
+            .filter(|(_, name, _)| search::is_candidate(&query, name))
+            .collect();
+
+        Ok(search::best(&query, candidates, limit as usize))
     }
-}
 
-pub struct SqliteRepository {
-    connection: Mutex<Connection>,
-}
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        if self.error {
+            return Err(RetrieveAllError::Unknown(unavailable()));
+        }
 
-impl SqliteRepository {
-    pub fn try_new(path: &str) -> Result<Self, ()> {
-        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
-        {
-            Ok(connection) => connection,
-            _ => return Err(()),
+        let lock = match self.data.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
         };
 
-        match connection.execute("pragma foreign_keys = 1", []) {
-            Ok(_) => Ok(Self {
-                connection: Mutex::new(connection),
-            }),
-            _ => Err(()),
+        let mut forms = lock
+            .iter()
+            .filter(|p| p.number == number)
+            .cloned()
+            .collect::<Vec<_>>();
+        forms.sort_by_key(|p| (!p.form.is_default(), p.form.clone()));
+
+        Ok(forms)
+    }
+
+    fn fetch_ability_holders(&self, name: AbilityName) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        if self.error {
+            return Err(RetrieveAllError::Unknown(unavailable()));
         }
+
+        let lock = match self.data.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let mut holders = lock
+            .iter()
+            .filter(|p| p.abilities.contains(&name).is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+        holders.sort_by_key(|p| (p.number.clone(), !p.form.is_default(), p.form.clone()));
+
+        Ok(holders)
     }
-}
 
-impl Repository for SqliteRepository {
-    fn insert(
+    fn delete_pokemon(
         &self,
         number: PokemonNumber,
-        name: PokemonName,
-        types: PokemonTypes,
-    ) -> Result<Pokemon, InsertError> {
-        let mut lock = match self.connection.lock() {
+        form: FormSlug,
+        expected: Option<Vec<u32>>,
+        actor: &Actor,
+    ) -> Result<Vec<Pokemon>, DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown(unavailable()));
+        }
+
+        let mut lock = match self.data.lock() {
             Ok(lock) => lock,
-            _ => return Err(InsertError::Unknown),
+            Err(e) => return Err(DeleteError::Unknown(poisoned(e))),
         };
 
-        let transaction = match lock.transaction() {
-            Ok(transaction) => transaction,
-            Err(_) => return Err(InsertError::Unknown),
+        let index = match lock
+            .iter()
+            .position(|p| p.number == number && p.form == form)
+        {
+            Some(index) => index,
+            None => return Err(DeleteError::NotFound),
         };
 
-        match transaction.execute(
-            "insert into pokemons (number, name) values (?, ?)",
-            params![u16::from(number.clone()), String::from(name.clone())],
-        ) {
-            Ok(_) => {}
-            Err(rusqlite::Error::SqliteFailure(_, Some(message))) => {
-                if message == "UNIQUE constraint failed: pokemons.number" {
-                    return Err(InsertError::Conflict);
-                } else {
-                    return Err(InsertError::Unknown);
-                }
-            }
-            _ => return Err(InsertError::Unknown),
-        }
-
-        for _type in Vec::<String>::from(types.clone()) {
-            if transaction
-                .execute(
-                    "insert into types (pokemon_number, name) values (?, ?)",
-                    params![u16::from(number.clone()), _type],
-                )
-                .is_err()
-            {
-                return Err(InsertError::Unknown);
-            }
-        }
-
-        match transaction.commit() {
-            Ok(_) => Ok(Pokemon::new(number, name, types)),
-            _ => Err(InsertError::Unknown),
+        let revision = lock[index].revision;
+        if expected
+            .as_ref()
+            .is_some_and(|expected| !expected.contains(&revision))
+        {
+            return Err(DeleteError::Stale(revision));
         }
-    }
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, RetrieveAllError> {
-        let lock = match self.connection.lock() {
-            Ok(lock) => lock,
-            _ => return Err(RetrieveAllError::Unknown),
+        let mut trash = match self.trash.lock() {
+            Ok(trash) => trash,
+            Err(e) => return Err(DeleteError::Unknown(poisoned(e))),
         };
 
-        let pokemon_rows = match self::fetch_pokemon_rows(&lock, None) {
-            Ok(pokemon_rows) => pokemon_rows,
-            _ => return Err(RetrieveAllError::Unknown),
+        let mut log = match self.audit.lock() {
+            Ok(log) => log,
+            Err(e) => return Err(DeleteError::Unknown(poisoned(e))),
         };
 
-        let mut pokemons = vec![];
-
-        for pokemon_row in pokemon_rows {
-            let type_rows = match fetch_type_rows(&lock, pokemon_row.0) {
-                Ok(type_rows) => type_rows,
-                _ => return Err(RetrieveAllError::Unknown),
-            };
+        // Deleting the default form deletes every other one.
+        let (mut deleted, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut *lock)
+            .into_iter()
+            .partition(|p| p.number == number && (form.is_default() || p.form == form));
+        *lock = kept;
+        deleted.sort_by_key(|p| (!p.form.is_default(), p.form.clone()));
 
-            match (
-                PokemonNumber::try_from(pokemon_row.0),
-                PokemonName::try_from(pokemon_row.1),
-                PokemonTypes::try_from(type_rows),
-            ) {
-                (Ok(number), Ok(name), Ok(types)) => {
-                    pokemons.push(Pokemon::new(number, name, types))
-                }
-                _ => return Err(RetrieveAllError::Unknown),
-            }
+        let deleted_at = now();
+        for pokemon in &deleted {
+            append(
+                &mut log,
+                AuditEvent::deleted(actor, pokemon.clone()).at(deleted_at.clone()),
+            );
+            trash.push(DeletedPokemon {
+                pokemon: pokemon.clone(),
+                deleted_at: deleted_at.clone(),
+            });
         }
 
-        Ok(pokemons)
+        Ok(deleted)
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, RetrieveError> {
-        let lock = match self.connection.lock() {
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, RetrieveAllError> {
+        if self.error {
+            return Err(RetrieveAllError::Unknown(unavailable()));
+        }
+
+        let lock = match self.trash.lock() {
             Ok(lock) => lock,
-            _ => return Err(RetrieveError::Unknown),
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
         };
 
-        let pokemon_rows = match self::fetch_pokemon_rows(&lock, Some(u16::from(number.clone()))) {
-            Ok(pokemon_rows) => pokemon_rows,
-            _ => return Err(RetrieveError::Unknown),
-        };
+        let mut trash = lock.clone();
+        trash.sort_by_key(|d| {
+            (
+                d.pokemon.number.clone(),
+                !d.pokemon.form.is_default(),
+                d.pokemon.form.clone(),
+            )
+        });
 
-        let pokemon_row = match pokemon_rows.first() {
-            Some(pokemon) => pokemon.to_owned(),
-            _ => return Err(RetrieveError::NotFound),
-        };
+        Ok(trash)
+    }
 
-        let type_rows = match fetch_type_rows(&lock, pokemon_row.0) {
-            Ok(type_rows) => type_rows,
-            _ => return Err(RetrieveError::Unknown),
+    fn restore(
+        &self,
+        number: PokemonNumber,
+        form: FormSlug,
+        actor: &Actor,
+    ) -> Result<Vec<Pokemon>, RestoreError> {
+        if self.error {
+            return Err(RestoreError::Unknown(unavailable()));
+        }
+
+        let mut lock = match self.data.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RestoreError::Unknown(poisoned(e))),
+        };
+
+        let mut trash = match self.trash.lock() {
+            Ok(trash) => trash,
+            Err(e) => return Err(RestoreError::Unknown(poisoned(e))),
+        };
+
+        let deleted_at = match trash
+            .iter()
+            .find(|d| d.pokemon.number == number && d.pokemon.form == form)
+        {
+            Some(deleted) => deleted.deleted_at.clone(),
+            None => return Err(RestoreError::NotFound),
+        };
+
+        if !form.is_default()
+            && !lock
+                .iter()
+                .any(|p| p.number == number && p.form.is_default())
+        {
+            return Err(RestoreError::DefaultFormDeleted);
+        }
+
+        let mut log = match self.audit.lock() {
+            Ok(log) => log,
+            Err(e) => return Err(RestoreError::Unknown(poisoned(e))),
+        };
+
+        // The default form brings back the forms deleted along with it.
+        let (mut restored, kept): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut *trash).into_iter().partition(|d| {
+                d.pokemon.number == number
+                    && match form.is_default() {
+                        true => d.deleted_at == deleted_at,
+                        false => d.pokemon.form == form,
+                    }
+            });
+        *trash = kept;
+        restored.sort_by_key(|d| (!d.pokemon.form.is_default(), d.pokemon.form.clone()));
+
+        let restored_at = now();
+        let mut pokemons = vec![];
+        for DeletedPokemon { pokemon, .. } in restored {
+            append(
+                &mut log,
+                AuditEvent::restored(actor, pokemon.clone()).at(restored_at.clone()),
+            );
+            lock.push(pokemon.clone());
+            pokemons.push(pokemon);
+        }
+
+        Ok(pokemons)
+    }
+
+    fn purge(&self, before: String, actor: &Actor) -> Result<Vec<Pokemon>, DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown(unavailable()));
+        }
+
+        let mut trash = match self.trash.lock() {
+            Ok(trash) => trash,
+            Err(e) => return Err(DeleteError::Unknown(poisoned(e))),
+        };
+
+        let (mut purged, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut *trash)
+            .into_iter()
+            .partition(|d| d.deleted_at < before);
+        *trash = kept;
+        purged.sort_by_key(|d| {
+            (
+                d.pokemon.number.clone(),
+                !d.pokemon.form.is_default(),
+                d.pokemon.form.clone(),
+            )
+        });
+
+        let mut log = match self.audit.lock() {
+            Ok(log) => log,
+            Err(e) => return Err(DeleteError::Unknown(poisoned(e))),
+        };
+
+        let purged_at = now();
+        let mut pokemons = vec![];
+        for DeletedPokemon { pokemon, .. } in purged {
+            if pokemon.form.is_default() {
+                if let Err(e) = self.forget_relations(&pokemon.number) {
+                    return Err(DeleteError::Unknown(e));
+                }
+            }
+            append(
+                &mut log,
+                AuditEvent::purged(actor, pokemon.clone()).at(purged_at.clone()),
+            );
+            pokemons.push(pokemon);
+        }
+
+        Ok(pokemons)
+    }
+
+    fn update(
+        &self,
+        pokemon: Pokemon,
+        expected: Option<Vec<u32>>,
+        actor: &Actor,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown(unavailable()));
+        }
+
+        match self.unknown_ability(&pokemon) {
+            Ok(None) => {}
+            Ok(Some(name)) => return Err(UpdateError::UnknownAbility(name)),
+            Err(e) => return Err(UpdateError::Unknown(e)),
+        }
+
+        let mut lock = match self.data.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(UpdateError::Unknown(poisoned(e))),
+        };
+
+        let mut log = match self.audit.lock() {
+            Ok(log) => log,
+            Err(e) => return Err(UpdateError::Unknown(poisoned(e))),
+        };
+
+        match lock
+            .iter_mut()
+            .find(|p| p.number == pokemon.number && p.form == pokemon.form)
+        {
+            Some(current)
+                if expected
+                    .as_ref()
+                    .is_some_and(|expected| !expected.contains(&current.revision)) =>
+            {
+                Err(UpdateError::Stale(current.revision))
+            }
+            Some(current) => {
+                let before = current.clone();
+                *current = pokemon.with_revision(current.revision + 1);
+                append(
+                    &mut log,
+                    AuditEvent::updated(actor, before, current.clone()),
+                );
+                Ok(current.clone())
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    fn import(
+        &self,
+        pokemons: Vec<Pokemon>,
+        on_conflict: OnConflict,
+        actor: &Actor,
+    ) -> Result<Vec<ImportOutcome>, ImportError> {
+        if self.error {
+            return Err(ImportError::Unknown(unavailable()));
+        }
+
+        let mut lock = match self.data.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(ImportError::Unknown(poisoned(e))),
+        };
+
+        let trash = match self.trash.lock() {
+            Ok(trash) => trash,
+            Err(e) => return Err(ImportError::Unknown(poisoned(e))),
+        };
+
+        let mut log = match self.audit.lock() {
+            Ok(log) => log,
+            Err(e) => return Err(ImportError::Unknown(poisoned(e))),
+        };
+
+        let mut catalogue = match self.abilities.lock() {
+            Ok(catalogue) => catalogue,
+            Err(e) => return Err(ImportError::Unknown(poisoned(e))),
+        };
+
+        // Work on copies so that a failed import leaves the data untouched.
+        let mut data = lock.clone();
+        let mut abilities = catalogue.clone();
+        let mut events = vec![];
+        let mut outcomes = Vec::with_capacity(pokemons.len());
+
+        for (index, pokemon) in pokemons.into_iter().enumerate() {
+            if !pokemon.form.is_default()
+                && !data
+                    .iter()
+                    .any(|p| p.number == pokemon.number && p.form.is_default())
+            {
+                outcomes.push(ImportOutcome::MissingDefaultForm);
+                continue;
+            }
+
+            if is_trashed(&trash, &pokemon) {
+                outcomes.push(ImportOutcome::Deleted);
+                continue;
+            }
+
+            let outcome = match data
+                .iter_mut()
+                .find(|p| p.number == pokemon.number && p.form == pokemon.form)
+            {
+                Some(_) if on_conflict == OnConflict::Fail => {
+                    return Err(ImportError::Conflict(index))
+                }
+                Some(_) if on_conflict == OnConflict::Skip => ImportOutcome::Skipped,
+                Some(current) => {
+                    add_missing_abilities(&mut abilities, &pokemon);
+                    let before = current.clone();
+                    *current = pokemon.with_revision(current.revision + 1);
+                    events.push(AuditEvent::updated(actor, before, current.clone()));
+                    ImportOutcome::Replaced(current.revision)
+                }
+                None => {
+                    add_missing_abilities(&mut abilities, &pokemon);
+                    let revision = next_revision(&log, &pokemon);
+                    let pokemon = pokemon.with_revision(revision);
+                    data.push(pokemon.clone());
+                    events.push(AuditEvent::inserted(actor, pokemon));
+                    ImportOutcome::Inserted(revision)
+                }
+            };
+            outcomes.push(outcome);
+        }
+
+        for event in events {
+            append(&mut log, event);
+        }
+
+        *lock = data;
+        *catalogue = abilities;
+        Ok(outcomes)
+    }
+
+    fn fetch_history(&self, number: PokemonNumber) -> Result<Vec<AuditEvent>, RetrieveAllError> {
+        if self.error {
+            return Err(RetrieveAllError::Unknown(unavailable()));
+        }
+
+        match self.audit.lock() {
+            Ok(log) => Ok(log.iter().filter(|e| e.number == number).cloned().collect()),
+            Err(e) => Err(RetrieveAllError::Unknown(poisoned(e))),
+        }
+    }
+
+    fn fetch_audit(
+        &self,
+        since: Option<String>,
+        limit: u32,
+    ) -> Result<Vec<AuditEvent>, RetrieveAllError> {
+        if self.error {
+            return Err(RetrieveAllError::Unknown(unavailable()));
+        }
+
+        match self.audit.lock() {
+            Ok(log) => Ok(log
+                .iter()
+                .filter(|e| since.as_ref().is_none_or(|since| &e.timestamp >= since))
+                .take(limit as usize)
+                .cloned()
+                .collect()),
+            Err(e) => Err(RetrieveAllError::Unknown(poisoned(e))),
+        }
+    }
+
+    fn insert_ability(&self, ability: Ability) -> Result<Ability, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown(unavailable()));
+        }
+
+        let mut lock = match self.abilities.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        if lock.iter().any(|a| a.name == ability.name) {
+            return Err(InsertError::Conflict);
+        }
+
+        lock.push(ability.clone());
+        lock.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(ability)
+    }
+
+    fn fetch_abilities(&self) -> Result<Vec<Ability>, RetrieveAllError> {
+        if self.error {
+            return Err(RetrieveAllError::Unknown(unavailable()));
+        }
+
+        match self.abilities.lock() {
+            Ok(lock) => Ok(lock.clone()),
+            Err(e) => Err(RetrieveAllError::Unknown(poisoned(e))),
+        }
+    }
+
+    fn fetch_ability(&self, name: AbilityName) -> Result<Ability, RetrieveError> {
+        if self.error {
+            return Err(RetrieveError::Unknown(unavailable()));
+        }
+
+        let lock = match self.abilities.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveError::Unknown(poisoned(e))),
+        };
+
+        match lock.iter().find(|a| a.name == name).cloned() {
+            Some(ability) => Ok(ability),
+            None => Err(RetrieveError::NotFound),
+        }
+    }
+
+    fn insert_evolution(&self, evolution: Evolution) -> Result<Evolution, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown(unavailable()));
+        }
+
+        let data = match self.data.lock() {
+            Ok(data) => data,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+        let stored = |number: &PokemonNumber| data.iter().any(|p| &p.number == number);
+
+        let mut lock = match self.evolutions.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        // The chains are checked as they are listed, without the evolutions of
+        // the deleted Pokemons, which still keep their own place.
+        let evolutions = lock
+            .iter()
+            .filter(|e| stored(&e.from) && stored(&e.to))
+            .cloned()
+            .collect::<Vec<_>>();
+        check_evolution(&evolutions, &evolution)?;
+        if lock.iter().any(|e| e.to == evolution.to) {
+            return Err(InsertError::Conflict);
+        }
+
+        lock.push(evolution.clone());
+        Ok(evolution)
+    }
+
+    fn fetch_evolutions(&self) -> Result<Vec<Evolution>, RetrieveAllError> {
+        if self.error {
+            return Err(RetrieveAllError::Unknown(unavailable()));
+        }
+
+        let data = match self.data.lock() {
+            Ok(data) => data,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+        let stored = |number: &PokemonNumber| data.iter().any(|p| &p.number == number);
+
+        // The evolutions of a deleted Pokemon are kept until it is purged.
+        match self.evolutions.lock() {
+            Ok(lock) => Ok(lock
+                .iter()
+                .filter(|e| stored(&e.from) && stored(&e.to))
+                .cloned()
+                .collect()),
+            Err(e) => Err(RetrieveAllError::Unknown(poisoned(e))),
+        }
+    }
+
+    fn insert_move(&self, pokemon_move: Move) -> Result<Move, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown(unavailable()));
+        }
+
+        let mut lock = match self.moves.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        if lock.iter().any(|m| m.name == pokemon_move.name) {
+            return Err(InsertError::Conflict);
+        }
+
+        lock.push(pokemon_move.clone());
+        lock.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(pokemon_move)
+    }
+
+    fn fetch_moves(&self) -> Result<Vec<Move>, RetrieveAllError> {
+        if self.error {
+            return Err(RetrieveAllError::Unknown(unavailable()));
+        }
+
+        match self.moves.lock() {
+            Ok(lock) => Ok(lock.clone()),
+            Err(e) => Err(RetrieveAllError::Unknown(poisoned(e))),
+        }
+    }
+
+    fn fetch_move(&self, name: MoveName) -> Result<Move, RetrieveError> {
+        if self.error {
+            return Err(RetrieveError::Unknown(unavailable()));
+        }
+
+        let lock = match self.moves.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveError::Unknown(poisoned(e))),
+        };
+
+        match lock.iter().find(|m| m.name == name).cloned() {
+            Some(pokemon_move) => Ok(pokemon_move),
+            None => Err(RetrieveError::NotFound),
+        }
+    }
+
+    fn insert_learnset_entry(
+        &self,
+        number: PokemonNumber,
+        entry: LearnsetEntry,
+    ) -> Result<LearnsetEntry, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown(unavailable()));
+        }
+
+        let mut lock = match self.learnsets.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        if lock.iter().any(|(n, e)| {
+            n == &number
+                && e.move_name == entry.move_name
+                && e.method == entry.method
+                && e.generation == entry.generation
+        }) {
+            return Err(InsertError::Conflict);
+        }
+
+        lock.push((number, entry.clone()));
+        Ok(entry)
+    }
+
+    fn fetch_learnset(
+        &self,
+        number: PokemonNumber,
+        query: LearnsetQuery,
+    ) -> Result<Vec<LearnsetEntry>, RetrieveAllError> {
+        if self.error {
+            return Err(RetrieveAllError::Unknown(unavailable()));
+        }
+
+        let lock = match self.learnsets.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let mut entries = lock
+            .iter()
+            .filter(|(n, entry)| n == &number && query.matches(entry))
+            .map(|(_, entry)| entry.clone())
+            .collect::<Vec<_>>();
+        entries.sort_by_key(LearnsetEntry::sort_key);
+
+        Ok(entries)
+    }
+
+    fn insert_key(&self, key: ApiKey) -> Result<ApiKey, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown(unavailable()));
+        }
+
+        let mut lock = match self.keys.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        if lock
+            .iter()
+            .any(|k| k.name == key.name || k.hash == key.hash)
+        {
+            return Err(InsertError::Conflict);
+        }
+
+        lock.push(key.clone());
+        lock.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(key)
+    }
+
+    fn fetch_keys(&self) -> Result<Vec<ApiKey>, RetrieveAllError> {
+        if self.error {
+            return Err(RetrieveAllError::Unknown(unavailable()));
+        }
+
+        match self.keys.lock() {
+            Ok(lock) => Ok(lock.clone()),
+            Err(e) => Err(RetrieveAllError::Unknown(poisoned(e))),
+        }
+    }
+
+    fn fetch_key(&self, hash: String) -> Result<ApiKey, RetrieveError> {
+        if self.error {
+            return Err(RetrieveError::Unknown(unavailable()));
+        }
+
+        let lock = match self.keys.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveError::Unknown(poisoned(e))),
+        };
+
+        match lock.iter().find(|k| k.hash == hash).cloned() {
+            Some(key) => Ok(key),
+            None => Err(RetrieveError::NotFound),
+        }
+    }
+
+    fn revoke_key(&self, name: KeyName) -> Result<(), DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown(unavailable()));
+        }
+
+        let mut lock = match self.keys.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(DeleteError::Unknown(poisoned(e))),
+        };
+
+        match lock.iter_mut().find(|k| k.name == name) {
+            Some(key) => {
+                key.revoked = true;
+                Ok(())
+            }
+            None => Err(DeleteError::NotFound),
+        }
+    }
+}
+
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteRepository {
+    /// Opens the database at `path`, creating it when missing, and brings its
+    /// schema up to date.
+    pub fn try_new(path: &str) -> Result<Self, MigrationError> {
+        let repo = Self::connect(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE,
+        )?;
+        repo.migrate()?;
+        Ok(repo)
+    }
+
+    /// Runs a whole SQL script, such as a dump, against the database.
+    #[cfg(test)]
+    pub fn load(&self, script: &str) -> Result<(), rusqlite::Error> {
+        match self.connection.lock() {
+            Ok(lock) => lock.execute_batch(script),
+            Err(e) => Err(rusqlite::Error::InvalidParameterName(e.to_string())),
+        }
+    }
+
+    /// Opens an existing database without touching its schema.
+    pub fn open(path: &str) -> Result<Self, rusqlite::Error> {
+        Self::connect(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+    }
+
+    fn connect(path: &str, flags: OpenFlags) -> Result<Self, rusqlite::Error> {
+        let connection = Connection::open_with_flags(path, flags)?;
+        connection.execute("pragma foreign_keys = 1", [])?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    pub fn migrate(&self) -> Result<u32, MigrationError> {
+        match self.connection.lock() {
+            Ok(mut lock) => migrations::migrate(&mut lock),
+            Err(e) => Err(MigrationError::Poisoned(e.to_string())),
+        }
+    }
+
+    pub fn schema_status(&self) -> Result<SchemaStatus, MigrationError> {
+        match self.connection.lock() {
+            Ok(lock) => Ok(migrations::status(&lock)?),
+            Err(e) => Err(MigrationError::Poisoned(e.to_string())),
+        }
+    }
+}
+
+impl Repository for SqliteRepository {
+    fn insert(&self, pokemon: Pokemon, actor: &Actor) -> Result<Pokemon, InsertError> {
+        let mut lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        let transaction = match lock.transaction() {
+            Ok(transaction) => transaction,
+            Err(e) => return Err(InsertError::Unknown(e.into())),
+        };
+
+        match is_form_trashed(&transaction, &pokemon) {
+            Ok(true) => return Err(InsertError::Deleted),
+            Ok(false) => {}
+            Err(e) => return Err(InsertError::Unknown(e.into())),
+        }
+        let pokemon = match next_recorded_revision(&transaction, &pokemon) {
+            Ok(revision) => pokemon.with_revision(revision),
+            Err(e) => return Err(InsertError::Unknown(e)),
+        };
+        insert_pokemon(&transaction, &pokemon)?;
+
+        if let Err(e) = record(&transaction, &AuditEvent::inserted(actor, pokemon.clone())) {
+            return Err(InsertError::Unknown(e.into()));
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemon),
+            Err(e) => Err(InsertError::Unknown(e.into())),
+        }
+    }
+
+    fn fetch_all(&self, query: FetchAllQuery) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let pokemon_rows = match self::fetch_pokemon_rows(&lock, &query) {
+            Ok(pokemon_rows) => pokemon_rows,
+            Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+        };
+
+        let mut pokemons = vec![];
+
+        for pokemon_row in pokemon_rows {
+            match pokemon_from_row(pokemon_row) {
+                Ok(pokemon) => pokemons.push(pokemon),
+                Err(e) => return Err(RetrieveAllError::Unknown(e)),
+            }
+        }
+
+        Ok(pokemons)
+    }
+
+    fn fetch_one(&self, number: PokemonNumber, form: FormSlug) -> Result<Pokemon, RetrieveError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveError::Unknown(poisoned(e))),
+        };
+
+        let pokemon_rows = match self::fetch_form_rows(&lock, number, Some(form), false) {
+            Ok(pokemon_rows) => pokemon_rows,
+            Err(e) => return Err(RetrieveError::Unknown(e.into())),
+        };
+
+        let pokemon_row = match pokemon_rows.into_iter().next() {
+            Some(pokemon) => pokemon,
+            _ => return Err(RetrieveError::NotFound),
+        };
+
+        pokemon_from_row(pokemon_row).map_err(RetrieveError::Unknown)
+    }
+
+    fn search(&self, query: String, limit: u32) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let candidates = match fetch_search_candidates(&lock, &query) {
+            Ok(candidates) => candidates,
+            Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+        };
+
+        let numbers = search::best(
+            &query,
+            candidates
+                .into_iter()
+                .map(|(number, name)| (number, name, number))
+                .collect(),
+            limit as usize,
+        );
+
+        let mut pokemons = vec![];
+        for number in numbers {
+            let number =
+                PokemonNumber::try_from(number).map_err(|e| RetrieveAllError::Unknown(e.into()))?;
+            let rows = match fetch_form_rows(&lock, number, Some(FormSlug::default()), false) {
+                Ok(rows) => rows,
+                Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+            };
+            for row in rows {
+                pokemons.push(pokemon_from_row(row).map_err(RetrieveAllError::Unknown)?);
+            }
+        }
+
+        Ok(pokemons)
+    }
+
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let pokemon_rows = match self::fetch_form_rows(&lock, number, None, false) {
+            Ok(pokemon_rows) => pokemon_rows,
+            Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+        };
+
+        pokemon_rows
+            .into_iter()
+            .map(pokemon_from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RetrieveAllError::Unknown)
+    }
+
+    fn fetch_ability_holders(&self, name: AbilityName) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let pokemon_rows = match fetch_ability_holder_rows(&lock, &name) {
+            Ok(pokemon_rows) => pokemon_rows,
+            Err(e) => return Err(RetrieveAllError::Unknown(e)),
+        };
+
+        pokemon_rows
+            .into_iter()
+            .map(pokemon_from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RetrieveAllError::Unknown)
+    }
+
+    fn delete_pokemon(
+        &self,
+        number: PokemonNumber,
+        form: FormSlug,
+        expected: Option<Vec<u32>>,
+        actor: &Actor,
+    ) -> Result<Vec<Pokemon>, DeleteError> {
+        let mut lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(DeleteError::Unknown(poisoned(e))),
+        };
+
+        let transaction = match lock.transaction() {
+            Ok(transaction) => transaction,
+            Err(e) => return Err(DeleteError::Unknown(e.into())),
+        };
+
+        // Deleting the default form deletes every other one.
+        let deleted = match fetch_forms_before(
+            &transaction,
+            number.clone(),
+            (!form.is_default()).then(|| form.clone()),
+        ) {
+            Ok(deleted) => deleted,
+            Err(e) => return Err(DeleteError::Unknown(e)),
+        };
+
+        match deleted.iter().find(|p| p.form == form) {
+            Some(p)
+                if expected
+                    .as_ref()
+                    .is_some_and(|expected| !expected.contains(&p.revision)) =>
+            {
+                return Err(DeleteError::Stale(p.revision))
+            }
+            Some(_) => {}
+            None => return Err(DeleteError::NotFound),
+        }
+
+        let deleted_at = now();
+        let number = u16::from(number);
+        let res = match form.is_default() {
+            true => transaction
+                .execute(
+                    "update pokemons set deleted_at = ?1 where number = ?2 and deleted_at is null",
+                    params![deleted_at, number],
+                )
+                .and_then(|_| {
+                    transaction.execute(
+                        "update forms set deleted_at = ?1 where pokemon_number = ?2 and deleted_at is null",
+                        params![deleted_at, number],
+                    )
+                }),
+            false => transaction.execute(
+                "update forms set deleted_at = ? where pokemon_number = ? and form = ? and deleted_at is null",
+                params![deleted_at, number, String::from(form.clone())],
+            ),
+        };
+        if let Err(e) = res {
+            return Err(DeleteError::Unknown(e.into()));
+        }
+
+        for pokemon in &deleted {
+            let event = AuditEvent::deleted(actor, pokemon.clone()).at(deleted_at.clone());
+            if let Err(e) = record(&transaction, &event) {
+                return Err(DeleteError::Unknown(e.into()));
+            }
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(deleted),
+            Err(e) => Err(DeleteError::Unknown(e.into())),
+        }
+    }
+
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        fetch_trash_rows(&lock, None)
+            .and_then(|rows| rows.into_iter().map(deleted_pokemon_from_row).collect())
+            .map_err(RetrieveAllError::Unknown)
+    }
+
+    fn restore(
+        &self,
+        number: PokemonNumber,
+        form: FormSlug,
+        actor: &Actor,
+    ) -> Result<Vec<Pokemon>, RestoreError> {
+        let mut lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RestoreError::Unknown(poisoned(e))),
+        };
+
+        let transaction = match lock.transaction() {
+            Ok(transaction) => transaction,
+            Err(e) => return Err(RestoreError::Unknown(e.into())),
+        };
+
+        let deleted = match fetch_form_rows(&transaction, number.clone(), None, true) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RestoreError::Unknown(e.into())),
+        };
+        let deleted_at = match deleted
+            .iter()
+            .find(|row| row.form == String::from(form.clone()))
+        {
+            Some(row) => row.deleted_at.clone(),
+            None => return Err(RestoreError::NotFound),
+        };
+
+        let number = u16::from(number);
+        if !form.is_default() {
+            match fetch_revision(&transaction, number, &FormSlug::default()) {
+                Ok(Some(_)) => {}
+                Ok(None) => return Err(RestoreError::DefaultFormDeleted),
+                Err(e) => return Err(RestoreError::Unknown(e.into())),
+            }
+        }
+
+        // The default form brings back the forms deleted along with it.
+        let restored = deleted.into_iter().filter(|row| match form.is_default() {
+            true => row.deleted_at == deleted_at,
+            false => row.form == String::from(form.clone()),
+        });
+
+        let restored_at = now();
+        let mut pokemons = vec![];
+        for row in restored {
+            let res = match row.form.as_str() {
+                "default" => transaction.execute(
+                    "update pokemons set deleted_at = null where number = ?",
+                    params![number],
+                ),
+                slug => transaction.execute(
+                    "update forms set deleted_at = null where pokemon_number = ? and form = ?",
+                    params![number, slug],
+                ),
+            };
+            if let Err(e) = res {
+                return Err(RestoreError::Unknown(e.into()));
+            }
+
+            let restored = pokemon_from_row(row).map_err(RestoreError::Unknown)?;
+            let event = AuditEvent::restored(actor, restored.clone()).at(restored_at.clone());
+            if let Err(e) = record(&transaction, &event) {
+                return Err(RestoreError::Unknown(e.into()));
+            }
+            pokemons.push(restored);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemons),
+            Err(e) => Err(RestoreError::Unknown(e.into())),
+        }
+    }
+
+    fn purge(&self, before: String, actor: &Actor) -> Result<Vec<Pokemon>, DeleteError> {
+        let mut lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(DeleteError::Unknown(poisoned(e))),
+        };
+
+        let transaction = match lock.transaction() {
+            Ok(transaction) => transaction,
+            Err(e) => return Err(DeleteError::Unknown(e.into())),
+        };
+
+        let rows = match fetch_trash_rows(&transaction, Some(&before)) {
+            Ok(rows) => rows,
+            Err(e) => return Err(DeleteError::Unknown(e)),
+        };
+
+        let purged_at = now();
+        let mut pokemons = vec![];
+        for row in rows {
+            let pokemon = pokemon_from_row(row).map_err(DeleteError::Unknown)?;
+            if let Err(e) = purge_form(&transaction, pokemon.clone(), actor, &purged_at) {
+                return Err(DeleteError::Unknown(e.into()));
+            }
+            pokemons.push(pokemon);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemons),
+            Err(e) => Err(DeleteError::Unknown(e.into())),
+        }
+    }
+
+    fn update(
+        &self,
+        pokemon: Pokemon,
+        expected: Option<Vec<u32>>,
+        actor: &Actor,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(UpdateError::Unknown(poisoned(e))),
+        };
+
+        let transaction = match lock.transaction() {
+            Ok(transaction) => transaction,
+            Err(e) => return Err(UpdateError::Unknown(e.into())),
+        };
+
+        let pokemon = replace_pokemon(&transaction, pokemon, expected, actor)?;
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemon),
+            Err(e) => Err(UpdateError::Unknown(e.into())),
+        }
+    }
+
+    fn import(
+        &self,
+        pokemons: Vec<Pokemon>,
+        on_conflict: OnConflict,
+        actor: &Actor,
+    ) -> Result<Vec<ImportOutcome>, ImportError> {
+        let mut lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(ImportError::Unknown(poisoned(e))),
+        };
+
+        // Dropping the transaction without committing rolls the whole batch back.
+        let transaction = match lock.transaction() {
+            Ok(transaction) => transaction,
+            Err(e) => return Err(ImportError::Unknown(e.into())),
+        };
+
+        let mut outcomes = Vec::with_capacity(pokemons.len());
+
+        for (index, pokemon) in pokemons.into_iter().enumerate() {
+            if !pokemon.form.is_default() {
+                match transaction.query_row(
+                    "select exists(select 1 from pokemons where number = ? and deleted_at is null)",
+                    params![u16::from(pokemon.number.clone())],
+                    |row| row.get::<_, bool>(0),
+                ) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        outcomes.push(ImportOutcome::MissingDefaultForm);
+                        continue;
+                    }
+                    Err(e) => return Err(ImportError::Unknown(e.into())),
+                }
+            }
+
+            match is_form_trashed(&transaction, &pokemon) {
+                Ok(false) => {}
+                Ok(true) => {
+                    outcomes.push(ImportOutcome::Deleted);
+                    continue;
+                }
+                Err(e) => return Err(ImportError::Unknown(e.into())),
+            }
+
+            let exists = match fetch_revision(
+                &transaction,
+                u16::from(pokemon.number.clone()),
+                &pokemon.form,
+            ) {
+                Ok(revision) => revision.is_some(),
+                Err(e) => return Err(ImportError::Unknown(e.into())),
+            };
+            match (exists, on_conflict) {
+                (true, OnConflict::Fail) => return Err(ImportError::Conflict(index)),
+                (true, OnConflict::Skip) => {
+                    outcomes.push(ImportOutcome::Skipped);
+                    continue;
+                }
+                _ => {}
+            }
+
+            if let Err(e) = insert_missing_abilities(&transaction, &pokemon.abilities) {
+                return Err(ImportError::Unknown(e.into()));
+            }
+
+            let outcome = if exists {
+                match replace_pokemon(&transaction, pokemon, None, actor) {
+                    Ok(replaced) => ImportOutcome::Replaced(replaced.revision),
+                    Err(e) => return Err(ImportError::Unknown(e.into())),
+                }
+            } else {
+                let revision = match next_recorded_revision(&transaction, &pokemon) {
+                    Ok(revision) => revision,
+                    Err(e) => return Err(ImportError::Unknown(e)),
+                };
+                let inserted = pokemon.with_revision(revision);
+                if let Err(e) = insert_pokemon(&transaction, &inserted) {
+                    return Err(ImportError::Unknown(e.into()));
+                }
+                if let Err(e) = record(&transaction, &AuditEvent::inserted(actor, inserted)) {
+                    return Err(ImportError::Unknown(e.into()));
+                }
+                ImportOutcome::Inserted(revision)
+            };
+            outcomes.push(outcome);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(outcomes),
+            Err(e) => Err(ImportError::Unknown(e.into())),
+        }
+    }
+
+    fn fetch_history(&self, number: PokemonNumber) -> Result<Vec<AuditEvent>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let rows = match fetch_audit_rows(&lock, Some(u16::from(number)), None, None) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+        };
+
+        rows.into_iter()
+            .map(audit_event_from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RetrieveAllError::Unknown)
+    }
+
+    fn fetch_audit(
+        &self,
+        since: Option<String>,
+        limit: u32,
+    ) -> Result<Vec<AuditEvent>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let rows = match fetch_audit_rows(&lock, None, since, Some(limit)) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+        };
+
+        rows.into_iter()
+            .map(audit_event_from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RetrieveAllError::Unknown)
+    }
+
+    fn insert_ability(&self, ability: Ability) -> Result<Ability, InsertError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        match lock.execute(
+            "insert into abilities (name, description) values (?, ?)",
+            params![String::from(ability.name.clone()), ability.description],
+        ) {
+            Ok(_) => Ok(ability),
+            Err(rusqlite::Error::SqliteFailure(_, Some(message)))
+                if message == "UNIQUE constraint failed: abilities.name" =>
+            {
+                Err(InsertError::Conflict)
+            }
+            Err(e) => Err(InsertError::Unknown(e.into())),
+        }
+    }
+
+    fn fetch_abilities(&self) -> Result<Vec<Ability>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let rows = match fetch_ability_rows(&lock, None) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+        };
+
+        rows.into_iter()
+            .map(ability_from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RetrieveAllError::Unknown)
+    }
+
+    fn fetch_ability(&self, name: AbilityName) -> Result<Ability, RetrieveError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveError::Unknown(poisoned(e))),
+        };
+
+        let row = match fetch_ability_rows(&lock, Some(name)) {
+            Ok(rows) => match rows.into_iter().next() {
+                Some(row) => row,
+                None => return Err(RetrieveError::NotFound),
+            },
+            Err(e) => return Err(RetrieveError::Unknown(e.into())),
+        };
+
+        ability_from_row(row).map_err(RetrieveError::Unknown)
+    }
+
+    fn insert_evolution(&self, evolution: Evolution) -> Result<Evolution, InsertError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        let evolutions = match fetch_evolution_rows(&lock) {
+            Ok(rows) => rows
+                .into_iter()
+                .map(evolution_from_row)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(InsertError::Unknown)?,
+            Err(e) => return Err(InsertError::Unknown(e.into())),
+        };
+        check_evolution(&evolutions, &evolution)?;
+
+        let trigger = TriggerValue::from(evolution.trigger.clone());
+
+        match lock.execute(
+            "insert into evolutions (from_number, to_number, trigger, level, item) values (?, ?, ?, ?, ?)",
+            params![
+                u16::from(evolution.from.clone()),
+                u16::from(evolution.to.clone()),
+                trigger.kind,
+                trigger.level,
+                trigger.item,
+            ],
+        ) {
+            Ok(_) => Ok(evolution),
+            Err(rusqlite::Error::SqliteFailure(e, _))
+                if e.code == rusqlite::ErrorCode::ConstraintViolation
+                    && e.extended_code != rusqlite::ffi::SQLITE_CONSTRAINT_FOREIGNKEY =>
+            {
+                Err(InsertError::Conflict)
+            }
+            Err(e) => Err(InsertError::Unknown(e.into())),
+        }
+    }
+
+    fn fetch_evolutions(&self) -> Result<Vec<Evolution>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let rows = match fetch_evolution_rows(&lock) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+        };
+
+        rows.into_iter()
+            .map(evolution_from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RetrieveAllError::Unknown)
+    }
+
+    fn insert_move(&self, pokemon_move: Move) -> Result<Move, InsertError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        match lock.execute(
+            "insert into moves (name, type, category, power, accuracy, pp) values (?, ?, ?, ?, ?, ?)",
+            params![
+                String::from(pokemon_move.name.clone()),
+                String::from(pokemon_move.move_type),
+                String::from(pokemon_move.category),
+                pokemon_move.power.map(u8::from),
+                pokemon_move.accuracy.map(u8::from),
+                u8::from(pokemon_move.pp),
+            ],
+        ) {
+            Ok(_) => Ok(pokemon_move),
+            Err(rusqlite::Error::SqliteFailure(_, Some(message)))
+                if message == "UNIQUE constraint failed: moves.name" =>
+            {
+                Err(InsertError::Conflict)
+            }
+            Err(e) => Err(InsertError::Unknown(e.into())),
+        }
+    }
+
+    fn fetch_moves(&self) -> Result<Vec<Move>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let rows = match fetch_move_rows(&lock, None) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+        };
+
+        rows.into_iter()
+            .map(move_from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RetrieveAllError::Unknown)
+    }
+
+    fn fetch_move(&self, name: MoveName) -> Result<Move, RetrieveError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveError::Unknown(poisoned(e))),
         };
 
-        match (
-            PokemonNumber::try_from(pokemon_row.0),
-            PokemonName::try_from(pokemon_row.1),
-            PokemonTypes::try_from(type_rows),
-        ) {
-            (Ok(number), Ok(name), Ok(types)) => Ok(Pokemon::new(number, name, types)),
-            _ => Err(RetrieveError::Unknown),
-        }
+        let row = match fetch_move_rows(&lock, Some(name)) {
+            Ok(rows) => match rows.into_iter().next() {
+                Some(row) => row,
+                None => return Err(RetrieveError::NotFound),
+            },
+            Err(e) => return Err(RetrieveError::Unknown(e.into())),
+        };
+
+        move_from_row(row).map_err(RetrieveError::Unknown)
+    }
+
+    fn insert_learnset_entry(
+        &self,
+        number: PokemonNumber,
+        entry: LearnsetEntry,
+    ) -> Result<LearnsetEntry, InsertError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        match lock.execute(
+            "insert into learnsets (pokemon_number, move_name, method, level, generation) values (?, ?, ?, ?, ?)",
+            params![
+                u16::from(number),
+                String::from(entry.move_name.clone()),
+                String::from(entry.method),
+                entry.level.map(u8::from),
+                u8::from(entry.generation),
+            ],
+        ) {
+            Ok(_) => Ok(entry),
+            Err(rusqlite::Error::SqliteFailure(e, _))
+                if e.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_PRIMARYKEY =>
+            {
+                Err(InsertError::Conflict)
+            }
+            Err(e) => Err(InsertError::Unknown(e.into())),
+        }
+    }
+
+    fn fetch_learnset(
+        &self,
+        number: PokemonNumber,
+        query: LearnsetQuery,
+    ) -> Result<Vec<LearnsetEntry>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let rows = match fetch_learnset_rows(&lock, number, query) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+        };
+
+        rows.into_iter()
+            .map(learnset_entry_from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RetrieveAllError::Unknown)
+    }
+
+    fn insert_key(&self, key: ApiKey) -> Result<ApiKey, InsertError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(poisoned(e))),
+        };
+
+        match lock.execute(
+            "insert into api_keys (name, role, prefix, hash, created_at, revoked) values (?, ?, ?, ?, ?, ?)",
+            params![
+                String::from(key.name.clone()),
+                String::from(key.role),
+                key.prefix,
+                key.hash,
+                key.created_at,
+                key.revoked
+            ],
+        ) {
+            Ok(_) => Ok(key),
+            Err(rusqlite::Error::SqliteFailure(e, _))
+                if e.code == rusqlite::ErrorCode::ConstraintViolation =>
+            {
+                Err(InsertError::Conflict)
+            }
+            Err(e) => Err(InsertError::Unknown(e.into())),
+        }
+    }
+
+    fn fetch_keys(&self) -> Result<Vec<ApiKey>, RetrieveAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveAllError::Unknown(poisoned(e))),
+        };
+
+        let rows = match fetch_key_rows(&lock, None) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RetrieveAllError::Unknown(e.into())),
+        };
+
+        rows.into_iter()
+            .map(key_from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RetrieveAllError::Unknown)
+    }
+
+    fn fetch_key(&self, hash: String) -> Result<ApiKey, RetrieveError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(RetrieveError::Unknown(poisoned(e))),
+        };
+
+        let row = match fetch_key_rows(&lock, Some(hash)) {
+            Ok(rows) => match rows.into_iter().next() {
+                Some(row) => row,
+                None => return Err(RetrieveError::NotFound),
+            },
+            Err(e) => return Err(RetrieveError::Unknown(e.into())),
+        };
+
+        key_from_row(row).map_err(RetrieveError::Unknown)
+    }
+
+    fn revoke_key(&self, name: KeyName) -> Result<(), DeleteError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(DeleteError::Unknown(poisoned(e))),
+        };
+
+        match lock.execute(
+            "update api_keys set revoked = 1 where name = ?",
+            params![String::from(name)],
+        ) {
+            Ok(0) => Err(DeleteError::NotFound),
+            Ok(_) => Ok(()),
+            Err(e) => Err(DeleteError::Unknown(e.into())),
+        }
+    }
+}
+
+/// Inserts a Pokemon within an open transaction, which is left to the caller
+/// to commit.
+fn insert_pokemon(connection: &Connection, pokemon: &Pokemon) -> Result<(), InsertError> {
+    match unknown_ability(connection, &pokemon.abilities) {
+        Ok(None) => {}
+        Ok(Some(name)) => return Err(InsertError::UnknownAbility(name)),
+        Err(e) => return Err(InsertError::Unknown(e.into())),
+    }
+
+    let number = u16::from(pokemon.number.clone());
+    let values = stats_columns(pokemon.stats);
+
+    let inserted = if pokemon.form.is_default() {
+        connection.execute(
+            "insert into pokemons (number, name, hp, attack, defense, special_attack, special_defense, speed, revision)
+             values (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                number,
+                String::from(pokemon.name.clone()),
+                values[0],
+                values[1],
+                values[2],
+                values[3],
+                values[4],
+                values[5],
+                pokemon.revision,
+            ],
+        )
+    } else {
+        connection.execute(
+            "insert into forms (pokemon_number, form, name, hp, attack, defense, special_attack, special_defense, speed, revision)
+             values (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                number,
+                String::from(pokemon.form.clone()),
+                String::from(pokemon.name.clone()),
+                values[0],
+                values[1],
+                values[2],
+                values[3],
+                values[4],
+                values[5],
+                pokemon.revision,
+            ],
+        )
+    };
+
+    match inserted {
+        Ok(_) => {}
+        Err(rusqlite::Error::SqliteFailure(e, _))
+            if e.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_PRIMARYKEY =>
+        {
+            return Err(InsertError::Conflict);
+        }
+        Err(e) => return Err(InsertError::Unknown(e.into())),
+    }
+
+    if let Err(e) = insert_relations(connection, number, pokemon) {
+        return Err(InsertError::Unknown(e.into()));
+    }
+
+    Ok(())
+}
+
+/// Overwrites a stored Pokemon within an open transaction, which is left to the
+/// caller to commit, and returns its new revision.
+fn update_pokemon(
+    connection: &Connection,
+    pokemon: &Pokemon,
+    expected: Option<Vec<u32>>,
+) -> Result<u32, UpdateError> {
+    match unknown_ability(connection, &pokemon.abilities) {
+        Ok(None) => {}
+        Ok(Some(name)) => return Err(UpdateError::UnknownAbility(name)),
+        Err(e) => return Err(UpdateError::Unknown(e.into())),
+    }
+
+    let number = u16::from(pokemon.number.clone());
+    let values = stats_columns(pokemon.stats);
+
+    let revision = match fetch_revision(connection, number, &pokemon.form) {
+        Ok(Some(revision))
+            if expected
+                .as_ref()
+                .is_some_and(|expected| !expected.contains(&revision)) =>
+        {
+            return Err(UpdateError::Stale(revision))
+        }
+        Ok(Some(revision)) => revision,
+        Ok(None) => return Err(UpdateError::NotFound),
+        Err(e) => return Err(UpdateError::Unknown(e.into())),
+    };
+
+    let updated = if pokemon.form.is_default() {
+        connection.execute(
+            "update pokemons
+             set name = ?, hp = ?, attack = ?, defense = ?, special_attack = ?, special_defense = ?, speed = ?,
+                 revision = revision + 1
+             where number = ?",
+            params![
+                String::from(pokemon.name.clone()),
+                values[0],
+                values[1],
+                values[2],
+                values[3],
+                values[4],
+                values[5],
+                number,
+            ],
+        )
+    } else {
+        connection.execute(
+            "update forms
+             set name = ?, hp = ?, attack = ?, defense = ?, special_attack = ?, special_defense = ?, speed = ?,
+                 revision = revision + 1
+             where pokemon_number = ? and form = ?",
+            params![
+                String::from(pokemon.name.clone()),
+                values[0],
+                values[1],
+                values[2],
+                values[3],
+                values[4],
+                values[5],
+                number,
+                String::from(pokemon.form.clone()),
+            ],
+        )
+    };
+
+    match updated {
+        Ok(0) => return Err(UpdateError::NotFound),
+        Ok(_) => {}
+        Err(e) => return Err(UpdateError::Unknown(e.into())),
+    }
+
+    if let Err(e) = delete_relations(connection, number, &pokemon.form) {
+        return Err(UpdateError::Unknown(e.into()));
+    }
+
+    if let Err(e) = insert_relations(connection, number, pokemon) {
+        return Err(UpdateError::Unknown(e.into()));
+    }
+
+    Ok(revision + 1)
+}
+
+/// Replaces a form as `update_pokemon` does and records the change, leaving
+/// the transaction to the caller to commit.
+fn replace_pokemon(
+    connection: &Connection,
+    pokemon: Pokemon,
+    expected: Option<Vec<u32>>,
+    actor: &Actor,
+) -> Result<Pokemon, UpdateError> {
+    let before = match fetch_forms_before(
+        connection,
+        pokemon.number.clone(),
+        Some(pokemon.form.clone()),
+    ) {
+        Ok(forms) => match forms.into_iter().next() {
+            Some(before) => before,
+            None => return Err(UpdateError::NotFound),
+        },
+        Err(e) => return Err(UpdateError::Unknown(e)),
+    };
+
+    let revision = update_pokemon(connection, &pokemon, expected)?;
+    let after = pokemon.with_revision(revision);
+
+    match record(
+        connection,
+        &AuditEvent::updated(actor, before, after.clone()),
+    ) {
+        Ok(()) => Ok(after),
+        Err(e) => Err(UpdateError::Unknown(e.into())),
+    }
+}
+
+/// Every stored form of a Pokemon, or only `form`, as they are before a change.
+fn fetch_forms_before(
+    connection: &Connection,
+    number: PokemonNumber,
+    form: Option<FormSlug>,
+) -> Result<Vec<Pokemon>, Source> {
+    fetch_form_rows(connection, number, form, false)?
+        .into_iter()
+        .map(pokemon_from_row)
+        .collect()
+}
+
+/// Tells whether the same form of a Pokemon is in the trash.
+fn is_form_trashed(connection: &Connection, pokemon: &Pokemon) -> Result<bool, rusqlite::Error> {
+    connection.query_row(
+        "select exists(
+             select 1 from pokemons where number = ?1 and ?2 = 'default' and deleted_at is not null
+             union all
+             select 1 from forms where pokemon_number = ?1 and form = ?2 and deleted_at is not null
+         )",
+        params![
+            u16::from(pokemon.number.clone()),
+            String::from(pokemon.form.clone())
+        ],
+        |row| row.get(0),
+    )
+}
+
+/// Removes a deleted form for good and records it. The forms and relations of
+/// a default form cascade from it.
+fn purge_form(
+    connection: &Connection,
+    pokemon: Pokemon,
+    actor: &Actor,
+    purged_at: &str,
+) -> Result<(), rusqlite::Error> {
+    let number = u16::from(pokemon.number.clone());
+    match pokemon.form.is_default() {
+        true => {
+            connection.execute("delete from pokemons where number = ?", params![number])?;
+        }
+        false => {
+            connection.execute(
+                "delete from forms where pokemon_number = ? and form = ?",
+                params![number, String::from(pokemon.form.clone())],
+            )?;
+            delete_relations(connection, number, &pokemon.form)?;
+        }
+    }
+
+    record(
+        connection,
+        &AuditEvent::purged(actor, pokemon).at(String::from(purged_at)),
+    )
+}
+
+/// Appends an event to the audit log, which numbers it.
+fn record(connection: &Connection, event: &AuditEvent) -> Result<(), rusqlite::Error> {
+    connection.execute(
+        "insert into audit_events (action, pokemon_number, form, actor, request_id, timestamp, before, after)
+         values (?, ?, ?, ?, ?, ?, ?, ?)",
+        params![
+            String::from(event.action),
+            u16::from(event.number.clone()),
+            String::from(event.form.clone()),
+            event.actor,
+            event.request_id,
+            event.timestamp,
+            event.before.as_ref().map(audit::encode),
+            event.after.as_ref().map(audit::encode),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// The revision a form is inserted at: the first one, or the one after the
+/// last recorded in the audit log when the form was purged before.
+fn next_recorded_revision(connection: &Connection, pokemon: &Pokemon) -> Result<u32, Source> {
+    let snapshot = connection
+        .query_row(
+            "select coalesce(after, before) from audit_events
+             where pokemon_number = ? and form = ?
+             order by id desc limit 1",
+            params![
+                u16::from(pokemon.number.clone()),
+                String::from(pokemon.form.clone())
+            ],
+            |row| row.get::<usize, Option<String>>(0),
+        )
+        .optional()?
+        .flatten();
+
+    match snapshot {
+        Some(json) => Ok(audit::revision(&json)? + 1),
+        None => Ok(Pokemon::FIRST_REVISION),
+    }
+}
+
+/// The revision of a stored form, `None` when there is no such form.
+fn fetch_revision(
+    connection: &Connection,
+    number: u16,
+    form: &FormSlug,
+) -> Result<Option<u32>, rusqlite::Error> {
+    connection
+        .query_row(
+            "select revision from pokemons where number = ?1 and ?2 = 'default' and deleted_at is null
+             union all
+             select revision from forms where pokemon_number = ?1 and form = ?2 and deleted_at is null",
+            params![number, String::from(form.clone())],
+            |row| row.get::<usize, u32>(0),
+        )
+        .optional()
+}
+
+/// Returns the first of `abilities` missing from the catalogue.
+fn unknown_ability(
+    connection: &Connection,
+    abilities: &PokemonAbilities,
+) -> Result<Option<AbilityName>, rusqlite::Error> {
+    let mut stmt = connection.prepare_cached("select 1 from abilities where name = ?")?;
+
+    for (_, name) in abilities.iter() {
+        if !stmt.exists(params![String::from(name.clone())])? {
+            return Ok(Some(name.clone()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Adds the abilities missing from the catalogue, without a description.
+fn insert_missing_abilities(
+    connection: &Connection,
+    abilities: &PokemonAbilities,
+) -> Result<(), rusqlite::Error> {
+    let mut stmt = connection
+        .prepare_cached("insert or ignore into abilities (name, description) values (?, '')")?;
+
+    for (_, name) in abilities.iter() {
+        stmt.execute(params![String::from(name.clone())])?;
     }
 
-    fn delete_pokemon(&self, number: PokemonNumber) -> Result<(), DeleteError> {
-        let lock = match self.connection.lock() {
-            Ok(lock) => lock,
-            _ => return Err(DeleteError::Unknown),
-        };
+    Ok(())
+}
 
-        match lock.execute(
-            "delete from pokemons where number = ?",
-            params![u16::from(number)],
-        ) {
-            Ok(0) => Err(DeleteError::NotFound),
-            Ok(_) => Ok(()),
-            Err(_) => Err(DeleteError::Unknown),
-        }
+/// Stores the types and abilities of a Pokemon whose row already exists.
+fn insert_relations(
+    connection: &Connection,
+    number: u16,
+    pokemon: &Pokemon,
+) -> Result<(), rusqlite::Error> {
+    let form = String::from(pokemon.form.clone());
+
+    for _type in Vec::<String>::from(pokemon.types.clone()) {
+        connection.execute(
+            "insert into types (pokemon_number, form, name) values (?, ?, ?)",
+            params![number, form, _type],
+        )?;
+    }
+
+    for value in Vec::<SlotValue>::from(pokemon.abilities.clone()) {
+        connection.execute(
+            "insert into pokemon_abilities (pokemon_number, form, slot, ability_name) values (?, ?, ?, ?)",
+            params![number, form, value.slot, value.name],
+        )?;
     }
+
+    Ok(())
 }
 
-fn fetch_pokemon_rows(
+/// Removes the types and abilities of a single form of a Pokemon.
+fn delete_relations(
+    connection: &Connection,
+    number: u16,
+    form: &FormSlug,
+) -> Result<(), rusqlite::Error> {
+    let form = String::from(form.clone());
+
+    connection.execute(
+        "delete from types where pokemon_number = ? and form = ?",
+        params![number, form],
+    )?;
+    connection.execute(
+        "delete from pokemon_abilities where pokemon_number = ? and form = ?",
+        params![number, form],
+    )?;
+
+    Ok(())
+}
+
+/// A Pokemon as stored, the stats being either all set or all missing.
+struct PokemonRow {
+    number: u16,
+    form: String,
+    name: String,
+    types: Vec<String>,
+    stats: Option<[u16; 6]>,
+    abilities: Vec<SlotValue>,
+    revision: u32,
+    deleted_at: Option<String>,
+}
+
+/// Rebuilds a Pokemon from a stored row, which fails if the row was written
+/// by hand and breaks the entity rules.
+fn pokemon_from_row(row: PokemonRow) -> Result<Pokemon, Source> {
+    let stats = match row.stats {
+        Some(values) => Some(BaseStats::try_from(StatValues::from(values))?),
+        None => None,
+    };
+
+    Ok(Pokemon::new(
+        PokemonNumber::try_from(row.number)?,
+        PokemonName::try_from(row.name)?,
+        PokemonTypes::try_from(row.types)?,
+    )
+    .with_form(FormSlug::try_from(row.form)?)
+    .with_stats(stats)
+    .with_abilities(PokemonAbilities::try_from(row.abilities)?)
+    .with_revision(row.revision))
+}
+
+fn deleted_pokemon_from_row(row: PokemonRow) -> Result<DeletedPokemon, Source> {
+    let deleted_at = row.deleted_at.clone().unwrap_or_default();
+    Ok(DeletedPokemon {
+        pokemon: pokemon_from_row(row)?,
+        deleted_at,
+    })
+}
+
+fn ability_from_row(row: (String, String)) -> Result<Ability, Source> {
+    Ok(Ability::new(AbilityName::try_from(row.0)?, row.1))
+}
+
+fn evolution_from_row(row: (u16, u16, TriggerValue)) -> Result<Evolution, Source> {
+    Ok(Evolution::new(
+        PokemonNumber::try_from(row.0)?,
+        PokemonNumber::try_from(row.1)?,
+        EvolutionTrigger::try_from(row.2)?,
+    ))
+}
+
+/// A move as stored: name, type, category, power, accuracy and PP.
+type MoveRow = (String, String, String, Option<u8>, Option<u8>, u8);
+
+fn move_from_row(row: MoveRow) -> Result<Move, Source> {
+    Ok(Move {
+        name: MoveName::try_from(row.0)?,
+        move_type: PokemonType::try_from(row.1)?,
+        category: MoveCategory::try_from(row.2)?,
+        power: row.3.map(MovePower::try_from).transpose()?,
+        accuracy: row.4.map(MoveAccuracy::try_from).transpose()?,
+        pp: PowerPoints::try_from(row.5)?,
+    })
+}
+
+/// A learnset entry as stored: move name, method, level and generation.
+type LearnsetRow = (String, String, Option<u8>, u8);
+
+fn learnset_entry_from_row(row: LearnsetRow) -> Result<LearnsetEntry, Source> {
+    Ok(LearnsetEntry::new(
+        MoveName::try_from(row.0)?,
+        LearnMethod::try_from(row.1)?,
+        row.2.map(Level::try_from).transpose()?,
+        Generation::try_from(row.3)?,
+    )?)
+}
+
+/// An API key as stored: name, role, prefix, hash, creation time and whether
+/// it is revoked.
+type KeyRow = (String, String, String, String, String, bool);
+
+fn key_from_row(row: KeyRow) -> Result<ApiKey, Source> {
+    Ok(ApiKey {
+        name: KeyName::try_from(row.0)?,
+        role: Role::try_from(row.1)?,
+        prefix: row.2,
+        hash: row.3,
+        created_at: row.4,
+        revoked: row.5,
+    })
+}
+
+/// An audit event as stored: id, action, number, form, actor, request ID,
+/// timestamp and the snapshots before and after the change.
+type AuditRow = (
+    u64,
+    String,
+    u16,
+    String,
+    String,
+    Option<String>,
+    String,
+    Option<String>,
+    Option<String>,
+);
+
+fn audit_event_from_row(row: AuditRow) -> Result<AuditEvent, Source> {
+    Ok(AuditEvent {
+        id: row.0,
+        action: AuditAction::try_from(row.1)?,
+        number: PokemonNumber::try_from(row.2)?,
+        form: FormSlug::try_from(row.3)?,
+        actor: row.4,
+        request_id: row.5,
+        timestamp: row.6,
+        before: row.7.as_deref().map(audit::decode).transpose()?,
+        after: row.8.as_deref().map(audit::decode).transpose()?,
+    })
+}
+
+/// The values of the stat columns, in table order.
+fn stats_columns(stats: Option<BaseStats>) -> [Option<u16>; 6] {
+    match stats {
+        Some(stats) => <[u16; 6]>::from(StatValues::from(stats)).map(Some),
+        None => [None; 6],
+    }
+}
+
+/// The number and name of the default forms sharing a trigram with `query`,
+/// found through the full-text index, or containing it when it is too short
+/// to have any trigram.
+fn fetch_search_candidates(
     lock: &MutexGuard<'_, Connection>,
-    number: Option<u16>,
-) -> Result<Vec<(u16, String)>, ()> {
-    let (query, params) = match number {
-        Some(number) => (
-            "select number, name from pokemons where number = ?",
-            vec![number],
+    query: &str,
+) -> Result<Vec<(u16, String)>, rusqlite::Error> {
+    let trigrams = search::trigrams(query);
+    let (sql, param) = match trigrams.is_empty() {
+        true => (
+            "select number, name from pokemons where instr(lower(name), ?) > 0 and deleted_at is null",
+            query.trim().to_lowercase(),
+        ),
+        false => (
+            "select pokemons_search.rowid, pokemons_search.name from pokemons_search
+             join pokemons on pokemons.number = pokemons_search.rowid
+             where pokemons_search match ? and pokemons.deleted_at is null",
+            trigrams
+                .iter()
+                .map(|trigram| format!("\"{}\"", trigram.replace('"', "\"\"")))
+                .collect::<Vec<_>>()
+                .join(" OR "),
         ),
-        _ => ("select number, name from pokemons", vec![]),
     };
 
-    let mut stmt = match lock.prepare(query) {
-        Ok(stmt) => stmt,
-        _ => return Err(()),
-    };
+    let mut stmt = lock.prepare_cached(sql)?;
+    let rows = stmt.query_map(params![param], |row| {
+        Ok((row.get::<usize, u16>(0)?, row.get::<usize, String>(1)?))
+    })?;
 
-    let mut rows = match stmt.query(params_from_iter(params)) {
-        Ok(rows) => rows,
-        _ => return Err(()),
-    };
+    rows.collect()
+}
 
-    let mut pokemons = vec![];
+/// Loads every API key, or the single one whose secret has this hash.
+fn fetch_key_rows(
+    lock: &MutexGuard<'_, Connection>,
+    hash: Option<String>,
+) -> Result<Vec<KeyRow>, rusqlite::Error> {
+    let mut stmt = lock.prepare_cached(
+        "select name, role, prefix, hash, created_at, revoked from api_keys where ?1 is null or hash = ?1 order by name",
+    )?;
+    let rows = stmt.query_map(params![hash], |row| {
+        Ok((
+            row.get::<usize, String>(0)?,
+            row.get::<usize, String>(1)?,
+            row.get::<usize, String>(2)?,
+            row.get::<usize, String>(3)?,
+            row.get::<usize, String>(4)?,
+            row.get::<usize, bool>(5)?,
+        ))
+    })?;
+
+    rows.collect()
+}
+
+/// Loads the audit events of a Pokemon, or of every Pokemon since a time, in
+/// the order they were recorded.
+fn fetch_audit_rows(
+    lock: &MutexGuard<'_, Connection>,
+    number: Option<u16>,
+    since: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<AuditRow>, rusqlite::Error> {
+    let mut stmt = lock.prepare_cached(
+        "select id, action, pokemon_number, form, actor, request_id, timestamp, before, after
+         from audit_events
+         where (?1 is null or pokemon_number = ?1) and (?2 is null or timestamp >= ?2)
+         order by id
+         limit coalesce(?3, -1)",
+    )?;
+    let rows = stmt.query_map(params![number, since, limit], |row| {
+        Ok((
+            row.get::<usize, u64>(0)?,
+            row.get::<usize, String>(1)?,
+            row.get::<usize, u16>(2)?,
+            row.get::<usize, String>(3)?,
+            row.get::<usize, String>(4)?,
+            row.get::<usize, Option<String>>(5)?,
+            row.get::<usize, String>(6)?,
+            row.get::<usize, Option<String>>(7)?,
+            row.get::<usize, Option<String>>(8)?,
+        ))
+    })?;
+
+    rows.collect()
+}
+
+/// Loads the abilities catalogue, or the single ability called `name`.
+fn fetch_ability_rows(
+    lock: &MutexGuard<'_, Connection>,
+    name: Option<AbilityName>,
+) -> Result<Vec<(String, String)>, rusqlite::Error> {
+    let mut stmt = lock.prepare_cached(
+        "select name, description from abilities where ?1 is null or name = ?1 order by name",
+    )?;
+    let rows = stmt.query_map(params![name.map(String::from)], |row| {
+        Ok((row.get::<usize, String>(0)?, row.get::<usize, String>(1)?))
+    })?;
+
+    rows.collect()
+}
+
+fn fetch_evolution_rows(
+    lock: &MutexGuard<'_, Connection>,
+) -> Result<Vec<(u16, u16, TriggerValue)>, rusqlite::Error> {
+    let mut stmt = lock.prepare_cached(
+        "select from_number, to_number, trigger, level, item from evolutions
+         join pokemons as parents on parents.number = from_number and parents.deleted_at is null
+         join pokemons as children on children.number = to_number and children.deleted_at is null
+         order by from_number, to_number",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<usize, u16>(0)?,
+            row.get::<usize, u16>(1)?,
+            TriggerValue {
+                kind: row.get::<usize, String>(2)?,
+                level: row.get::<usize, Option<u8>>(3)?,
+                item: row.get::<usize, Option<String>>(4)?,
+            },
+        ))
+    })?;
+
+    rows.collect()
+}
+
+/// Loads the moves catalogue, or the single move called `name`.
+fn fetch_move_rows(
+    lock: &MutexGuard<'_, Connection>,
+    name: Option<MoveName>,
+) -> Result<Vec<MoveRow>, rusqlite::Error> {
+    let mut stmt = lock.prepare_cached(
+        "select name, type, category, power, accuracy, pp from moves where ?1 is null or name = ?1 order by name",
+    )?;
+    let rows = stmt.query_map(params![name.map(String::from)], |row| {
+        Ok((
+            row.get::<usize, String>(0)?,
+            row.get::<usize, String>(1)?,
+            row.get::<usize, String>(2)?,
+            row.get::<usize, Option<u8>>(3)?,
+            row.get::<usize, Option<u8>>(4)?,
+            row.get::<usize, u8>(5)?,
+        ))
+    })?;
+
+    rows.collect()
+}
+
+/// The methods are listed in the order of `LearnMethod`, not alphabetically.
+fn fetch_learnset_rows(
+    lock: &MutexGuard<'_, Connection>,
+    number: PokemonNumber,
+    query: LearnsetQuery,
+) -> Result<Vec<LearnsetRow>, rusqlite::Error> {
+    let mut stmt = lock.prepare_cached(
+        "select move_name, method, level, generation from learnsets
+        where pokemon_number = ?1 and (?2 is null or method = ?2) and (?3 is null or generation = ?3)
+        order by generation,
+            case method when 'level' then 0 when 'tm' then 1 when 'egg' then 2 else 3 end,
+            level, move_name",
+    )?;
+    let rows = stmt.query_map(
+        params![
+            u16::from(number),
+            query.method.map(String::from),
+            query.generation.map(u8::from),
+        ],
+        |row| {
+            Ok((
+                row.get::<usize, String>(0)?,
+                row.get::<usize, String>(1)?,
+                row.get::<usize, Option<u8>>(2)?,
+                row.get::<usize, u8>(3)?,
+            ))
+        },
+    )?;
+
+    rows.collect()
+}
 
-    while let Ok(Some(row)) = rows.next() {
-        match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
-            (Ok(number), Ok(name)) => pokemons.push((number, name)),
-            _ => return Err(()),
+/// Reads the six stat columns starting at `first`, all of them being null
+/// when the stats are unknown.
+fn stats_from_row(row: &Row<'_>, first: usize) -> Result<Option<[u16; 6]>, rusqlite::Error> {
+    let mut stats = [0; 6];
+    for (index, stat) in stats.iter_mut().enumerate() {
+        match row.get::<usize, Option<u16>>(first + index)? {
+            Some(value) => *stat = value,
+            None => return Ok(None),
         }
     }
 
+    Ok(Some(stats))
+}
+
+/// Loads every form of a Pokemon, or only `form`, the default one first, among
+/// the forms in the trash when `deleted` and among the others otherwise.
+fn fetch_form_rows(
+    lock: &Connection,
+    number: PokemonNumber,
+    form: Option<FormSlug>,
+    deleted: bool,
+) -> Result<Vec<PokemonRow>, rusqlite::Error> {
+    let number = u16::from(number);
+    let form = form.map(String::from);
+
+    let mut types: HashMap<String, Vec<String>> = HashMap::new();
+    let mut stmt = lock.prepare_cached(
+        "select form, name from types
+         where pokemon_number = ?1 and (?2 is null or form = ?2)
+         order by name",
+    )?;
+    let mut rows = stmt.query(params![number, form])?;
+    while let Some(row) = rows.next()? {
+        types
+            .entry(row.get::<usize, String>(0)?)
+            .or_default()
+            .push(row.get::<usize, String>(1)?);
+    }
+
+    let mut abilities: HashMap<String, Vec<SlotValue>> = HashMap::new();
+    let mut stmt = lock.prepare_cached(
+        "select form, slot, ability_name from pokemon_abilities
+         where pokemon_number = ?1 and (?2 is null or form = ?2)",
+    )?;
+    let mut rows = stmt.query(params![number, form])?;
+    while let Some(row) = rows.next()? {
+        abilities
+            .entry(row.get::<usize, String>(0)?)
+            .or_default()
+            .push(SlotValue {
+                slot: row.get::<usize, String>(1)?,
+                name: row.get::<usize, String>(2)?,
+            });
+    }
+
+    let mut stmt = lock.prepare_cached(
+        "select form, name, hp, attack, defense, special_attack, special_defense, speed, revision, deleted_at from (
+             select 'default' as form, name, hp, attack, defense, special_attack, special_defense, speed, revision, deleted_at
+             from pokemons where number = ?1
+             union all
+             select form, name, hp, attack, defense, special_attack, special_defense, speed, revision, deleted_at
+             from forms where pokemon_number = ?1
+         )
+         where (?2 is null or form = ?2) and (deleted_at is not null) = ?3
+         order by form <> 'default', form",
+    )?;
+    let mut rows = stmt.query(params![number, form, deleted])?;
+
+    let mut pokemons = vec![];
+    while let Some(row) = rows.next()? {
+        let form = row.get::<usize, String>(0)?;
+        pokemons.push(PokemonRow {
+            number,
+            name: row.get::<usize, String>(1)?,
+            types: types.remove(&form).unwrap_or_default(),
+            stats: stats_from_row(row, 2)?,
+            abilities: abilities.remove(&form).unwrap_or_default(),
+            revision: row.get::<usize, u32>(8)?,
+            deleted_at: row.get::<usize, Option<String>>(9)?,
+            form,
+        });
+    }
+
     Ok(pokemons)
 }
 
-fn fetch_type_rows(lock: &MutexGuard<Connection>, number: u16) -> Result<Vec<String>, ()> {
-    let mut stmt = match lock.prepare("select name from types where pokemon_number = ?") {
-        Ok(stmt) => stmt,
-        Err(_) => return Err(()),
+/// Loads the stored forms holding an ability, by number, the default one first.
+fn fetch_ability_holder_rows(
+    connection: &Connection,
+    name: &AbilityName,
+) -> Result<Vec<PokemonRow>, Source> {
+    let name = String::from(name.clone());
+    let mut stmt = connection.prepare_cached(
+        "select distinct pokemon_number from pokemon_abilities where ability_name = ? order by 1",
+    )?;
+    let numbers = stmt
+        .query_map(params![name], |row| row.get::<usize, u16>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut rows = vec![];
+    for number in numbers {
+        rows.extend(
+            fetch_form_rows(connection, PokemonNumber::try_from(number)?, None, false)?
+                .into_iter()
+                .filter(|row| row.abilities.iter().any(|ability| ability.name == name)),
+        );
+    }
+
+    Ok(rows)
+}
+
+/// Loads the forms in the trash, those deleted before `before` only when given,
+/// by number, the default one first.
+fn fetch_trash_rows(
+    connection: &Connection,
+    before: Option<&str>,
+) -> Result<Vec<PokemonRow>, Source> {
+    let mut stmt = connection.prepare_cached(
+        "select number from pokemons where deleted_at is not null and (?1 is null or deleted_at < ?1)
+         union
+         select pokemon_number from forms where deleted_at is not null and (?1 is null or deleted_at < ?1)
+         order by 1",
+    )?;
+    let numbers = stmt
+        .query_map(params![before], |row| row.get::<usize, u16>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut rows = vec![];
+    for number in numbers {
+        rows.extend(
+            fetch_form_rows(connection, PokemonNumber::try_from(number)?, None, true)?
+                .into_iter()
+                .filter(|row| {
+                    before.is_none_or(|before| {
+                        row.deleted_at
+                            .as_deref()
+                            .is_some_and(|deleted_at| deleted_at < before)
+                    })
+                }),
+        );
+    }
+
+    Ok(rows)
+}
+
+/// Loads the page of Pokemons described by `query` together with their types
+/// in a single statement, one row per (Pokemon, type) pair, then the abilities
+/// of the same page in a second one.
+fn fetch_pokemon_rows(
+    lock: &MutexGuard<'_, Connection>,
+    query: &FetchAllQuery,
+) -> Result<Vec<PokemonRow>, rusqlite::Error> {
+    let mut filters = String::new();
+    let mut params = vec![];
+
+    if let Some(pokemon_type) = query.pokemon_type {
+        filters.push_str(
+            " and exists (select 1 from types where types.pokemon_number = pokemons.number and types.form = 'default' and types.name = ?)",
+        );
+        params.push(Value::Text(String::from(pokemon_type)));
+    }
+
+    if let Some(min) = query.min_number.clone() {
+        filters.push_str(" and number >= ?");
+        params.push(Value::Integer(u16::from(min).into()));
+    }
+
+    if let Some(max) = query.max_number.clone() {
+        filters.push_str(" and number <= ?");
+        params.push(Value::Integer(u16::from(max).into()));
+    }
+
+    let direction = match query.order {
+        SortOrder::Asc => "asc",
+        SortOrder::Desc => "desc",
     };
 
-    let mut rows = match stmt.query([number]) {
-        Ok(rows) => rows,
-        Err(_) => return Err(()),
+    let order = match query.sort_by {
+        SortBy::Number => format!("pokemons.number {}", direction),
+        SortBy::Name => format!("pokemons.name {0}, pokemons.number {0}", direction),
     };
 
-    let mut type_rows = vec![];
+    params.push(Value::Integer(query.limit.map_or(-1, i64::from)));
+    params.push(Value::Integer(query.offset.into()));
+
+    let page = format!(
+        "select * from pokemons where deleted_at is null{filters} order by {order} limit ? offset ?",
+        filters = filters,
+        order = order,
+    );
+
+    let sql = format!(
+        "select pokemons.number, pokemons.name, types.name,
+                pokemons.hp, pokemons.attack, pokemons.defense,
+                pokemons.special_attack, pokemons.special_defense, pokemons.speed,
+                pokemons.revision
+         from ({page}) as pokemons
+         left join types on types.pokemon_number = pokemons.number and types.form = 'default'
+         order by {order}, types.name",
+        page = page,
+        order = order,
+    );
+
+    let mut abilities: HashMap<u16, Vec<SlotValue>> = HashMap::new();
+    let mut stmt = lock.prepare_cached(&format!(
+        "select pokemon_abilities.pokemon_number, pokemon_abilities.slot, pokemon_abilities.ability_name
+         from ({page}) as pokemons
+         join pokemon_abilities on pokemon_abilities.pokemon_number = pokemons.number
+             and pokemon_abilities.form = 'default'",
+        page = page,
+    ))?;
+    let mut rows = stmt.query(params_from_iter(params.iter()))?;
+    while let Some(row) = rows.next()? {
+        abilities
+            .entry(row.get::<usize, u16>(0)?)
+            .or_default()
+            .push(SlotValue {
+                slot: row.get::<usize, String>(1)?,
+                name: row.get::<usize, String>(2)?,
+            });
+    }
+
+    let mut stmt = lock.prepare_cached(&sql)?;
+    let mut rows = stmt.query(params_from_iter(params))?;
+
+    let mut pokemons: Vec<PokemonRow> = vec![];
+
+    while let Some(row) = rows.next()? {
+        let number = row.get::<usize, u16>(0)?;
+        let type_name = row.get::<usize, Option<String>>(2)?;
+
+        match pokemons.last_mut() {
+            Some(last) if last.number == number => last.types.extend(type_name),
+            _ => pokemons.push(PokemonRow {
+                number,
+                form: FormSlug::default().into(),
+                name: row.get::<usize, String>(1)?,
+                types: type_name.into_iter().collect(),
+                stats: stats_from_row(row, 3)?,
+                abilities: abilities.remove(&number).unwrap_or_default(),
+                revision: row.get::<usize, u32>(9)?,
+                deleted_at: None,
+            }),
+        }
+    }
+
+    Ok(pokemons)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use super::*;
+
+    fn full_dex() -> SqliteRepository {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+
+        for number in 1..899u16 {
+            let first = PokemonType::ALL[number as usize % PokemonType::ALL.len()];
+            let second = PokemonType::ALL[(number as usize + 7) % PokemonType::ALL.len()];
+            let types = match number % 2 {
+                0 => vec![String::from(first)],
+                _ => vec![String::from(first), String::from(second)],
+            };
+
+            let _ = repo.insert(
+                Pokemon::new(
+                    PokemonNumber::try_from(number).unwrap(),
+                    PokemonName::try_from(format!("Pokemon #{:03}", number)).unwrap(),
+                    PokemonTypes::try_from(types).unwrap(),
+                ),
+                &Actor::ci(),
+            );
+        }
+
+        repo
+    }
+
+    #[test]
+    fn it_should_group_the_types_of_every_pokemon_in_the_page() {
+        let repo = full_dex();
+        let query = FetchAllQuery {
+            limit: Some(2),
+            offset: 2,
+            ..FetchAllQuery::default()
+        };
+
+        match repo.fetch_all(query) {
+            Ok(pokemons) => {
+                assert_eq!(pokemons.len(), 2);
+                assert_eq!(u16::from(pokemons[0].number.clone()), 3);
+                assert_eq!(Vec::<String>::from(pokemons[0].types.clone()).len(), 2);
+                assert_eq!(u16::from(pokemons[1].number.clone()), 4);
+                assert_eq!(Vec::<String>::from(pokemons[1].types.clone()).len(), 1);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_store_the_base_stats_when_they_are_known() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let _ = repo.insert(
+            Pokemon::pikachu().with_stats(Some(BaseStats::pikachu())),
+            &Actor::ci(),
+        );
+        let _ = repo.insert(Pokemon::charmander(), &Actor::ci());
+
+        match (
+            repo.fetch_one(PokemonNumber::pikachu(), FormSlug::default()),
+            repo.fetch_one(PokemonNumber::charmander(), FormSlug::default()),
+        ) {
+            (Ok(pikachu), Ok(charmander)) => {
+                assert_eq!(pikachu.stats, Some(BaseStats::pikachu()));
+                assert_eq!(charmander.stats, None);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_store_the_abilities_in_their_slot() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let pikachu = Pokemon::pikachu().with_abilities(PokemonAbilities::pikachu());
+
+        match repo.insert(pikachu.clone(), &Actor::ci()) {
+            Err(InsertError::UnknownAbility(name)) => assert_eq!(name, AbilityName::static_()),
+            _ => unreachable!(),
+        }
+
+        for name in [AbilityName::static_(), AbilityName::lightning_rod()] {
+            let _ = repo.insert_ability(Ability::new(name, String::new()));
+        }
+        let _ = repo.insert(pikachu.clone(), &Actor::ci());
+        let _ = repo.insert(Pokemon::charmander(), &Actor::ci());
+        let _ = repo.insert(pikachu.with_form(FormSlug::alola()), &Actor::ci());
+
+        match repo.fetch_ability_holders(AbilityName::lightning_rod()) {
+            Ok(pokemons) => {
+                assert_eq!(
+                    pokemons.iter().map(|p| p.form.clone()).collect::<Vec<_>>(),
+                    vec![FormSlug::default(), FormSlug::alola()]
+                );
+                assert_eq!(pokemons[0].abilities, PokemonAbilities::pikachu());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_allow_a_single_pre_evolution() {
+        let repo = full_dex();
+        let evolution = |from: u16, to: u16| {
+            Evolution::new(
+                PokemonNumber::try_from(from).unwrap(),
+                PokemonNumber::try_from(to).unwrap(),
+                EvolutionTrigger::Trade,
+            )
+        };
+
+        assert!(repo.insert_evolution(evolution(133, 134)).is_ok());
+        assert!(repo.insert_evolution(evolution(133, 135)).is_ok());
+        assert!(matches!(
+            repo.insert_evolution(evolution(133, 134)),
+            Err(InsertError::Conflict)
+        ));
+        assert!(matches!(
+            repo.insert_evolution(evolution(132, 134)),
+            Err(InsertError::Invalid(ValidationError::HasPreEvolution {
+                number: 134,
+                from: 133
+            }))
+        ));
+        assert!(matches!(
+            repo.insert_evolution(evolution(134, 133)),
+            Err(InsertError::Invalid(ValidationError::EvolutionCycle {
+                from: 134,
+                to: 133
+            }))
+        ));
+
+        // The evolution of a deleted Pokemon is hidden, and kept until it is purged.
+        let _ = repo.delete_pokemon(
+            PokemonNumber::try_from(135).unwrap(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+        assert!(matches!(
+            repo.insert_evolution(evolution(133, 135)),
+            Err(InsertError::Conflict)
+        ));
+
+        match repo.fetch_evolutions() {
+            Ok(evolutions) => assert_eq!(evolutions, vec![evolution(133, 134)]),
+            _ => unreachable!(),
+        }
+
+        let _ = repo.purge(String::from("9999-12-31T00:00:00.000Z"), &Actor::ci());
+        assert!(matches!(
+            repo.insert_evolution(evolution(133, 135)),
+            Err(InsertError::Unknown(_))
+        ));
+    }
+
+    #[test]
+    fn it_should_store_the_alternate_forms_next_to_the_default_one() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let alola = Pokemon::pikachu()
+            .with_form(FormSlug::alola())
+            .with_stats(Some(BaseStats::pikachu()));
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(Pokemon::charmander(), &Actor::ci());
+
+        assert!(repo.insert(alola.clone(), &Actor::ci()).is_ok());
+        assert!(matches!(
+            repo.insert(alola.clone(), &Actor::ci()),
+            Err(InsertError::Conflict)
+        ));
+        assert!(matches!(
+            repo.insert(Pokemon::pikachu(), &Actor::ci()),
+            Err(InsertError::Conflict)
+        ));
+
+        match (
+            repo.fetch_one(PokemonNumber::pikachu(), FormSlug::alola()),
+            repo.fetch_forms(PokemonNumber::pikachu()),
+            repo.fetch_all(FetchAllQuery::default()),
+        ) {
+            (Ok(pokemon), Ok(forms), Ok(pokemons)) => {
+                assert_eq!(pokemon.form, FormSlug::alola());
+                assert_eq!(pokemon.stats, Some(BaseStats::pikachu()));
+                assert_eq!(
+                    forms.into_iter().map(|p| p.form).collect::<Vec<_>>(),
+                    vec![FormSlug::default(), FormSlug::alola()]
+                );
+                assert_eq!(pokemons.len(), 2);
+            }
+            _ => unreachable!(),
+        }
+
+        assert!(repo
+            .delete_pokemon(
+                PokemonNumber::pikachu(),
+                FormSlug::alola(),
+                None,
+                &Actor::ci()
+            )
+            .is_ok());
+        assert!(repo
+            .fetch_one(PokemonNumber::pikachu(), FormSlug::default())
+            .is_ok());
+
+        let _ = repo.insert(alola, &Actor::ci());
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+        match repo.fetch_forms(PokemonNumber::pikachu()) {
+            Ok(forms) => assert!(forms.is_empty()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_keep_the_deleted_forms_in_the_trash_until_they_are_purged() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(
+            Pokemon::pikachu().with_form(FormSlug::alola()),
+            &Actor::ci(),
+        );
+        let _ = repo.insert(Pokemon::charmander(), &Actor::ci());
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+
+        assert!(matches!(
+            repo.fetch_one(PokemonNumber::pikachu(), FormSlug::default()),
+            Err(RetrieveError::NotFound)
+        ));
+        match (repo.fetch_all(FetchAllQuery::default()), repo.fetch_trash()) {
+            (Ok(pokemons), Ok(trash)) => {
+                assert_eq!(pokemons.len(), 1);
+                assert_eq!(
+                    trash
+                        .iter()
+                        .map(|d| d.pokemon.form.clone())
+                        .collect::<Vec<_>>(),
+                    vec![FormSlug::default(), FormSlug::alola()]
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        assert!(matches!(
+            repo.restore(PokemonNumber::pikachu(), FormSlug::alola(), &Actor::ci()),
+            Err(RestoreError::DefaultFormDeleted)
+        ));
+        match repo.restore(PokemonNumber::pikachu(), FormSlug::default(), &Actor::ci()) {
+            Ok(restored) => assert_eq!(
+                restored.into_iter().map(|p| p.revision).collect::<Vec<_>>(),
+                vec![Pokemon::FIRST_REVISION; 2]
+            ),
+            _ => unreachable!(),
+        }
+        match (
+            repo.fetch_forms(PokemonNumber::pikachu()),
+            repo.fetch_trash(),
+        ) {
+            (Ok(forms), Ok(trash)) => {
+                assert_eq!(forms.len(), 2);
+                assert!(trash.is_empty());
+            }
+            _ => unreachable!(),
+        }
+
+        // Only what was deleted before the cutoff is purged.
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::alola(),
+            None,
+            &Actor::ci(),
+        );
+        match repo.purge(String::from("2000-01-01T00:00:00.000Z"), &Actor::ci()) {
+            Ok(purged) => assert!(purged.is_empty()),
+            _ => unreachable!(),
+        }
+        match repo.purge(String::from("9999-12-31T00:00:00.000Z"), &Actor::ci()) {
+            Ok(purged) => assert_eq!(
+                purged.into_iter().map(|p| p.form).collect::<Vec<_>>(),
+                vec![FormSlug::alola()]
+            ),
+            _ => unreachable!(),
+        }
+        assert!(matches!(
+            repo.restore(PokemonNumber::pikachu(), FormSlug::alola(), &Actor::ci()),
+            Err(RestoreError::NotFound)
+        ));
+
+        // A deleted form has to be purged explicitly before it is created again.
+        let _ = repo.delete_pokemon(
+            PokemonNumber::charmander(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+        assert!(matches!(
+            repo.insert(Pokemon::charmander(), &Actor::ci()),
+            Err(InsertError::Deleted)
+        ));
+        let _ = repo.purge(String::from("9999-12-31T00:00:00.000Z"), &Actor::ci());
+        match repo.insert(Pokemon::charmander(), &Actor::ci()) {
+            Ok(charmander) => assert_eq!(charmander.revision, Pokemon::FIRST_REVISION + 1),
+            _ => unreachable!(),
+        }
+        match (
+            repo.fetch_trash(),
+            repo.fetch_history(PokemonNumber::charmander()),
+        ) {
+            (Ok(trash), Ok(events)) => {
+                assert!(trash.is_empty());
+                assert_eq!(
+                    events.iter().map(|e| e.action).collect::<Vec<_>>(),
+                    vec![
+                        AuditAction::Insert,
+                        AuditAction::Delete,
+                        AuditAction::Purge,
+                        AuditAction::Insert
+                    ]
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_leave_the_trash_as_it_is_when_an_import_rejects_a_pokemon() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+
+        match repo.import(
+            vec![Pokemon::pikachu().with_abilities(PokemonAbilities::pikachu())],
+            OnConflict::Replace,
+            &Actor::ci(),
+        ) {
+            Ok(outcomes) => assert_eq!(outcomes, vec![ImportOutcome::Deleted]),
+            _ => unreachable!(),
+        }
+        match (
+            repo.fetch_trash(),
+            repo.fetch_history(PokemonNumber::pikachu()),
+            repo.fetch_abilities(),
+        ) {
+            (Ok(trash), Ok(events), Ok(abilities)) => {
+                assert_eq!(trash.len(), 1);
+                assert!(events.iter().all(|e| e.action != AuditAction::Purge));
+                assert!(abilities.is_empty());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_roll_back_an_import_on_the_first_conflict() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let batch = || {
+            vec![
+                Pokemon::charmander(),
+                Pokemon::pikachu().with_form(FormSlug::alola()),
+                Pokemon::pikachu().with_abilities(PokemonAbilities::pikachu()),
+                Pokemon::pikachu().with_stats(Some(BaseStats::pikachu())),
+            ]
+        };
+
+        assert!(matches!(
+            repo.import(batch(), OnConflict::Fail, &Actor::ci()),
+            Err(ImportError::Conflict(2))
+        ));
+        assert!(repo
+            .fetch_one(PokemonNumber::charmander(), FormSlug::default())
+            .is_err());
+
+        match repo.import(batch(), OnConflict::Replace, &Actor::ci()) {
+            Ok(outcomes) => assert_eq!(
+                outcomes,
+                vec![
+                    ImportOutcome::Inserted(Pokemon::FIRST_REVISION),
+                    ImportOutcome::Inserted(Pokemon::FIRST_REVISION),
+                    ImportOutcome::Replaced(2),
+                    ImportOutcome::Replaced(3),
+                ]
+            ),
+            _ => unreachable!(),
+        }
+
+        // The abilities missing from the catalogue are added to it.
+        match repo.fetch_abilities() {
+            Ok(abilities) => assert_eq!(
+                abilities.into_iter().map(|a| a.name).collect::<Vec<_>>(),
+                vec![AbilityName::lightning_rod(), AbilityName::static_()]
+            ),
+            _ => unreachable!(),
+        }
+
+        match repo.fetch_one(PokemonNumber::pikachu(), FormSlug::default()) {
+            Ok(pikachu) => assert_eq!(pikachu.stats, Some(BaseStats::pikachu())),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_list_a_learnset_in_method_order() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert_move(Move::thunderbolt());
+        let _ = repo.insert_move(Move::quick_attack());
+
+        let generation = Generation::try_from(8).unwrap();
+        let entry = |name, method, level: Option<u8>| {
+            LearnsetEntry::new(
+                name,
+                method,
+                level.map(|l| Level::try_from(l).unwrap()),
+                generation,
+            )
+            .unwrap()
+        };
+        let tm = entry(MoveName::thunderbolt(), LearnMethod::Tm, None);
+        let level = entry(MoveName::thunderbolt(), LearnMethod::Level, Some(36));
+        let start = entry(MoveName::quick_attack(), LearnMethod::Level, Some(1));
+
+        for e in [tm.clone(), level.clone(), start.clone()] {
+            let _ = repo.insert_learnset_entry(PokemonNumber::pikachu(), e);
+        }
+        assert!(matches!(
+            repo.insert_learnset_entry(PokemonNumber::pikachu(), tm.clone()),
+            Err(InsertError::Conflict)
+        ));
+
+        match repo.fetch_learnset(PokemonNumber::pikachu(), LearnsetQuery::default()) {
+            Ok(entries) => assert_eq!(entries, vec![start.clone(), level.clone(), tm]),
+            _ => unreachable!(),
+        }
+
+        let query = LearnsetQuery {
+            method: Some(LearnMethod::Level),
+            ..LearnsetQuery::default()
+        };
+        match repo.fetch_learnset(PokemonNumber::pikachu(), query) {
+            Ok(entries) => assert_eq!(entries, vec![start, level]),
+            _ => unreachable!(),
+        }
+
+        match repo.fetch_move(MoveName::thunderbolt()) {
+            Ok(pokemon_move) => assert_eq!(pokemon_move, Move::thunderbolt()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_keep_the_search_index_in_sync_with_the_pokemons() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let names = |query: &str| match repo.search(String::from(query), 10) {
+            Ok(pokemons) => pokemons
+                .into_iter()
+                .map(|p| String::from(p.name))
+                .collect::<Vec<_>>(),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(names("pikchu"), vec!["Pikachu"]);
+        assert_eq!(names("pi"), vec!["Pikachu"]);
+
+        let _ = repo.update(
+            Pokemon::new(
+                PokemonNumber::pikachu(),
+                PokemonName::try_from(String::from("Raichu")).unwrap(),
+                PokemonTypes::pikachu(),
+            ),
+            None,
+            &Actor::ci(),
+        );
+        assert!(names("pikachu").is_empty());
+        assert_eq!(names("raichu"), vec!["Raichu"]);
+
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+        assert!(names("raichu").is_empty());
+    }
+
+    #[test]
+    fn it_should_only_change_a_form_at_the_expected_revision() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(
+            Pokemon::pikachu().with_form(FormSlug::alola()),
+            &Actor::ci(),
+        );
+
+        match repo.update(Pokemon::pikachu(), Some(vec![1]), &Actor::ci()) {
+            Ok(pokemon) => assert_eq!(pokemon.revision, 2),
+            _ => unreachable!(),
+        }
+        assert!(matches!(
+            repo.update(Pokemon::pikachu(), Some(vec![1]), &Actor::ci()),
+            Err(UpdateError::Stale(2))
+        ));
+        match repo.fetch_one(PokemonNumber::pikachu(), FormSlug::alola()) {
+            Ok(alola) => assert_eq!(alola.revision, Pokemon::FIRST_REVISION),
+            _ => unreachable!(),
+        }
+
+        assert!(matches!(
+            repo.delete_pokemon(
+                PokemonNumber::pikachu(),
+                FormSlug::default(),
+                Some(vec![1]),
+                &Actor::ci()
+            ),
+            Err(DeleteError::Stale(2))
+        ));
+        assert!(repo
+            .delete_pokemon(
+                PokemonNumber::pikachu(),
+                FormSlug::default(),
+                Some(vec![1, 2]),
+                &Actor::ci()
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn it_should_record_every_change_in_an_immutable_audit_log() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(
+            Pokemon::pikachu().with_form(FormSlug::alola()),
+            &Actor::ci(),
+        );
+        let _ = repo.update(
+            Pokemon::pikachu().with_stats(Some(BaseStats::pikachu())),
+            None,
+            &Actor::cli(),
+        );
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+
+        match repo.fetch_history(PokemonNumber::pikachu()) {
+            Ok(events) => {
+                assert_eq!(
+                    events
+                        .iter()
+                        .map(|e| (e.id, e.action, e.form.clone()))
+                        .collect::<Vec<_>>(),
+                    vec![
+                        (1, AuditAction::Insert, FormSlug::default()),
+                        (2, AuditAction::Insert, FormSlug::alola()),
+                        (3, AuditAction::Update, FormSlug::default()),
+                        (4, AuditAction::Delete, FormSlug::default()),
+                        (5, AuditAction::Delete, FormSlug::alola()),
+                    ]
+                );
+                assert_eq!(events[2].actor, Actor::cli().name);
+                assert_eq!(events[2].request_id, None);
+                assert_eq!(events[2].before.as_ref().map(|p| p.stats), Some(None));
+                assert_eq!(
+                    events[2].after.as_ref().and_then(|p| p.stats),
+                    Some(BaseStats::pikachu())
+                );
+                assert_eq!(events[3].before.as_ref().map(|p| p.revision), Some(2));
+                assert_eq!(events[3].request_id.as_deref(), Some("req-1"));
+            }
+            _ => unreachable!(),
+        }
+        match repo.fetch_audit(Some(String::from("2000-01-01T00:00:00.000Z")), 2) {
+            Ok(events) => assert_eq!(events.iter().map(|e| e.id).collect::<Vec<_>>(), [1, 2]),
+            _ => unreachable!(),
+        }
+
+        let tampered = repo.connection.lock().map(|lock| {
+            (
+                lock.execute("update audit_events set actor = 'someone'", []),
+                lock.execute("delete from audit_events", []),
+            )
+        });
+        assert!(matches!(tampered, Ok((Err(_), Err(_)))));
+    }
+
+    #[test]
+    fn it_should_find_a_stored_key_by_the_hash_of_its_secret() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let _ = repo.insert_key(ApiKey::editor());
+        assert!(matches!(
+            repo.insert_key(ApiKey::editor()),
+            Err(InsertError::Conflict)
+        ));
+        let _ = repo.revoke_key(ApiKey::editor().name);
+
+        match repo.fetch_key(ApiKey::hash("pdx_editor")) {
+            Ok(key) => {
+                assert_eq!(key.name, ApiKey::editor().name);
+                assert_eq!(key.role, Role::Editor);
+                assert!(key.revoked);
+            }
+            _ => unreachable!(),
+        }
+        assert!(matches!(
+            repo.fetch_key(ApiKey::hash("pdx_other")),
+            Err(RetrieveError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn it_should_keep_the_sqlite_error_as_the_source_of_an_unknown_error() {
+        let repo = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let _ = repo
+            .connection
+            .lock()
+            .map(|lock| lock.execute_batch("drop table types; drop table pokemons;"));
 
-    while let Ok(Some(row)) = rows.next() {
-        match row.get::<usize, String>(0) {
-            Ok(name) => type_rows.push(name),
-            _ => return Err(()),
+        match repo.fetch_all(FetchAllQuery::default()) {
+            Err(e @ RetrieveAllError::Unknown(_)) => {
+                let source = e.source().map(|source| source.to_string());
+                assert!(source.is_some_and(|source| source.contains("no such table")));
+            }
+            _ => unreachable!(),
         }
     }
 
-    Ok(type_rows)
+    /// Run with `cargo test --release -- --ignored --nocapture bench_` to track
+    /// the latency of listing the whole national dex.
+    #[test]
+    #[ignore]
+    fn bench_fetch_all_full_dex() {
+        let repo = full_dex();
+        let iterations = 100;
+
+        let start = Instant::now();
+        for _ in 0..iterations {
+            match repo.fetch_all(FetchAllQuery::default()) {
+                Ok(pokemons) => assert_eq!(pokemons.len(), 898),
+                _ => unreachable!(),
+            }
+        }
+        let elapsed = start.elapsed();
+
+        println!(
+            "fetch_all (898 pokemons): {:?} per call over {} iterations",
+            elapsed / iterations,
+            iterations
+        );
+    }
 }
@@ -0,0 +1,309 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::domain::entities::{
+    Ability, AbilityName, Actor, ApiKey, AuditEvent, DeletedPokemon, Evolution, FormSlug, KeyName,
+    LearnsetEntry, Move, MoveName, Pokemon, PokemonNumber,
+};
+use crate::events::{EventKind, Events};
+use crate::repositories::pokemon::{
+    DeleteError, FetchAllQuery, ImportError, ImportOutcome, InsertError, LearnsetQuery, OnConflict,
+    Repository, RestoreError, RetrieveAllError, RetrieveError, UpdateError,
+};
+
+/// Publishes every change to a Pokemon committed through the repository it
+/// wraps, whichever it is, once the repository has answered. The changes go
+/// through one at a time, so that their events are published in the order
+/// they were committed.
+pub struct PublishingRepository {
+    inner: Arc<dyn Repository>,
+    events: Arc<Events>,
+    writes: Mutex<()>,
+}
+
+impl PublishingRepository {
+    pub fn new(inner: Arc<dyn Repository>, events: Arc<Events>) -> Self {
+        Self {
+            inner,
+            events,
+            writes: Mutex::new(()),
+        }
+    }
+
+    /// Commits a change and publishes it before the next change can start. A
+    /// change that panicked committed nothing more, so the lock is still used.
+    fn serialized<T>(&self, change: impl FnOnce() -> T) -> T {
+        let _write = self.writes.lock().unwrap_or_else(PoisonError::into_inner);
+        change()
+    }
+
+    fn publish(&self, kind: EventKind, pokemons: &[Pokemon]) {
+        for pokemon in pokemons {
+            self.events.publish(kind, pokemon.clone());
+        }
+    }
+}
+
+impl Repository for PublishingRepository {
+    fn insert(&self, pokemon: Pokemon, actor: &Actor) -> Result<Pokemon, InsertError> {
+        self.serialized(|| {
+            let inserted = self.inner.insert(pokemon, actor)?;
+            self.events.publish(EventKind::Created, inserted.clone());
+            Ok(inserted)
+        })
+    }
+
+    fn fetch_all(&self, query: FetchAllQuery) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        self.inner.fetch_all(query)
+    }
+
+    fn fetch_one(&self, number: PokemonNumber, form: FormSlug) -> Result<Pokemon, RetrieveError> {
+        self.inner.fetch_one(number, form)
+    }
+
+    fn search(&self, query: String, limit: u32) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        self.inner.search(query, limit)
+    }
+
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        self.inner.fetch_forms(number)
+    }
+
+    fn fetch_ability_holders(&self, name: AbilityName) -> Result<Vec<Pokemon>, RetrieveAllError> {
+        self.inner.fetch_ability_holders(name)
+    }
+
+    fn delete_pokemon(
+        &self,
+        number: PokemonNumber,
+        form: FormSlug,
+        expected: Option<Vec<u32>>,
+        actor: &Actor,
+    ) -> Result<Vec<Pokemon>, DeleteError> {
+        self.serialized(|| {
+            let deleted = self.inner.delete_pokemon(number, form, expected, actor)?;
+            self.publish(EventKind::Deleted, &deleted);
+            Ok(deleted)
+        })
+    }
+
+    fn fetch_trash(&self) -> Result<Vec<DeletedPokemon>, RetrieveAllError> {
+        self.inner.fetch_trash()
+    }
+
+    fn restore(
+        &self,
+        number: PokemonNumber,
+        form: FormSlug,
+        actor: &Actor,
+    ) -> Result<Vec<Pokemon>, RestoreError> {
+        self.serialized(|| {
+            let restored = self.inner.restore(number, form, actor)?;
+            self.publish(EventKind::Created, &restored);
+            Ok(restored)
+        })
+    }
+
+    fn purge(&self, before: String, actor: &Actor) -> Result<Vec<Pokemon>, DeleteError> {
+        // The purged forms were announced when they were deleted.
+        self.inner.purge(before, actor)
+    }
+
+    fn update(
+        &self,
+        pokemon: Pokemon,
+        expected: Option<Vec<u32>>,
+        actor: &Actor,
+    ) -> Result<Pokemon, UpdateError> {
+        self.serialized(|| {
+            let updated = self.inner.update(pokemon, expected, actor)?;
+            self.events.publish(EventKind::Updated, updated.clone());
+            Ok(updated)
+        })
+    }
+
+    fn import(
+        &self,
+        pokemons: Vec<Pokemon>,
+        on_conflict: OnConflict,
+        actor: &Actor,
+    ) -> Result<Vec<ImportOutcome>, ImportError> {
+        self.serialized(|| {
+            let outcomes = self.inner.import(pokemons.clone(), on_conflict, actor)?;
+            for (pokemon, outcome) in pokemons.into_iter().zip(&outcomes) {
+                match outcome {
+                    ImportOutcome::Inserted(revision) => self
+                        .events
+                        .publish(EventKind::Created, pokemon.with_revision(*revision)),
+                    ImportOutcome::Replaced(revision) => self
+                        .events
+                        .publish(EventKind::Updated, pokemon.with_revision(*revision)),
+                    _ => {}
+                }
+            }
+            Ok(outcomes)
+        })
+    }
+
+    fn fetch_history(&self, number: PokemonNumber) -> Result<Vec<AuditEvent>, RetrieveAllError> {
+        self.inner.fetch_history(number)
+    }
+
+    fn fetch_audit(
+        &self,
+        since: Option<String>,
+        limit: u32,
+    ) -> Result<Vec<AuditEvent>, RetrieveAllError> {
+        self.inner.fetch_audit(since, limit)
+    }
+
+    fn insert_ability(&self, ability: Ability) -> Result<Ability, InsertError> {
+        self.inner.insert_ability(ability)
+    }
+
+    fn fetch_abilities(&self) -> Result<Vec<Ability>, RetrieveAllError> {
+        self.inner.fetch_abilities()
+    }
+
+    fn fetch_ability(&self, name: AbilityName) -> Result<Ability, RetrieveError> {
+        self.inner.fetch_ability(name)
+    }
+
+    fn insert_evolution(&self, evolution: Evolution) -> Result<Evolution, InsertError> {
+        self.inner.insert_evolution(evolution)
+    }
+
+    fn fetch_evolutions(&self) -> Result<Vec<Evolution>, RetrieveAllError> {
+        self.inner.fetch_evolutions()
+    }
+
+    fn insert_move(&self, pokemon_move: Move) -> Result<Move, InsertError> {
+        self.inner.insert_move(pokemon_move)
+    }
+
+    fn fetch_moves(&self) -> Result<Vec<Move>, RetrieveAllError> {
+        self.inner.fetch_moves()
+    }
+
+    fn fetch_move(&self, name: MoveName) -> Result<Move, RetrieveError> {
+        self.inner.fetch_move(name)
+    }
+
+    fn insert_learnset_entry(
+        &self,
+        number: PokemonNumber,
+        entry: LearnsetEntry,
+    ) -> Result<LearnsetEntry, InsertError> {
+        self.inner.insert_learnset_entry(number, entry)
+    }
+
+    fn fetch_learnset(
+        &self,
+        number: PokemonNumber,
+        query: LearnsetQuery,
+    ) -> Result<Vec<LearnsetEntry>, RetrieveAllError> {
+        self.inner.fetch_learnset(number, query)
+    }
+
+    fn insert_key(&self, key: ApiKey) -> Result<ApiKey, InsertError> {
+        self.inner.insert_key(key)
+    }
+
+    fn fetch_keys(&self) -> Result<Vec<ApiKey>, RetrieveAllError> {
+        self.inner.fetch_keys()
+    }
+
+    fn fetch_key(&self, hash: String) -> Result<ApiKey, RetrieveError> {
+        self.inner.fetch_key(hash)
+    }
+
+    fn revoke_key(&self, name: KeyName) -> Result<(), DeleteError> {
+        self.inner.revoke_key(name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_publish_every_committed_change() {
+        let events = Arc::new(Events::default());
+        let repo = PublishingRepository::new(Arc::new(InMemoryRepository::new()), events.clone());
+
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+        let _ = repo.import(
+            vec![Pokemon::charmander(), Pokemon::pikachu()],
+            OnConflict::Replace,
+            &Actor::ci(),
+        );
+        let _ = repo.delete_pokemon(
+            PokemonNumber::pikachu(),
+            FormSlug::default(),
+            None,
+            &Actor::ci(),
+        );
+        let _ = repo.restore(PokemonNumber::pikachu(), FormSlug::default(), &Actor::ci());
+
+        assert_eq!(
+            events
+                .wait(0, Duration::ZERO)
+                .into_iter()
+                .map(|e| (e.kind, u16::from(e.pokemon.number), e.pokemon.revision))
+                .collect::<Vec<_>>(),
+            vec![
+                (EventKind::Created, 25, 1),
+                (EventKind::Created, 4, 1),
+                (EventKind::Updated, 25, 2),
+                (EventKind::Deleted, 25, 2),
+                (EventKind::Created, 25, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_publish_concurrent_updates_in_the_order_of_their_revisions() {
+        let events = Arc::new(Events::default());
+        let repo = Arc::new(PublishingRepository::new(
+            Arc::new(InMemoryRepository::new()),
+            events.clone(),
+        ));
+        let _ = repo.insert(Pokemon::pikachu(), &Actor::ci());
+
+        let writers = (0..4)
+            .map(|_| {
+                let repo = repo.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        let _ = repo.update(Pokemon::pikachu(), None, &Actor::ci());
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            let _ = writer.join();
+        }
+
+        let revisions = events
+            .wait(0, Duration::ZERO)
+            .into_iter()
+            .map(|e| e.pokemon.revision)
+            .collect::<Vec<_>>();
+        assert_eq!(revisions, (1..=101).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn it_should_not_publish_a_failed_change() {
+        let events = Arc::new(Events::default());
+        let repo = PublishingRepository::new(
+            Arc::new(InMemoryRepository::new().with_error()),
+            events.clone(),
+        );
+
+        assert!(repo.insert(Pokemon::pikachu(), &Actor::ci()).is_err());
+        assert!(events.wait(0, Duration::ZERO).is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+//! Ranking of the Pokemon names matching a search, shared by the repositories
+//! so that they answer the same query in the same order.
+//!
+//! A name is a candidate when it shares a trigram with the query, or contains
+//! it when the query is too short to have any. The candidates are then ranked:
+//! exact matches first, then prefixes, then substrings, then names within a
+//! few typos of the query.
+
+use std::collections::HashSet;
+
+/// The shortest query allowed to match with typos.
+const MIN_FUZZY_LEN: usize = 3;
+
+/// How well a name matches a query, the best rank being the lowest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rank {
+    Exact,
+    Prefix,
+    Substring,
+    /// The number of edits between the query and the name, or its beginning.
+    Fuzzy(usize),
+}
+
+/// The lowercase trigrams of a text, none when it is shorter than three
+/// characters, as the SQLite FTS5 trigram tokenizer splits it.
+pub fn trigrams(text: &str) -> HashSet<String> {
+    let chars = text.trim().to_lowercase().chars().collect::<Vec<_>>();
+    chars
+        .windows(3)
+        .map(|window| window.iter().collect())
+        .collect()
+}
+
+/// Whether a name is one of the candidates of a query.
+pub fn is_candidate(query: &str, name: &str) -> bool {
+    let query_trigrams = trigrams(query);
+    match query_trigrams.is_empty() {
+        true => name.to_lowercase().contains(&query.trim().to_lowercase()),
+        false => !query_trigrams.is_disjoint(&trigrams(name)),
+    }
+}
+
+/// Ranks a candidate, `None` meaning it is too far from the query to be shown.
+pub fn rank(query: &str, name: &str) -> Option<Rank> {
+    let query = query.trim().to_lowercase();
+    let name = name.to_lowercase();
+
+    if name == query {
+        return Some(Rank::Exact);
+    }
+    if name.starts_with(&query) {
+        return Some(Rank::Prefix);
+    }
+    if name.contains(&query) {
+        return Some(Rank::Substring);
+    }
+
+    let len = query.chars().count();
+    if len < MIN_FUZZY_LEN {
+        return None;
+    }
+
+    let beginning = name.chars().take(len).collect::<String>();
+    let distance = edit_distance(&query, &name).min(edit_distance(&query, &beginning));
+    // One typo in a short query, one more every four characters.
+    match distance <= len / 4 + 1 {
+        true => Some(Rank::Fuzzy(distance)),
+        false => None,
+    }
+}
+
+/// Keeps the `limit` best ranked candidates, the lowest number first among
+/// equally ranked ones.
+pub fn best<T>(query: &str, candidates: Vec<(u16, String, T)>, limit: usize) -> Vec<T> {
+    let mut ranked = candidates
+        .into_iter()
+        .filter_map(|(number, name, value)| rank(query, &name).map(|rank| (rank, number, value)))
+        .collect::<Vec<_>>();
+
+    ranked.sort_by_key(|(rank, number, _)| (*rank, *number));
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, _, value)| value)
+        .collect()
+}
+
+/// The Levenshtein distance between two texts, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_should_rank_exact_then_prefix_then_substring_then_fuzzy_matches() {
+        assert_eq!(rank("pikachu", "Pikachu"), Some(Rank::Exact));
+        assert_eq!(rank("char", "Charmander"), Some(Rank::Prefix));
+        assert_eq!(rank("chu", "Raichu"), Some(Rank::Substring));
+        assert_eq!(rank("pikchu", "Pikachu"), Some(Rank::Fuzzy(1)));
+        assert_eq!(rank("charmnader", "Charmander"), Some(Rank::Fuzzy(2)));
+        assert_eq!(rank("pikchu", "Bulbasaur"), None);
+    }
+
+    #[test]
+    fn it_should_only_tolerate_typos_in_long_enough_queries() {
+        assert_eq!(rank("px", "Pikachu"), None);
+        assert!(is_candidate("pi", "Pikachu"));
+        assert!(!is_candidate("px", "Pikachu"));
+    }
+
+    #[test]
+    fn it_should_keep_the_best_candidates_in_rank_order() {
+        let candidates = [
+            (26, "Raichu"),
+            (25, "Pikachu"),
+            (172, "Pichu"),
+            (4, "Charmander"),
+        ]
+        .into_iter()
+        .map(|(number, name)| (number, String::from(name), number))
+        .collect();
+
+        assert_eq!(best("pichu", candidates, 2), vec![172, 25]);
+    }
+}